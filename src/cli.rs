@@ -1,12 +1,20 @@
 use std::path::PathBuf;
 
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
+
+use crate::terminal;
 
 #[derive(Parser)]
 #[command(name = "wt", version, about = "Git worktree manager")]
 pub struct Cli {
     #[command(subcommand)]
     pub command: Option<Command>,
+    /// Outside a repository, use the single repository one directory down
+    #[arg(long, global = true)]
+    pub discover: bool,
+    /// Show git's full error output, including its advice, below wt's error
+    #[arg(long, short = 'v', global = true)]
+    pub verbose: bool,
 }
 
 #[derive(Subcommand)]
@@ -17,13 +25,30 @@ pub enum Command {
         long_about = "Create a worktree for a branch or ref.\n\
             By default, checks out an existing branch or ref.\n\
             Use --create to create a new branch from HEAD, or provide [base] to create from a specific start point.\n\
+            Use --from-base to start it from the configured base branch instead of HEAD \
+            (wt.baseBranch, then base in .wt.toml, then the remote's default branch, \
+            preferring the remote-tracking branch), without making it the upstream. \
+            An explicit [base] wins over --from-base; \
+            if no base can be found, wt warns and branches from HEAD.\n\
             Tags and other non-branch refs check out as detached HEAD.\n\
-            Worktrees are created under ~/.wt/worktrees/<id>/<repo>/.",
-        after_help = "Examples:\n  wt new feat/login\n  wt new -c feat/login\n  wt new -c feat/login develop\n  wt new fix/session-timeout --repo /path/to/repo\n  wt new v1.0"
+            Use --independent to make a separate local clone instead of a linked worktree \
+            (its own .git directory, not shared with the repository).\n\
+            Worktrees are created under ~/.wt/worktrees/<id>/<repo>/ unless --path is given.\n\
+            Use --sparse to check out only the given directories (cone mode) instead of \
+            wt.sparsePaths or the current worktree's sparse-checkout.\n\
+            Auto-links are best-effort: a link that fails is reported and the worktree is kept. \
+            Use --strict-setup to instead remove the worktree (and any branch it created) when setup fails.\n\
+            Use --from-stash-ref with --as to review a stash entry on its own branch; \
+            if the stash does not apply cleanly, the worktree is kept for resolving the conflicts.\n\
+            Use --open to open the worktree in $VISUAL or $EDITOR, or --open=CMD for another command, \
+            after printing its path. With a terminal on stdout, wt waits for the editor and fails if it does; \
+            otherwise the editor is started in the background.",
+        after_help = "Examples:\n  wt new feat/login\n  wt new -c feat/login\n  wt new -c feat/login develop\n  wt new -c feat/login --from-base\n  wt new -c feat/login @{upstream}\n  wt new fix/session-timeout --repo /path/to/repo\n  wt new v1.0\n  wt new --independent feat/login\n  wt new -c feat/login --path ../my-repo-login\n  wt new -c feat/login --copy-untracked='.env*'\n  eval \"$(wt new --cd feat/login)\"\n  wt new --from-stash-ref 'stash@{1}' --as review/patch\n  wt new -c feat/login --open=code\n  wt new -c fix/2.1 origin/release-2.1 --track origin/release-2.1\n  wt new -c feat/login --sparse services/auth,libs/common"
     )]
     New {
         /// Branch name or ref
-        name: String,
+        #[arg(required_unless_present = "from_stash_ref")]
+        name: Option<String>,
         /// Create a new branch instead of checking out an existing ref
         #[arg(short = 'c', long = "create")]
         create: bool,
@@ -33,14 +58,77 @@ pub enum Command {
         /// Repository path
         #[arg(long)]
         repo: Option<PathBuf>,
+        /// Create a separate local clone instead of a linked worktree
+        #[arg(long, conflicts_with = "create")]
+        independent: bool,
+        /// Create the worktree at this path instead of under ~/.wt/worktrees/
+        #[arg(long, value_name = "DIR")]
+        path: Option<PathBuf>,
+        /// Copy gitignored files from the primary worktree, optionally only those matching GLOB
+        #[arg(long, value_name = "GLOB", num_args = 0..=1, require_equals = true)]
+        copy_untracked: Option<Option<String>>,
+        /// Remove the new worktree again if auto-linking or copying fails
+        #[arg(long)]
+        strict_setup: bool,
+        /// Print a `cd '<path>'` command to eval instead of the bare path
+        #[arg(long)]
+        cd: bool,
+        /// Open the new worktree in CMD, or in $VISUAL or $EDITOR
+        #[arg(
+            long,
+            value_name = "CMD",
+            num_args = 0..=1,
+            require_equals = true,
+            overrides_with = "no_open"
+        )]
+        open: Option<Option<String>>,
+        /// Don't open an editor, even with wt.openOnCreate set
+        #[arg(long, overrides_with = "open")]
+        no_open: bool,
+        /// Create the --as branch at a stash entry's base commit and apply the stash there
+        #[arg(
+            long,
+            value_name = "STASH",
+            requires = "as_branch",
+            conflicts_with_all = ["name", "create", "independent"]
+        )]
+        from_stash_ref: Option<String>,
+        /// Branch name for --from-stash-ref
+        #[arg(long = "as", value_name = "BRANCH", requires = "from_stash_ref")]
+        as_branch: Option<String>,
+        /// Set the created branch's upstream to this local or remote-tracking branch
+        #[arg(long, value_name = "REF", requires = "create")]
+        track: Option<String>,
+        /// Leave the created branch without an upstream, even when branching off a remote one
+        #[arg(long, requires = "create", conflicts_with = "track")]
+        no_track: bool,
+        /// Check out only these comma-separated directories (cone-mode sparse checkout)
+        #[arg(
+            long,
+            value_name = "DIR",
+            value_delimiter = ',',
+            conflicts_with = "independent"
+        )]
+        sparse: Vec<String>,
+        /// Branch from the configured base branch instead of HEAD (requires --create)
+        #[arg(long, requires = "create")]
+        from_base: bool,
     },
     /// List worktrees
     #[command(
         visible_alias = "ls",
         long_about = "List worktrees for the current repository.\n\
             The leading '*' marks the active/current worktree.\n\
-            Use --all to list worktrees across all repositories managed under ~/.wt/worktrees/.",
-        after_help = "Examples:\n  wt ls\n  wt ls --repo /path/to/repo\n  wt ls --json\n  wt ls --all\n  wt ls --all --json"
+            Use --all to list worktrees across all repositories managed under ~/.wt/worktrees/, \
+            grouped by repository; it works from any directory, and with --json each entry \
+            carries its repo's name and path (repo, repo_path).\n\
+            Add --repo-filter to narrow --all to repositories whose name matches a glob.\n\
+            Use --sort to order rows by branch, path, or HEAD commit date (newest first); \
+            the primary worktree stays on top, and --json and --by-index numbering follow the same order.\n\
+            Use --remote to list remote-tracking branches instead, with the worktree each is \
+            checked out in; those without one (-) are candidates for `wt new`. \
+            It reads refs as of the last fetch.",
+        after_help = "Examples:\n  wt ls\n  wt ls --repo /path/to/repo\n  wt ls --json\n  wt ls --all\n  wt ls --all --json\n  wt ls --all --repo-filter 'work-*'\n  wt ls --links\n  wt ls --size\n  wt ls --age\n  wt ls --sort committed\n  wt ls --remote"
     )]
     List {
         /// Repository path
@@ -52,6 +140,46 @@ pub enum Command {
         /// List worktrees across all discovered repositories
         #[arg(long)]
         all: bool,
+        /// Show whether each worktree has the configured links (✓ all, ⚠ partial, ✗ none)
+        #[arg(long, conflicts_with = "all")]
+        links: bool,
+        /// Show each worktree's size on disk, not counting the shared git objects
+        #[arg(long, conflicts_with = "all")]
+        size: bool,
+        /// Show how long ago each worktree's HEAD commit was made
+        #[arg(long, conflicts_with = "all")]
+        age: bool,
+        /// With --all, only show repositories whose name matches this glob
+        #[arg(long, value_name = "GLOB", requires = "all", conflicts_with = "repo")]
+        repo_filter: Option<String>,
+        /// Order rows by this key; the primary worktree stays first
+        #[arg(long, value_enum, value_name = "KEY", conflicts_with = "all")]
+        sort: Option<ListSort>,
+        /// List remote branches and which of them have a worktree
+        #[arg(long, conflicts_with_all = ["all", "links", "size", "age", "sort"])]
+        remote: bool,
+    },
+    /// Summarize the state of every worktree in a repository
+    #[command(
+        long_about = "Summarize the state of every worktree in the current repository.\n\
+            Each row shows the branch, whether the worktree is dirty, how far it is ahead of or \
+            behind its upstream (- without one, = when level), and whether the branch is merged \
+            into the base branch or its upstream is gone. A count of each follows on stderr.\n\
+            Always exits 0; use --json and its counts to gate scripts on dirty worktrees.\n\
+            Use --all to cover every repository managed under ~/.wt/worktrees/; a repository \
+            that can't be read is listed with its error.",
+        after_help = "Examples:\n  wt status\n  wt status --repo /path/to/repo\n  wt status --json | jq '.counts.dirty'\n  wt status --all --json"
+    )]
+    Status {
+        /// Repository path
+        #[arg(long, conflicts_with = "all")]
+        repo: Option<PathBuf>,
+        /// Summarize worktrees across all discovered repositories
+        #[arg(long)]
+        all: bool,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
     },
     /// Remove worktrees by name, ref, or path
     #[command(
@@ -59,13 +187,25 @@ pub enum Command {
         long_about = "Remove worktrees by branch name, ref, or worktree root path.\n\
             Tags and other non-branch refs are resolved to detached HEAD worktrees.\n\
             Name lookup requires repository context (current repo or --repo).\n\
-            Also deletes the local branch by default.\n\
-            Use --force to remove dirty worktrees and force-delete the branch.",
-        after_help = "Examples:\n  wt rm feat/login\n  wt rm v1.0\n  wt rm feat/a feat/b feat/c\n  wt rm /Users/me/.wt/worktrees/a3f2/my-repo\n  wt rm feat/login --force"
+            Also deletes the local branch by default, if it is merged into its upstream\n\
+            (or, without one, the remote default branch, or else HEAD).\n\
+            Detached worktrees keep all branches unless --delete-branch names one.\n\
+            Use --force to remove dirty worktrees and force-delete the branch.\n\
+            An independent checkout (`wt new --independent`) is deleted as a whole, its branch \
+            with it; it is refused while it has local changes or unpushed commits unless --force is used.\n\
+            Use --ignore-untracked to treat a worktree whose only changes are untracked files \
+            (build output, say) as clean; those files are removed with it.\n\
+            Use --merged to remove every clean, unlocked worktree in this repository whose branch\n\
+            is merged into the base branch (wt.baseBranch, or else the remote default).\n\
+            Use --dry-run to run the same checks and list what would be removed; it still exits 1 \
+            if any target would be refused.\n\
+            With several targets, a failing one is reported and the rest are still removed; \
+            --fail-fast stops at the first failure instead, leaving later targets in place.",
+        after_help = "Examples:\n  wt rm feat/login\n  wt rm v1.0\n  wt rm feat/a feat/b feat/c\n  wt rm /Users/me/.wt/worktrees/a3f2/my-repo\n  wt rm feat/login --force\n  wt rm feat/login --ignore-untracked\n  wt rm v1.0 --delete-branch release/1.0\n  wt rm --merged\n  wt rm feat/a feat/b --dry-run\n  wt rm feat/a feat/b --fail-fast"
     )]
     Remove {
         /// Branch names, refs, or paths
-        #[arg(required = true)]
+        #[arg(required_unless_present = "merged")]
         names: Vec<String>,
         /// Repository path
         #[arg(long)]
@@ -76,6 +216,95 @@ pub enum Command {
         /// Remove the worktree but keep the branch
         #[arg(long)]
         keep_branch: bool,
+        /// Delete this branch when removing a detached worktree
+        #[arg(long, value_name = "BRANCH", conflicts_with = "keep_branch")]
+        delete_branch: Option<String>,
+        /// Remove every clean worktree whose branch is merged into the base branch
+        #[arg(long, conflicts_with_all = ["names", "force", "delete_branch"])]
+        merged: bool,
+        /// Treat the names as row numbers from this repo's last `wt list`
+        #[arg(long, conflicts_with = "merged")]
+        by_index: bool,
+        /// Run every check and show what would be removed, without removing anything
+        #[arg(long, short = 'n')]
+        dry_run: bool,
+        /// Stop at the first target that cannot be removed
+        #[arg(long)]
+        fail_fast: bool,
+        /// Don't count untracked files as local changes
+        #[arg(long)]
+        ignore_untracked: bool,
+    },
+    /// Rename a worktree's branch
+    #[command(
+        visible_alias = "mv",
+        long_about = "Rename the branch checked out in a worktree, like `git branch -m`.\n\
+            The worktree stays where it is: managed worktree paths don't contain the branch name, \
+            so there is nothing to relocate. Prints the worktree's path.\n\
+            Refuses if the new branch already exists or the old name matches more than one worktree.",
+        after_help = "Examples:\n  wt move feat/logni feat/login\n  wt mv feat/logni feat/login --repo /path/to/repo"
+    )]
+    Move {
+        /// Branch of the worktree to rename
+        old: String,
+        /// New branch name
+        new: String,
+        /// Repository path
+        #[arg(long)]
+        repo: Option<PathBuf>,
+    },
+    /// Tag the commit checked out in a detached worktree
+    #[command(
+        long_about = "Tag the commit checked out in the current worktree, which must be detached.\n\
+            Creates a lightweight tag, or an annotated one with --message. \
+            On a branch, use `git tag` instead.",
+        after_help = "Examples:\n  wt tag v1.2.0\n  wt tag good-build -m \"passes the full suite\"\n  wt tag v1.2.0 --repo /path/to/worktree"
+    )]
+    Tag {
+        /// Tag name
+        name: String,
+        /// Create an annotated tag with this message
+        #[arg(short = 'm', long)]
+        message: Option<String>,
+        /// Worktree path
+        #[arg(long)]
+        repo: Option<PathBuf>,
+    },
+    /// Recreate a deleted branch and a worktree for it
+    #[command(
+        long_about = "Recreate a branch deleted by `wt rm` (or otherwise) and a worktree for it.\n\
+            The branch's last tip comes from wt's record of branches it deleted, \
+            or else from HEAD's reflog (where the branch was when it was last checked out).\n\
+            Shows the commit and asks before restoring; use --yes to skip the prompt.",
+        after_help = "Examples:\n  wt restore feat/login\n  wt restore feat/login --yes"
+    )]
+    Restore {
+        /// Name of the deleted branch
+        branch: String,
+        /// Repository path
+        #[arg(long)]
+        repo: Option<PathBuf>,
+        /// Restore without asking for confirmation
+        #[arg(long, short = 'y')]
+        yes: bool,
+    },
+    /// Remove untracked and ignored files from a worktree
+    #[command(
+        long_about = "Remove untracked and ignored files from a worktree (git clean -xd).\n\
+            By default, only shows what would be removed.\n\
+            Use --force to delete the files.\n\
+            The primary worktree cannot be cleaned.",
+        after_help = "Examples:\n  wt clean feat/login\n  wt clean feat/login --force\n  wt clean v1.0 --repo /path/to/repo"
+    )]
+    Clean {
+        /// Branch name or ref of the worktree to clean
+        name: String,
+        /// Repository path
+        #[arg(long)]
+        repo: Option<PathBuf>,
+        /// Delete the files instead of listing them
+        #[arg(long, short = 'f')]
+        force: bool,
     },
     /// Clean up merged, stale, and orphaned worktrees
     #[command(
@@ -84,54 +313,230 @@ pub enum Command {
             Also prunes stale worktree metadata for missing directories, and removes \
             orphaned worktree directories whose backing repository has been deleted.\n\n\
             Use --gone to also remove worktrees whose upstream tracking branch no longer \
-            exists (e.g. after a squash-merge deleted the remote branch).\n\n\
+            exists (e.g. after a squash-merge deleted the remote branch). \
+            A summary at the end groups removed worktrees by reason.\n\n\
             Use --stale to also remove worktrees whose branch was never pushed \
             (no upstream tracking branch).\n\n\
+            Use --squashed to also remove worktrees whose branch's changes are already in the \
+            base branch as one squashed commit. This is a heuristic (`git cherry` against a \
+            squash of the branch), so it is opt-in and such removals are labeled squash-merged.\n\n\
+            Use --unused to also remove worktrees that no `wt new`, `switch`, or `path` has \
+            handed out within a duration (e.g. 30d), whatever their merge status. \
+            Their branches are kept unless another reason also applies, and worktrees \
+            with no recorded access are left alone.\n\n\
+            Use --older-than to only remove worktrees whose HEAD commit is older than a duration \
+            (e.g. 14d), whatever the reason; newer ones are skipped. Dirty worktrees are \
+            skipped at any age.\n\n\
+            Use --ignore-untracked to prune worktrees whose only changes are untracked files, \
+            which are removed with them.\n\n\
+            Use --keep-branches to remove worktrees without deleting their branches.\n\n\
+            Use --interactive to confirm each removal. It only asks when stdin is a terminal; \
+            otherwise prune runs as usual, and with --dry-run it only lists.\n\n\
+            Use --prune-refs to also run `git remote prune` for the removed branches' remotes \
+            and unset the upstream of any kept branch whose remote branch is then gone.\n\n\
             Use --base to override the auto-detected default branch for merged detection \
             (useful when the base branch is not main/master, or there is no remote).\n\n\
+            Use --report-file to also write a JSON report of the removed worktrees and branches, \
+            orphans, and errors, per repo and in total. Failing to write it is only a warning.\n\n\
             By default, discovers all repos from ~/.wt/worktrees/ and prunes each one. \
-            Use --repo to target a single repository.",
-        after_help = "Examples:\n  wt prune\n  wt prune --gone\n  wt prune --stale\n  wt prune --base develop\n  wt prune --dry-run\n  wt prune --repo /path/to/repo"
+            Use --repo to target specific repositories instead; it can be repeated \
+            or given a glob pattern (e.g. '~/work/*').",
+        after_help = "Examples:\n  wt prune\n  wt prune --gone\n  wt prune --stale\n  wt prune --squashed\n  wt prune --unused 30d\n  wt prune --gone --older-than 14d\n  wt prune --keep-branches\n  wt prune --ignore-untracked\n  wt prune --base develop\n  wt prune --dry-run\n  wt prune --gone --interactive\n  wt prune --repo /path/to/repo\n  wt prune --repo '~/work/*'\n  wt prune --gone --report-file prune.json"
     )]
     Prune {
         /// Show what would be done without doing it
         #[arg(long, short = 'n')]
         dry_run: bool,
+        /// Ask before removing each worktree
+        #[arg(long, short = 'i')]
+        interactive: bool,
         /// Also remove worktrees whose upstream branch is gone
         #[arg(long)]
         gone: bool,
         /// Also remove worktrees whose branch was never pushed
         #[arg(long)]
         stale: bool,
-        /// Repository path (prune only this repo, skip orphan cleanup)
+        /// Also remove worktrees whose branch was squash-merged into the base branch
         #[arg(long)]
-        repo: Option<PathBuf>,
+        squashed: bool,
+        /// Also remove worktrees not accessed through wt within DURATION (s, m, h, d, w)
+        #[arg(long, value_name = "DURATION", value_parser = terminal::parse_duration)]
+        unused: Option<u64>,
+        /// Only remove worktrees whose HEAD commit is older than DURATION (s, m, h, d, w)
+        #[arg(long, value_name = "DURATION", value_parser = terminal::parse_duration)]
+        older_than: Option<u64>,
+        /// Don't count untracked files as local changes
+        #[arg(long)]
+        ignore_untracked: bool,
+        /// Remove worktrees but never delete their branches
+        #[arg(long)]
+        keep_branches: bool,
+        /// Also prune stale remote-tracking refs and tracking config of removed branches
+        #[arg(long)]
+        prune_refs: bool,
+        /// Repository paths or glob patterns (prune only these repos, skip orphan cleanup)
+        #[arg(long, num_args = 1..)]
+        repo: Vec<PathBuf>,
         /// Base branch for merged detection (e.g. develop, trunk)
         #[arg(long)]
         base: Option<String>,
+        /// Also write what was removed, per repo, to PATH as JSON
+        #[arg(long, value_name = "PATH")]
+        report_file: Option<PathBuf>,
+    },
+    /// Check a repository's worktrees for problems wt can't fix itself
+    #[command(
+        long_about = "Check a repository's worktrees for problems wt can't fix itself.\n\
+            Each problem is printed as a tab-separated line: its kind, then the paths involved.\n\
+            same-directory: two worktrees git tracks separately resolve to one directory \
+            (through a symlink or a case-insensitive filesystem); prune leaves them alone.\n\
+            unwritable-root: the worktree root (~/.wt/worktrees or $WT_ROOT) can't be written, \
+            so new worktrees can't be created.\n\
+            Exits 1 if any problem is found.",
+        after_help = "Examples:\n  wt doctor\n  wt doctor --repo /path/to/repo"
+    )]
+    Doctor {
+        /// Repository path
+        #[arg(long)]
+        repo: Option<PathBuf>,
+    },
+    /// List or clean up wt's per-repo lockfiles
+    #[command(
+        long_about = "List the per-repo lockfiles held by running wt commands.\n\
+            Each line shows the state (held or stale), owner pid, command, and repository.\n\
+            A lock is stale when its owner process is no longer running, e.g. after a crash; \
+            stale locks block new and rm for that repository until removed.\n\
+            Use --clean to remove stale locks. Locks held by live processes are never removed.",
+        after_help = "Examples:\n  wt locks\n  wt locks --clean"
+    )]
+    Locks {
+        /// Remove locks whose owner process is no longer running
+        #[arg(long)]
+        clean: bool,
+    },
+    /// Manage wt's internal state
+    #[command(
+        long_about = "Manage the state wt keeps outside your repositories.\n\
+            wt records when each worktree was last handed out by new, switch, or path. \
+            Use `wt state prune` to forget worktrees that no longer exist, \
+            e.g. after removing them with plain git.",
+        after_help = "Examples:\n  wt state prune"
+    )]
+    State {
+        #[command(subcommand)]
+        action: StateCommand,
+    },
+    /// Print worktrees as `branch<TAB>path<TAB>flags` for shell completions
+    #[command(hide = true)]
+    Complete {
+        /// Repository path
+        #[arg(long)]
+        repo: Option<PathBuf>,
     },
     /// Set up shell integration (completions + directory switching)
     #[command(
         long_about = "Set up shell integration.\n\
             Outputs completions and a wrapper function that auto-changes \
             directory after new and switch.",
-        after_help = "Examples:\n  eval \"$(wt init zsh)\"\n  eval \"$(wt init bash)\"\n  wt init fish | source"
+        after_help = "Examples:\n  eval \"$(wt init zsh)\"\n  eval \"$(wt init bash)\"\n  wt init fish | source\n  wt init zsh --write-rc"
     )]
     Init {
         /// Shell to generate integration for
         shell: clap_complete::Shell,
+        /// Append the setup line to ~/.zshrc or ~/.bashrc instead of printing the script (once)
+        #[arg(long)]
+        write_rc: bool,
     },
     /// Print the path to a worktree
     #[command(
         visible_alias = "p",
         long_about = "Print the path to a worktree.\n\
+            Without a name, prints the root of the worktree containing the current directory.\n\
             Looks up by branch name. Tags and other non-branch refs are resolved \
-            to a commit SHA and matched against detached HEAD worktrees.",
-        after_help = "Examples:\n  wt path feat/login\n  wt path v1.0\n  cd \"$(wt p feat/login)\""
+            to a commit SHA and matched against detached HEAD worktrees.\n\
+            With --by-index, the name is a row number from the last `wt list` in this repo \
+            (counting from 1), valid until a worktree is added or removed.",
+        after_help = "Examples:\n  wt path\n  wt path feat/login\n  wt path v1.0\n  wt path --by-index 2\n  cd \"$(wt p feat/login)\""
     )]
     Path {
         /// Branch name, tag, or ref
+        name: Option<String>,
+        /// Repository path
+        #[arg(long, requires = "name")]
+        repo: Option<PathBuf>,
+        /// Treat the name as a row number from this repo's last `wt list`
+        #[arg(long, requires = "name")]
+        by_index: bool,
+    },
+    /// Open a worktree in an editor, the file manager, or tmux
+    #[command(
+        long_about = "Open a worktree in an editor, the file manager, or tmux.\n\
+            Finds the worktree the same way as `wt path`.\n\
+            --with editor (the default) runs $VISUAL or $EDITOR on it, --with files opens it \
+            in the file manager (`open` on macOS, `xdg-open` elsewhere), and --with tmux \
+            switches to a tmux window named after the branch, rooted at the worktree, \
+            creating it if needed. Outside tmux it attaches to a session of that name instead.",
+        after_help = "Examples:\n  wt open feat/login\n  wt open feat/login --with files\n  wt open feat/login --with tmux\n  wt open --by-index 2"
+    )]
+    Open {
+        /// Branch name or ref of the worktree to open
         name: String,
+        /// What to open the worktree with
+        #[arg(long, value_enum, default_value_t = Opener::Editor)]
+        with: Opener,
+        /// Repository path
+        #[arg(long)]
+        repo: Option<PathBuf>,
+        /// Treat the name as a row number from this repo's last `wt list`
+        #[arg(long)]
+        by_index: bool,
+    },
+    /// Show everything wt knows about one worktree
+    #[command(
+        long_about = "Show everything wt knows about one worktree.\n\
+            Path, branch, HEAD commit, upstream with ahead/behind counts, dirty/locked/prunable \
+            state, the base branch and where HEAD forked from it, when the worktree was created \
+            and last handed out by wt, and the state of its linked files.\n\
+            The fork point is a guess: the merge base with the branch prune and diff compare against.",
+        after_help = "Examples:\n  wt info feat/login\n  wt info feat/login --json"
+    )]
+    Info {
+        /// Branch name or ref of the worktree to describe
+        name: String,
+        /// Repository path
+        #[arg(long)]
+        repo: Option<PathBuf>,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print the branch of the worktree containing a path
+    #[command(
+        long_about = "Print the branch of the worktree containing a path.\n\
+            The inverse of `wt path`. Detached worktrees print their short commit SHA.",
+        after_help = "Examples:\n  wt branch-of\n  wt branch-of ~/.wt/worktrees/a3f2b1/myrepo/src"
+    )]
+    BranchOf {
+        /// Path inside a worktree (defaults to the current directory)
+        #[arg(default_value = ".")]
+        path: PathBuf,
+        /// Repository path
+        #[arg(long)]
+        repo: Option<PathBuf>,
+    },
+    /// Show what a worktree's branch changes relative to the base branch
+    #[command(
+        long_about = "Show what a worktree's branch changes relative to the base branch.\n\
+            Prints `git diff --stat <base>...<branch>`, i.e. changes since the branch forked.\n\
+            Looks up the worktree like `wt path`. The base defaults to the remote's default branch.",
+        after_help = "Examples:\n  wt diff feat/login\n  wt diff feat/login --base develop"
+    )]
+    Diff {
+        /// Branch name or ref of the worktree to diff
+        name: String,
+        /// Base branch to diff against (defaults to the remote default branch)
+        #[arg(long)]
+        base: Option<String>,
         /// Repository path
         #[arg(long)]
         repo: Option<PathBuf>,
@@ -145,30 +550,71 @@ pub enum Command {
             If no branch with this name exists and no similar branch exists, creates one from HEAD.\n\
             If a similar branch name exists (possible typo), errors with a suggestion.\n\
             Use --create to skip the typo check and force creation.\n\
-            Non-branch refs (tags, SHAs) are rejected; use `wt new` instead.",
-        after_help = "Examples:\n  wt switch feat/login\n  wt s feat/login\n  wt switch -c feat/new-branch\n  cd \"$(wt switch feat/login)\""
+            Non-branch refs (tags, SHAs) are rejected; use `wt new` instead.\n\
+            Use `-` as the name to go back to the worktree you switched to before the last one, like `cd -`.\n\
+            With no name in a terminal, picks one of this repo's worktrees interactively \
+            (the picker draws on stderr; esc or ctrl-c exits 130 with no output). \
+            Without a terminal, a name is required.\n\
+            Use --shell to open $SHELL in the worktree (with WT_BRANCH and WT_REPO set) \
+            instead of printing its path; exit the shell to return. \
+            Without a terminal on stdout, the path is printed as usual.\n\
+            Use --eval to print shell code instead of the path that changes into the worktree \
+            and exports WT_BRANCH and WT_REPO, for `eval \"$(wt switch <name> --eval)\"`.\n\
+            Otherwise stdout is just the worktree path; use --path-only to also silence \
+            progress messages on stderr (warnings and errors are still shown).",
+        after_help = "Examples:\n  wt switch feat/login\n  wt s feat/login\n  wt switch -c feat/new-branch\n  wt switch feat/login --shell\n  eval \"$(wt switch feat/login --eval)\"\n  wt switch feat/login --path-only\n  wt switch -\n  cd \"$(wt switch)\"\n  cd \"$(wt switch feat/login)\""
     )]
     Switch {
         /// Branch name
-        name: String,
+        name: Option<String>,
         /// Create a new branch, skipping the similar-name check
-        #[arg(short = 'c', long = "create")]
+        #[arg(short = 'c', long = "create", requires = "name")]
         create: bool,
         /// Repository path
         #[arg(long)]
         repo: Option<PathBuf>,
+        /// Open a shell in the worktree instead of printing its path
+        #[arg(long)]
+        shell: bool,
+        /// Print shell code that cds into the worktree and exports WT_BRANCH and WT_REPO
+        #[arg(long, conflicts_with = "shell")]
+        eval: bool,
+        /// Print only the path, without progress messages
+        #[arg(long, conflicts_with = "shell")]
+        path_only: bool,
+        /// Open the worktree in $VISUAL or $EDITOR
+        #[arg(long, overrides_with = "no_open")]
+        open: bool,
+        /// Don't open an editor, even with wt.openOnCreate set
+        #[arg(long, overrides_with = "open")]
+        no_open: bool,
+        /// Treat the name as a row number from this repo's last `wt list`
+        #[arg(long, conflicts_with = "create", requires = "name")]
+        by_index: bool,
     },
     /// Link files from the primary worktree into linked worktrees
     #[command(
         visible_alias = "ln",
         long_about = "Link files from the primary worktree into all linked worktrees.\n\
             Source files must exist in the primary worktree.\n\
-            Correct symlinks are left in place. Conflicts are skipped unless --force is used.",
-        after_help = "Examples:\n  wt link .env .env.local\n  wt link config/.env\n  wt link .env --force\n  wt link --list"
+            Correct symlinks are left in place. Conflicts are skipped unless --force is used.\n\
+            Use --copy where symlinks aren't allowed: it writes plain copies instead, \
+            leaves identical copies in place, and does not record the files as links. \
+            Copies don't follow later edits to the primary; re-run with --force to refresh them.\n\
+            Use --all to link every path listed in the primary's .wtlink manifest (one per line, \
+            # for comments); entries that are invalid or missing are skipped with a warning.\n\
+            Use --repair to fix what --check reports: the configured links that are missing or \
+            replaced are relinked, and correct ones are left alone.\n\
+            Use --check to report configured links that are missing, not symlinks, \
+            point elsewhere, or dangle because the primary's file is gone (exits 1 if any).",
+        after_help = "Examples:\n  wt link .env .env.local\n  wt link config/.env\n  wt link .env --force\n  wt link .env --copy\n  wt link --all\n  wt link --repair\n  wt link --list\n  wt link --check"
     )]
     Link {
         /// Files or directories to link
-        #[arg(required_unless_present = "list", conflicts_with = "list")]
+        #[arg(
+            required_unless_present_any = ["list", "check", "all", "repair"],
+            conflicts_with_all = ["list", "check", "all", "repair"]
+        )]
         files: Vec<String>,
         /// Repository path
         #[arg(long)]
@@ -176,21 +622,46 @@ pub enum Command {
         /// Replace existing destinations that are not correct symlinks
         #[arg(long)]
         force: bool,
+        /// Copy files instead of symlinking them
+        #[arg(long, conflicts_with_all = ["list", "check"])]
+        copy: bool,
+        /// Link every path listed in the repository's .wtlink manifest
+        #[arg(long, conflicts_with_all = ["list", "check"])]
+        all: bool,
+        /// Relink configured links that are missing or wrong, leaving correct ones alone
+        #[arg(long, conflicts_with_all = ["list", "check", "all", "copy", "force"])]
+        repair: bool,
         /// List currently configured links for this repository
         #[arg(long)]
         list: bool,
+        /// Report broken links in linked worktrees, including dangling ones
+        #[arg(long, conflicts_with = "list")]
+        check: bool,
     },
     /// Clone a repository and create the first worktree
     #[command(
         visible_alias = "cl",
         long_about = "Clone a repository and create the first worktree.\n\
             The repository is stored as a bare clone under ~/.wt/repos/.\n\
-            A worktree for the default branch is created under ~/.wt/worktrees/.",
-        after_help = "Examples:\n  wt clone git@github.com:org/repo.git\n  wt clone https://github.com/org/repo"
+            A worktree for the default branch is created under ~/.wt/worktrees/.\n\
+            Both are named after the repository unless a name is given.\n\
+            A name already under ~/.wt/repos/ is refused, including one taken \
+            from the URL (after org1/app, org2/app needs a name of its own).\n\
+            Use --reference to borrow objects from an existing local clone, \
+            and --dissociate to copy them instead of keeping the link.",
+        after_help = "Examples:\n  wt clone git@github.com:org/repo.git\n  wt clone https://github.com/org/repo\n  wt clone https://github.com/org/repo repo-fork\n  wt clone https://github.com/org/repo --reference ~/src/repo\n  wt clone https://github.com/org/repo --reference ~/src/repo --dissociate"
     )]
     Clone {
         /// Repository URL
         url: String,
+        /// Directory name for the clone [default: from the URL]
+        name: Option<String>,
+        /// Borrow objects from an existing local repository
+        #[arg(long, value_name = "REPO")]
+        reference: Option<PathBuf>,
+        /// Copy borrowed objects so the clone does not depend on --reference
+        #[arg(long, requires = "reference")]
+        dissociate: bool,
     },
     /// Remove linked files from linked worktrees
     #[command(
@@ -215,3 +686,29 @@ pub enum Command {
         all: bool,
     },
 }
+
+#[derive(Subcommand)]
+pub enum StateCommand {
+    /// Forget state for worktrees that no longer exist
+    Prune,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ListSort {
+    /// Branch name, detached worktrees last
+    Branch,
+    /// Worktree path
+    Path,
+    /// HEAD commit date, newest first
+    Committed,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Opener {
+    /// $VISUAL or $EDITOR
+    Editor,
+    /// The OS file manager
+    Files,
+    /// A tmux window or session named after the branch
+    Tmux,
+}
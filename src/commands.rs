@@ -1,11 +1,24 @@
+pub mod branch_of;
+pub mod clean;
 pub mod clone;
+pub mod complete;
+pub mod diff;
+pub mod doctor;
+pub mod info;
 pub mod init;
 pub mod link;
 pub mod list;
+pub mod locks;
+pub mod mv;
 pub mod new;
+pub mod open;
 pub mod path;
 pub mod prune;
+pub mod restore;
 pub mod rm;
+pub mod state;
+pub mod status;
 pub mod switch;
+pub mod tag;
 pub mod tui;
 pub mod unlink;
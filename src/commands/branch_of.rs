@@ -0,0 +1,34 @@
+use std::path::Path;
+
+use crate::git::Git;
+use crate::worktree;
+
+pub fn run(path: &Path, repo: Option<&Path>) -> Result<(), String> {
+    let target = path
+        .canonicalize()
+        .map_err(|e| format!("cannot resolve {}: {e}", path.display()))?;
+    let dir = if target.is_dir() {
+        target.as_path()
+    } else {
+        target.parent().unwrap_or(&target)
+    };
+    let repo_root = Git::find_repo(Some(repo.unwrap_or(dir)))?;
+
+    let git = Git::new(&repo_root);
+    let output = git.list_worktrees()?;
+    let worktrees = worktree::parse_porcelain(&output);
+
+    // Nested worktrees are common (e.g. inside the primary checkout), so the
+    // deepest containing worktree wins, same as list's current marker.
+    let not_inside = || format!("not inside a worktree: {}", path.display());
+    let wt_path =
+        worktree::find_current_worktree(worktrees.iter().filter(|wt| !wt.bare), Some(&target))
+            .ok_or_else(not_inside)?;
+    let wt = worktree::find_by_path(&worktrees, &wt_path).ok_or_else(not_inside)?;
+
+    match &wt.branch {
+        Some(branch) => println!("{branch}"),
+        None => println!("{}", wt.head.get(..7).unwrap_or("(detached)")),
+    }
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use std::path::Path;
+
+use crate::git::Git;
+use crate::terminal;
+use crate::worktree;
+
+pub fn run(name: &str, repo: Option<&Path>, force: bool) -> Result<(), String> {
+    let repo_root = Git::find_repo(repo)?;
+
+    let git = Git::new(&repo_root);
+    let output = git.list_worktrees()?;
+    let worktrees = worktree::parse_porcelain(&output);
+
+    let wt = worktree::resolve_named(&worktrees, name, &git)?;
+
+    let target = worktree::canonicalize_or_self(&wt.path);
+
+    if let Some(main_wt) = worktrees.first()
+        && worktree::canonicalize_or_self(&main_wt.path) == target
+    {
+        return Err(format!(
+            "cannot clean the primary worktree: {}",
+            target.display()
+        ));
+    }
+
+    if force {
+        let cwd = std::env::current_dir().and_then(|p| p.canonicalize()).ok();
+        if worktree::is_cwd_inside(&target, cwd.as_deref()) {
+            return Err(format!(
+                "cannot clean {}: current directory is inside the worktree",
+                target.display()
+            ));
+        }
+    }
+
+    let output = git.clean(&target, force)?;
+    let path_display = terminal::tilde_path(&target);
+    if output.is_empty() {
+        eprintln!("nothing to clean ({path_display})");
+        return Ok(());
+    }
+
+    for line in output.lines() {
+        eprintln!("{line}");
+    }
+    if !force {
+        terminal::eprintln_dim("use --force to remove these files");
+    }
+    Ok(())
+}
@@ -1,14 +1,30 @@
-use crate::git::Git;
+use std::path::{Path, PathBuf};
+
+use crate::git::{self, Git};
 use crate::terminal;
 use crate::worktree;
 
-pub fn run(url: &str) -> Result<(), String> {
-    let repo_name = worktree::parse_repo_name(url)
-        .ok_or_else(|| format!("cannot determine repo name from: {url}"))?;
+pub fn run(
+    url: &str,
+    name: Option<&str>,
+    reference: Option<&Path>,
+    dissociate: bool,
+) -> Result<(), String> {
+    let repo_name = match name {
+        Some(name) => validate_name(name)?,
+        None => worktree::parse_repo_name(url)
+            .ok_or_else(|| format!("cannot determine repo name from: {url}"))?,
+    };
+    if let Some(existing) = existing_clone(repo_name) {
+        return Err(format!(
+            "'{repo_name}' is already cloned at {}; pass another name to clone it again",
+            terminal::tilde_path(&existing)
+        ));
+    }
 
     let bare_dest = worktree::create_bare_dest(repo_name)?;
 
-    match clone_into(&bare_dest, url, repo_name) {
+    match clone_into(&bare_dest, url, repo_name, reference, dissociate) {
         Ok(()) => Ok(()),
         Err(e) => {
             worktree::cleanup_dest(&bare_dest);
@@ -17,9 +33,36 @@ pub fn run(url: &str) -> Result<(), String> {
     }
 }
 
-fn clone_into(bare_dest: &std::path::Path, url: &str, repo_name: &str) -> Result<(), String> {
+fn validate_name(name: &str) -> Result<&str, String> {
+    if name.is_empty() || name == "." || name == ".." || name.contains('/') {
+        return Err(format!(
+            "invalid clone name '{name}': must be a single directory name"
+        ));
+    }
+    git::reject_leading_dash("clone name", name)?;
+    Ok(name)
+}
+
+// Bare clones live at ~/.wt/repos/<id>/<name>, so the random id never
+// collides; the name is what a second clone would duplicate.
+fn existing_clone(repo_name: &str) -> Option<PathBuf> {
+    let repos = worktree::repos_root().ok()?;
+    std::fs::read_dir(repos)
+        .ok()?
+        .flatten()
+        .map(|e| e.path().join(repo_name))
+        .find(|p| p.is_dir())
+}
+
+fn clone_into(
+    bare_dest: &Path,
+    url: &str,
+    repo_name: &str,
+    reference: Option<&Path>,
+    dissociate: bool,
+) -> Result<(), String> {
     eprintln!("cloning {url}");
-    Git::bare_clone(url, bare_dest)?;
+    Git::bare_clone(url, bare_dest, reference, dissociate)?;
 
     let git = Git::new(bare_dest);
 
@@ -27,14 +70,14 @@ fn clone_into(bare_dest: &std::path::Path, url: &str, repo_name: &str) -> Result
     terminal::eprintln_dim("fetching from 'origin'");
     git.fetch_remote("origin")?;
 
-    // best-effort: base_ref() has fallbacks if this fails
+    // best-effort: remote_default_ref() has fallbacks if this fails
     let _ = git.set_remote_head("origin");
 
-    let base = git.base_ref()?;
+    let base = git.remote_default_ref("origin")?;
     let default_branch = base.strip_prefix("origin/").unwrap_or(&base);
 
     let wt_dest = worktree::create_worktree_dest(repo_name)?;
-    if let Err(e) = git.checkout_worktree(default_branch, &wt_dest) {
+    if let Err(e) = git.checkout_worktree(default_branch, &wt_dest, None) {
         worktree::cleanup_dest(&wt_dest);
         return Err(e);
     }
@@ -0,0 +1,38 @@
+use std::path::Path;
+
+use crate::git::Git;
+use crate::terminal;
+use crate::worktree;
+
+// Data source for the bash and fish completions: one `branch\tpath\tflags`
+// line per worktree on a branch, flags comma-separated. Each shell formats
+// its own descriptions, so nothing here is colored or padded.
+pub fn run(repo: Option<&Path>) -> Result<(), String> {
+    let repo_root = Git::find_repo(repo)?;
+    let git = Git::new(&repo_root);
+    let output = git.list_worktrees()?;
+    let worktrees = worktree::parse_porcelain(&output);
+    let primary = worktree::find_primary(&worktrees, &repo_root).map(|wt| wt.path.clone());
+
+    for wt in &worktrees {
+        let Some(branch) = &wt.branch else {
+            continue;
+        };
+        let mut flags = Vec::new();
+        if primary.as_ref() == Some(&wt.path) {
+            flags.push("main");
+        }
+        if wt.locked {
+            flags.push("locked");
+        }
+        if wt.prunable {
+            flags.push("prunable");
+        }
+        println!(
+            "{branch}\t{}\t{}",
+            terminal::tilde_path(&wt.path),
+            flags.join(",")
+        );
+    }
+    Ok(())
+}
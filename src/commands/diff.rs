@@ -0,0 +1,31 @@
+use std::path::Path;
+
+use crate::git::Git;
+use crate::worktree;
+
+pub fn run(name: &str, base: Option<&str>, repo: Option<&Path>) -> Result<(), String> {
+    let repo_root = Git::find_repo(repo)?;
+
+    let git = Git::new(&repo_root);
+    let output = git.list_worktrees()?;
+    let worktrees = worktree::parse_porcelain(&output);
+
+    let wt = worktree::resolve_named(&worktrees, name, &git)?;
+    let head = wt.branch.as_deref().unwrap_or(&wt.head);
+
+    let base = match base {
+        Some(b) if git.rev_parse(b).is_none() => {
+            return Err(format!("base branch '{b}' not found"));
+        }
+        Some(b) => b.to_string(),
+        None => git.base_ref().map_err(|e| format!("{e}, use --base"))?,
+    };
+
+    let stat = git.diff_stat(&base, head)?;
+    if stat.is_empty() {
+        eprintln!("no changes against {base}");
+    } else {
+        println!("{stat}");
+    }
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use std::path::Path;
+
+use crate::git::Git;
+use crate::terminal;
+use crate::worktree;
+
+// Consistency checks that `wt` itself can't repair. Each problem is a
+// tab-separated line on stdout; the error only counts them.
+pub fn run(repo: Option<&Path>) -> Result<(), String> {
+    let repo_root = Git::find_repo(repo)?;
+    let git = Git::new(&repo_root);
+    let output = git.list_worktrees()?;
+    let worktrees = worktree::parse_porcelain(&output);
+
+    let mut problems = 0usize;
+    for group in worktree::canonical_collisions(&worktrees) {
+        let paths: Vec<String> = group
+            .iter()
+            .map(|wt| terminal::tilde_path(&wt.path))
+            .collect();
+        println!("same-directory\t{}", paths.join("\t"));
+        problems += 1;
+    }
+
+    if let Ok(root) = worktree::worktrees_root()
+        && worktree::check_root_writable(&root).is_err()
+    {
+        println!("unwritable-root\t{}", terminal::tilde_path(&root));
+        problems += 1;
+    }
+
+    match problems {
+        0 => {
+            eprintln!("no problems found");
+            Ok(())
+        }
+        1 => Err("found 1 problem".into()),
+        n => Err(format!("found {n} problems")),
+    }
+}
@@ -0,0 +1,158 @@
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+use serde::Serialize;
+
+use crate::commands::link::{self, LinkHealth};
+use crate::config;
+use crate::git::Git;
+use crate::recency;
+use crate::terminal;
+use crate::worktree::{self, Worktree};
+
+#[derive(Serialize)]
+struct Info {
+    path: String,
+    branch: Option<String>,
+    head: String,
+    summary: Option<String>,
+    upstream: Option<String>,
+    ahead: Option<u64>,
+    behind: Option<u64>,
+    locked: bool,
+    prunable: bool,
+    dirty: bool,
+    base: Option<String>,
+    fork_point: Option<String>,
+    created: Option<u64>,
+    last_used: Option<u64>,
+    links: Option<&'static str>,
+}
+
+pub fn run(name: &str, repo: Option<&Path>, json: bool) -> Result<(), String> {
+    let repo_root = Git::find_repo(repo)?;
+    let git = Git::new(&repo_root);
+    let output = git.list_worktrees()?;
+    let worktrees = worktree::parse_porcelain(&output);
+    let wt = worktree::resolve_named(&worktrees, name, &git)?;
+    let info = collect(&git, &repo_root, &worktrees, wt);
+
+    if json {
+        let json_str =
+            serde_json::to_string(&info).map_err(|e| format!("cannot serialize json: {e}"))?;
+        println!("{json_str}");
+    } else {
+        print_info(&info);
+    }
+    Ok(())
+}
+
+fn collect(git: &Git, repo_root: &Path, worktrees: &[Worktree], wt: &Worktree) -> Info {
+    let (dirty, ahead, behind) = if wt.live() {
+        Git::worktree_status(&wt.path)
+    } else {
+        (false, None, None)
+    };
+    // The fork point is a guess: where HEAD meets the base `prune` and `diff`
+    // compare against, which need not be what the branch was created from.
+    let base = git.base_ref().ok();
+    let fork_point = base.as_deref().and_then(|b| git.merge_base(b, &wt.head));
+    let primary = worktree::find_primary(worktrees, repo_root).map(|p| p.path.as_path());
+    let files = config::get_links(repo_root);
+    let links = primary
+        .filter(|primary| !files.is_empty() && *primary != wt.path && wt.live())
+        .map(|primary| link::link_health(&files, primary, &wt.path))
+        .map(LinkHealth::label);
+    let last_used = recency::load().ok().and_then(|r| r.accessed(&wt.path));
+
+    Info {
+        path: wt.path.display().to_string(),
+        branch: wt.branch.clone(),
+        head: wt.head.clone(),
+        summary: git.commit_summary(&wt.head),
+        upstream: wt.branch.as_deref().and_then(|b| git.upstream(b)),
+        ahead,
+        behind,
+        locked: wt.locked,
+        prunable: wt.prunable,
+        dirty,
+        base,
+        fork_point,
+        created: created_at(&wt.path),
+        last_used,
+        links,
+    }
+}
+
+// wt keeps no creation record of its own. Git writes a linked worktree's
+// `commondir` once, when it adds the worktree, so its mtime stands in. The
+// primary worktree has no admin entry and no creation time.
+fn created_at(wt_path: &Path) -> Option<u64> {
+    let admin = worktree::parse_gitdir(&wt_path.join(".git"))?;
+    let modified = std::fs::metadata(admin.join("commondir"))
+        .and_then(|m| m.modified())
+        .ok()?;
+    modified
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .map(|d| d.as_secs())
+}
+
+fn print_info(info: &Info) {
+    let clr = terminal::colors();
+    let row = |label: &str, value: &str| {
+        println!("{}{label:<10}{} {value}", clr.dim, clr.reset);
+    };
+    let ago = |at: u64| terminal::human_age(recency::now().saturating_sub(at));
+
+    row("path", &terminal::tilde_path(Path::new(&info.path)));
+    row(
+        "branch",
+        info.branch.as_deref().unwrap_or("(detached HEAD)"),
+    );
+    row(
+        "head",
+        info.summary
+            .as_deref()
+            .unwrap_or_else(|| info.head.get(..7).unwrap_or("(none)")),
+    );
+    let upstream = match (&info.upstream, info.ahead, info.behind) {
+        (Some(upstream), Some(ahead), Some(behind)) => {
+            format!("{upstream} (ahead {ahead}, behind {behind})")
+        }
+        (Some(upstream), _, _) => upstream.clone(),
+        (None, _, _) => "(none)".into(),
+    };
+    row("upstream", &upstream);
+    let state: Vec<&str> = [
+        (info.dirty, "dirty"),
+        (info.locked, "locked"),
+        (info.prunable, "prunable"),
+    ]
+    .into_iter()
+    .filter_map(|(set, label)| set.then_some(label))
+    .collect();
+    row(
+        "state",
+        &if state.is_empty() {
+            "clean".into()
+        } else {
+            state.join(", ")
+        },
+    );
+    let base = match (&info.base, &info.fork_point) {
+        (Some(base), Some(fork)) => {
+            format!("{base} (forked at {})", fork.get(..7).unwrap_or(fork))
+        }
+        (Some(base), None) => base.clone(),
+        (None, _) => "(unknown)".into(),
+    };
+    row("base", &base);
+    if let Some(created) = info.created {
+        row("created", &ago(created));
+    }
+    row("last used", &info.last_used.map_or("(never)".into(), ago));
+    if let Some(links) = info.links {
+        row("links", links);
+    }
+}
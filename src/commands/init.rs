@@ -1,6 +1,10 @@
+use std::io::Write;
+use std::path::Path;
+
 use clap::CommandFactory;
 
 use crate::cli::Cli;
+use crate::terminal;
 
 const SH_WRAPPER: &str = "wt() {
   if [ $# -eq 0 ]; then
@@ -43,12 +47,153 @@ const FISH_WRAPPER: &str = "function wt --wraps=wt
 end
 ";
 
-pub fn run(shell: clap_complete::Shell) -> Result<(), String> {
+// clap's bash completion knows nothing about branches; this wraps it so the
+// commands that take a worktree name complete from `wt complete`. Bash has no
+// descriptions, so only the branch column is used.
+const BASH_COMPLETION: &str = r#"
+_wt_worktree_branches() {
+    local i repo=()
+    for (( i = 1; i < ${#COMP_WORDS[@]}; i++ )); do
+        case "${COMP_WORDS[i]}" in
+            --repo=*) repo=(--repo "${COMP_WORDS[i]#--repo=}") ;;
+            --repo) repo=(--repo "${COMP_WORDS[i+1]}") ;;
+        esac
+    done
+    [[ ${#repo[@]} -gt 0 ]] && repo[1]="${repo[1]/#\~/$HOME}"
+    command wt complete "${repo[@]}" 2>/dev/null | cut -f1
+}
+
+_wt_dynamic() {
+    local cur="${COMP_WORDS[COMP_CWORD]}" prev="${COMP_WORDS[COMP_CWORD-1]}"
+    case "${COMP_WORDS[1]}" in
+        path|p|switch|s|remove|rm|clean|diff|open|info)
+            if [[ $COMP_CWORD -ge 2 && $cur != -* && $prev != --repo && $prev != --with ]]; then
+                local IFS=$'\n'
+                COMPREPLY=( $(compgen -W "$(_wt_worktree_branches)" -- "$cur") )
+                return 0
+            fi
+            ;;
+    esac
+    _wt "$@"
+}
+
+complete -F _wt_dynamic -o bashdefault -o default wt
+"#;
+
+// Fish shows a description next to each candidate: the worktree's path plus
+// its flags, like the zsh helpers. `new` offers the local branches that have
+// no worktree yet, and nothing once --create makes the name a new one.
+const FISH_COMPLETION: &str = r#"
+function __wt_repo_args
+    set -l tokens (commandline -opc)
+    for i in (seq (count $tokens))
+        switch $tokens[$i]
+            case '--repo=*'
+                printf '%s\n' --repo (string replace -- '--repo=' '' $tokens[$i])
+            case --repo
+                set -l next (math $i + 1)
+                set -q tokens[$next]; and printf '%s\n' --repo $tokens[$next]
+        end
+    end
+end
+
+function __wt_worktree_branches
+    set -l repo (__wt_repo_args)
+    command wt complete $repo 2>/dev/null | while read -l -d \t branch wt_path flags
+        set -l desc $wt_path
+        test -n "$flags"; and set desc "$desc ["(string replace -a , '] [' -- $flags)"]"
+        printf '%s\t%s\n' $branch $desc
+    end
+end
+
+function __wt_new_branches
+    set -l tokens (commandline -opc)
+    if contains -- -c $tokens; or contains -- --create $tokens
+        return
+    end
+    set -l repo (__wt_repo_args)
+    set -l git_dir
+    set -q repo[2]; and set git_dir -C $repo[-1]
+    set -l taken (command wt complete $repo 2>/dev/null | string split -f1 \t)
+    for branch in (command git $git_dir for-each-ref --format='%(refname:short)' refs/heads/ 2>/dev/null)
+        contains -- $branch $taken; or printf '%s\tbranch\n' $branch
+    end
+end
+
+complete -c wt -n "__fish_wt_using_subcommand path p switch s remove rm clean diff open info" -f -a "(__wt_worktree_branches)"
+complete -c wt -n "__fish_wt_using_subcommand new n" -f -a "(__wt_new_branches)"
+"#;
+
+const RC_BEGIN: &str = "# >>> wt shell integration >>>";
+const RC_END: &str = "# <<< wt shell integration <<<";
+
+pub fn run(shell: clap_complete::Shell, write_rc: bool) -> Result<(), String> {
+    if write_rc {
+        return write_rc_block(shell);
+    }
     let script = render(shell)?;
     print!("{script}");
     Ok(())
 }
 
+// The marker lines make a re-run a no-op and tell the user which lines to
+// delete to undo it. The eval goes at the end of the file, after compinit.
+fn write_rc_block(shell: clap_complete::Shell) -> Result<(), String> {
+    let (rc_name, line) = match shell {
+        clap_complete::Shell::Zsh => (".zshrc", "eval \"$(wt init zsh)\""),
+        clap_complete::Shell::Bash => (".bashrc", "eval \"$(wt init bash)\""),
+        clap_complete::Shell::Fish => {
+            eprintln!(
+                "fish needs no rc change, add `wt init fish | source` to ~/.config/fish/config.fish"
+            );
+            return Ok(());
+        }
+        other => return Err(format!("--write-rc does not support {other}")),
+    };
+    let home = std::env::var("HOME")
+        .map_err(|_| "cannot determine home directory: HOME is not set".to_string())?;
+    let dir = match shell {
+        clap_complete::Shell::Zsh => std::env::var("ZDOTDIR")
+            .ok()
+            .filter(|d| !d.is_empty())
+            .unwrap_or(home),
+        _ => home,
+    };
+    let path = Path::new(&dir).join(rc_name);
+
+    let existing = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(format!("cannot read {}: {e}", path.display())),
+    };
+    if existing.lines().any(|l| l.trim() == RC_BEGIN) {
+        eprintln!("already set up in {}", terminal::tilde_path(&path));
+        return Ok(());
+    }
+
+    let mut block = String::new();
+    if !existing.is_empty() {
+        block.push_str(if existing.ends_with('\n') {
+            "\n"
+        } else {
+            "\n\n"
+        });
+    }
+    block.push_str(&format!("{RC_BEGIN}\n{line}\n{RC_END}\n"));
+
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .and_then(|mut f| f.write_all(block.as_bytes()))
+        .map_err(|e| format!("cannot write {}: {e}", path.display()))?;
+    eprintln!(
+        "added wt shell integration to {}, restart your shell to use it",
+        terminal::tilde_path(&path)
+    );
+    Ok(())
+}
+
 fn render(shell: clap_complete::Shell) -> Result<String, String> {
     let mut out = Vec::new();
     clap_complete::generate(shell, &mut Cli::command(), "wt", &mut out);
@@ -479,6 +624,14 @@ _wt_unlink_files() {
         const NEW_BASE_TARGET: &str =
             "::base -- Start point for created branch (requires --create):_default";
         const NAMES_TARGET: &str = "*::names -- Branch names, refs, or paths:_default";
+        const CLEAN_NAME_TARGET: &str =
+            ":name -- Branch name or ref of the worktree to clean:_default";
+        const DIFF_NAME_TARGET: &str =
+            ":name -- Branch name or ref of the worktree to diff:_default";
+        const OPEN_NAME_TARGET: &str =
+            ":name -- Branch name or ref of the worktree to open:_default";
+        const INFO_NAME_TARGET: &str =
+            ":name -- Branch name or ref of the worktree to describe:_default";
         const PRUNE_BASE_TARGET: &str =
             "--base=[Base branch for merged detection (e.g. develop, trunk)]:BASE:_default";
         for (label, target) in [
@@ -489,6 +642,10 @@ _wt_unlink_files() {
             ("new name", NEW_NAME_TARGET),
             ("new base", NEW_BASE_TARGET),
             ("remove names", NAMES_TARGET),
+            ("clean name", CLEAN_NAME_TARGET),
+            ("diff name", DIFF_NAME_TARGET),
+            ("open name", OPEN_NAME_TARGET),
+            ("info name", INFO_NAME_TARGET),
             ("prune base", PRUNE_BASE_TARGET),
         ] {
             if !script.contains(target) {
@@ -515,6 +672,22 @@ _wt_unlink_files() {
             NAMES_TARGET,
             "*::names -- Branch names, refs, or paths:_wt_remove_targets",
         );
+        script = script.replace(
+            CLEAN_NAME_TARGET,
+            ":name -- Branch name or ref of the worktree to clean:_wt_path_branches",
+        );
+        script = script.replace(
+            DIFF_NAME_TARGET,
+            ":name -- Branch name or ref of the worktree to diff:_wt_path_branches",
+        );
+        script = script.replace(
+            OPEN_NAME_TARGET,
+            ":name -- Branch name or ref of the worktree to open:_wt_path_branches",
+        );
+        script = script.replace(
+            INFO_NAME_TARGET,
+            ":name -- Branch name or ref of the worktree to describe:_wt_path_branches",
+        );
         script = script.replace(
             PRUNE_BASE_TARGET,
             "--base=[Base branch for merged detection (e.g. develop, trunk)]:BASE:_wt_prune_base",
@@ -530,10 +703,15 @@ _wt_unlink_files() {
     }
 
     match shell {
-        clap_complete::Shell::Zsh | clap_complete::Shell::Bash => {
+        clap_complete::Shell::Zsh => script.push_str(SH_WRAPPER),
+        clap_complete::Shell::Bash => {
+            script.push_str(BASH_COMPLETION);
             script.push_str(SH_WRAPPER);
         }
-        clap_complete::Shell::Fish => script.push_str(FISH_WRAPPER),
+        clap_complete::Shell::Fish => {
+            script.push_str(FISH_COMPLETION);
+            script.push_str(FISH_WRAPPER);
+        }
         _ => {}
     }
 
@@ -590,6 +768,42 @@ mod tests {
         assert!(script.contains("and cd -- $out"));
     }
 
+    #[test]
+    fn fish_completion_describes_worktrees() {
+        let script = render(clap_complete::Shell::Fish).unwrap();
+        assert!(script.contains("function __wt_worktree_branches"));
+        assert!(script.contains("command wt complete $repo"));
+        assert!(script.contains("read -l -d \\t branch wt_path flags"));
+        assert!(script.contains("printf '%s\\t%s\\n' $branch $desc"));
+        assert!(script.contains(
+            "-n \"__fish_wt_using_subcommand path p switch s remove rm clean diff open info\" -f -a \"(__wt_worktree_branches)\""
+        ));
+    }
+
+    #[test]
+    fn fish_completion_offers_free_branches_for_new() {
+        let script = render(clap_complete::Shell::Fish).unwrap();
+        assert!(script.contains("function __wt_new_branches"));
+        assert!(script.contains("contains -- -c $tokens; or contains -- --create $tokens"));
+        assert!(script.contains("for-each-ref --format='%(refname:short)' refs/heads/"));
+        assert!(
+            script
+                .contains("-n \"__fish_wt_using_subcommand new n\" -f -a \"(__wt_new_branches)\"")
+        );
+        let helper = script.find("function __wt_new_branches").unwrap();
+        assert!(script.find("(__wt_new_branches)\"").unwrap() > helper);
+    }
+
+    #[test]
+    fn bash_completion_wraps_clap_completer() {
+        let script = render(clap_complete::Shell::Bash).unwrap();
+        assert!(script.contains("_wt_dynamic()"));
+        assert!(script.contains("command wt complete \"${repo[@]}\""));
+        assert!(script.contains("local IFS=$'\\n'"));
+        let clap = script.find("complete -F _wt -o").unwrap();
+        assert!(script.find("complete -F _wt_dynamic").unwrap() > clap);
+    }
+
     #[test]
     fn zsh_completion_is_dynamic() {
         let script = render(clap_complete::Shell::Zsh).unwrap();
@@ -650,6 +864,32 @@ mod tests {
                 .count(),
             2
         );
+        assert_eq!(
+            script
+                .matches(":name -- Branch name or ref of the worktree to clean:_wt_path_branches")
+                .count(),
+            1
+        );
+        assert_eq!(
+            script
+                .matches(":name -- Branch name or ref of the worktree to diff:_wt_path_branches")
+                .count(),
+            1
+        );
+        assert_eq!(
+            script
+                .matches(":name -- Branch name or ref of the worktree to open:_wt_path_branches")
+                .count(),
+            1
+        );
+        assert_eq!(
+            script
+                .matches(
+                    ":name -- Branch name or ref of the worktree to describe:_wt_path_branches"
+                )
+                .count(),
+            1
+        );
         assert_eq!(
             script
                 .matches(
@@ -663,6 +903,10 @@ mod tests {
         assert!(!script.contains("Branch name or ref:_default"));
         assert!(!script.contains("Start point for created branch (requires --create):_default"));
         assert!(!script.contains("Branch names, refs, or paths:_default"));
+        assert!(!script.contains("Branch name or ref of the worktree to clean:_default"));
+        assert!(!script.contains("Branch name or ref of the worktree to diff:_default"));
+        assert!(!script.contains("Branch name or ref of the worktree to open:_default"));
+        assert!(!script.contains("Branch name or ref of the worktree to describe:_default"));
         assert!(
             !script
                 .contains("Base branch for merged detection (e.g. develop, trunk)]:BASE:_default")
@@ -2,11 +2,36 @@ use std::path::Path;
 
 use crate::config;
 use crate::git::Git;
+use crate::terminal;
 use crate::worktree;
 
-pub fn run(files: &[String], repo: Option<&Path>, force: bool, list: bool) -> Result<(), String> {
+const MANIFEST_FILE: &str = ".wtlink";
+
+// Which files `wt link` works on.
+#[derive(Clone, Copy)]
+pub enum Files<'a> {
+    // Named on the command line, and recorded in the link config.
+    Named(&'a [String]),
+    // Every entry of the `.wtlink` manifest (`--all`).
+    Manifest,
+    // The recorded link config, replacing only what has drifted (`--repair`).
+    Recorded,
+}
+
+pub fn run(
+    set: Files,
+    repo: Option<&Path>,
+    force: bool,
+    copy: bool,
+    list: bool,
+    check: bool,
+) -> Result<(), String> {
     let repo_root = Git::find_repo(repo)?;
 
+    if check {
+        return check_links(&repo_root);
+    }
+
     if list {
         let links = config::get_links(&repo_root);
         if links.is_empty() {
@@ -26,15 +51,55 @@ pub fn run(files: &[String], repo: Option<&Path>, force: bool, list: bool) -> Re
     let primary = worktree::find_primary(&worktrees, &repo_root).ok_or("no worktrees found")?;
     let primary_path = &primary.path;
 
-    for file in files {
-        validate_path(file)?;
-        let source = primary_path.join(file);
-        if !source.exists() {
-            return Err(format!("not found in primary worktree: {file}"));
+    let repair = matches!(set, Files::Recorded);
+    // Repair is `--force` limited to the recorded links: correct links are
+    // left alone by the loop below, anything else in their place is replaced.
+    let force = force || repair;
+    let mut skipped = 0usize;
+    let files = if let Files::Named(files) = set {
+        for file in files {
+            validate_path(file)?;
+            let source = primary_path.join(file);
+            if !source.exists() {
+                return Err(format!("not found in primary worktree: {file}"));
+            }
         }
-    }
+        files.to_vec()
+    } else {
+        let listed = if repair {
+            let links = config::get_links(&repo_root);
+            if links.is_empty() {
+                eprintln!("no links configured");
+                return Ok(());
+            }
+            links
+        } else {
+            read_manifest(primary_path)?
+        };
+        // An entry that can't be linked is reported and passed over so one
+        // stale line doesn't block the rest.
+        let mut usable = Vec::new();
+        for file in listed {
+            if let Err(e) = validate_path(&file) {
+                eprintln!("skipped {file}: {e}");
+                skipped += 1;
+            } else if !primary_path.join(&file).exists() {
+                eprintln!("skipped {file}: not found in primary worktree");
+                skipped += 1;
+            } else {
+                usable.push(file);
+            }
+        }
+        usable
+    };
 
-    if let Err(e) = config::add_links(&repo_root, files) {
+    // Copies are snapshots, not links: recording them would make `--check`
+    // flag them and auto-link symlink them into new worktrees. Manifest and
+    // recorded entries are already kept elsewhere.
+    if !copy
+        && matches!(set, Files::Named(_))
+        && let Err(e) = config::add_links(&repo_root, &files)
+    {
         eprintln!("cannot save link config: {e}");
     }
 
@@ -47,17 +112,31 @@ pub fn run(files: &[String], repo: Option<&Path>, force: bool, list: bool) -> Re
         return Ok(());
     }
 
+    let mut done = 0usize;
+    let progress = terminal::Progress::new(linked.len());
     for wt in &linked {
-        for file in files {
+        progress.tick(
+            wt.branch
+                .as_deref()
+                .unwrap_or(&worktree::repo_basename(&wt.path)),
+        );
+        for file in &files {
             let source = primary_path.join(file);
             let dest = wt.path.join(file);
 
             if dest.symlink_metadata().is_ok() {
-                if is_expected_link(&dest, &source) {
+                let in_place = if copy {
+                    is_identical_copy(&dest, &source)
+                } else {
+                    is_expected_link(&dest, &source)
+                };
+                if in_place {
                     continue;
                 }
                 if !force {
+                    progress.clear();
                     eprintln!("skipped {file} ({}): already exists", wt.path.display());
+                    skipped += 1;
                     continue;
                 }
                 remove_dest(&dest)
@@ -69,24 +148,139 @@ pub fn run(files: &[String], repo: Option<&Path>, force: bool, list: bool) -> Re
                     .map_err(|e| format!("cannot create directory {}: {e}", parent.display()))?;
             }
 
-            symlink(&source, &dest)
-                .map_err(|e| format!("cannot link {} in {}: {e}", file, wt.path.display()))?;
-            eprintln!("linked {file} ({})", wt.path.display());
+            if repair {
+                symlink(&source, &dest)
+                    .map_err(|e| format!("cannot repair {} in {}: {e}", file, wt.path.display()))?;
+                progress.clear();
+                eprintln!("repaired {file} ({})", wt.path.display());
+            } else if copy {
+                copy_path(&source, &dest)
+                    .map_err(|e| format!("cannot copy {} to {}: {e}", file, wt.path.display()))?;
+                progress.clear();
+                eprintln!("copied {file} ({})", wt.path.display());
+            } else {
+                symlink(&source, &dest)
+                    .map_err(|e| format!("cannot link {} in {}: {e}", file, wt.path.display()))?;
+                progress.clear();
+                eprintln!("linked {file} ({})", wt.path.display());
+            }
+            done += 1;
         }
     }
+    progress.clear();
 
+    let verb = match set {
+        Files::Named(_) => None,
+        Files::Recorded => Some("repaired"),
+        Files::Manifest if copy => Some("copied"),
+        Files::Manifest => Some("linked"),
+    };
+    if let Some(verb) = verb {
+        eprintln!("{verb} {done}, skipped {skipped}");
+    }
     Ok(())
 }
 
-pub fn auto_link(repo_root: &Path, worktree_path: &Path, primary_path: &Path) {
+// `.wtlink` in the primary worktree: one relative path per line, with blank
+// lines and `#` comments ignored.
+fn read_manifest(primary: &Path) -> Result<Vec<String>, String> {
+    let path = primary.join(MANIFEST_FILE);
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Err(format!(
+                "no {MANIFEST_FILE} in {}",
+                terminal::tilde_path(primary)
+            ));
+        }
+        Err(e) => return Err(format!("cannot read {}: {e}", path.display())),
+    };
+    Ok(parse_manifest(&content))
+}
+
+fn parse_manifest(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect()
+}
+
+fn check_links(repo_root: &Path) -> Result<(), String> {
     let files = config::get_links(repo_root);
     if files.is_empty() {
-        return;
+        eprintln!("no links configured");
+        return Ok(());
+    }
+
+    let git = Git::new(repo_root);
+    let output = git.list_worktrees()?;
+    let worktrees = worktree::parse_porcelain(&output);
+    let primary = worktree::find_primary(&worktrees, repo_root).ok_or("no worktrees found")?;
+
+    let mut problems = 0usize;
+    for wt in worktrees
+        .iter()
+        .filter(|wt| wt.live() && !wt.bare && wt.path != primary.path)
+    {
+        for file in &files {
+            let source = primary.path.join(file);
+            if let Some(problem) = link_problem(&source, &wt.path.join(file)) {
+                println!("{problem}\t{file}\t{}", terminal::tilde_path(&wt.path));
+                problems += 1;
+            }
+        }
+    }
+
+    match problems {
+        0 => Ok(()),
+        1 => Err("found 1 link problem".into()),
+        n => Err(format!("found {n} link problems")),
+    }
+}
+
+// A dangling link points at the right place but the primary's file is gone;
+// fixing it means restoring the source, not re-running `wt link`.
+fn link_problem(source: &Path, dest: &Path) -> Option<&'static str> {
+    let Ok(meta) = dest.symlink_metadata() else {
+        return source.exists().then_some("missing");
+    };
+    if !meta.file_type().is_symlink() {
+        Some("not-a-link")
+    } else if !is_expected_link(dest, source) {
+        Some("wrong-target")
+    } else if !source.exists() {
+        Some("dangling")
+    } else {
+        None
+    }
+}
+
+// Each failure is reported as it happens; the returned error only counts them
+// so callers can decide whether a partial setup is acceptable.
+pub fn auto_link(
+    repo_root: &Path,
+    worktree_path: &Path,
+    primary_path: &Path,
+    quiet: bool,
+) -> Result<(), String> {
+    // .wt.toml was already validated by the caller; a broken one adds nothing.
+    let mut files = config::get_links(repo_root);
+    for file in config::load_repo(repo_root)
+        .map(|c| c.link)
+        .unwrap_or_default()
+    {
+        if !files.contains(&file) {
+            files.push(file);
+        }
     }
+    let mut failed = 0usize;
 
     for file in &files {
         if let Err(e) = validate_path(file) {
             eprintln!("cannot auto-link {file}: {e}");
+            failed += 1;
             continue;
         }
         let source = primary_path.join(file);
@@ -104,14 +298,22 @@ pub fn auto_link(repo_root: &Path, worktree_path: &Path, primary_path: &Path) {
                 "cannot auto-link {file}, cannot create {}: {e}",
                 parent.display()
             );
+            failed += 1;
             continue;
         }
         if let Err(e) = symlink(&source, &dest) {
             eprintln!("cannot auto-link {file}: {e}");
-        } else {
+            failed += 1;
+        } else if !quiet {
             eprintln!("auto-linked {file}");
         }
     }
+
+    match failed {
+        0 => Ok(()),
+        1 => Err("cannot auto-link 1 file".into()),
+        n => Err(format!("cannot auto-link {n} files")),
+    }
 }
 
 pub(crate) fn validate_path(file: &str) -> Result<(), String> {
@@ -135,6 +337,104 @@ pub(crate) fn is_expected_link(dest: &Path, source: &Path) -> bool {
     std::fs::read_link(dest).is_ok_and(|target| target == *source)
 }
 
+// A copy is current when it is a plain file (or directory tree) with the same
+// contents as the primary's; a symlink never counts, even to the right file.
+fn is_identical_copy(dest: &Path, source: &Path) -> bool {
+    let Ok(meta) = dest.symlink_metadata() else {
+        return false;
+    };
+    if source.is_dir() {
+        if !meta.is_dir() {
+            return false;
+        }
+        let names = |dir: &Path| -> Option<Vec<std::ffi::OsString>> {
+            let mut names: Vec<_> = std::fs::read_dir(dir)
+                .ok()?
+                .map(|e| e.map(|e| e.file_name()))
+                .collect::<Result<_, _>>()
+                .ok()?;
+            names.sort();
+            Some(names)
+        };
+        let (Some(ours), Some(theirs)) = (names(dest), names(source)) else {
+            return false;
+        };
+        ours == theirs
+            && ours
+                .iter()
+                .all(|name| is_identical_copy(&dest.join(name), &source.join(name)))
+    } else {
+        meta.is_file()
+            && matches!(
+                (std::fs::read(dest), std::fs::read(source)),
+                (Ok(a), Ok(b)) if a == b
+            )
+    }
+}
+
+fn copy_path(source: &Path, dest: &Path) -> Result<(), std::io::Error> {
+    if source.is_dir() {
+        std::fs::create_dir_all(dest)?;
+        for entry in std::fs::read_dir(source)? {
+            let entry = entry?;
+            copy_path(&entry.path(), &dest.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        std::fs::copy(source, dest).map(|_| ())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LinkHealth {
+    Linked,
+    Partial,
+    Missing,
+}
+
+impl LinkHealth {
+    pub(crate) fn symbol(self) -> &'static str {
+        match self {
+            Self::Linked => "✓",
+            Self::Partial => "⚠",
+            Self::Missing => "✗",
+        }
+    }
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Linked => "linked",
+            Self::Partial => "partial",
+            Self::Missing => "missing",
+        }
+    }
+}
+
+// Anything at the destination that isn't a working symlink of ours (a copy, a
+// stale or dangling link) counts as partial rather than missing: it needs
+// attention, not just `wt link`.
+pub(crate) fn link_health(files: &[String], primary: &Path, worktree: &Path) -> LinkHealth {
+    let mut linked = 0;
+    let mut present = 0;
+    for file in files {
+        let dest = worktree.join(file);
+        if dest.symlink_metadata().is_ok() {
+            present += 1;
+            let source = primary.join(file);
+            if is_expected_link(&dest, &source) && source.exists() {
+                linked += 1;
+            }
+        }
+    }
+    if linked == files.len() {
+        LinkHealth::Linked
+    } else if present == 0 {
+        LinkHealth::Missing
+    } else {
+        LinkHealth::Partial
+    }
+}
+
 pub(crate) fn remove_dest(dest: &Path) -> Result<(), std::io::Error> {
     if dest
         .symlink_metadata()
@@ -2,14 +2,21 @@ use std::path::{Path, PathBuf};
 
 use serde::Serialize;
 
+use crate::cli::ListSort;
+use crate::commands::link::{self, LinkHealth};
+use crate::config;
+use crate::fuzzy;
 use crate::git::Git;
+use crate::list_index;
 use crate::terminal::{self, Colors, trunc, trunc_tail};
-use crate::worktree::{self, WorktreeInfo};
+use crate::worktree::{self, RepoInfo, Worktree, WorktreeInfo};
 
 #[derive(Serialize)]
 struct WorktreeEntry {
     #[serde(skip_serializing_if = "Option::is_none")]
     repo: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    repo_path: Option<String>,
     name: String,
     path: String,
     branch: Option<String>,
@@ -21,25 +28,86 @@ struct WorktreeEntry {
     dirty: bool,
     ahead: Option<u64>,
     behind: Option<u64>,
+    operation: Option<&'static str>,
     current: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    independent: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    links: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    size: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    age: Option<String>,
+}
+
+// Optional columns between STATUS and PATH; each is computed only when its
+// flag asks for it, one entry per worktree.
+#[derive(Clone, Copy, Default)]
+struct Extra<'a> {
+    links: Option<&'a [Option<LinkHealth>]>,
+    sizes: Option<&'a [Option<u64>]>,
+    ages: Option<&'a [Option<String>]>,
 }
 
-pub fn run(repo: Option<&Path>, json: bool, all: bool) -> Result<(), String> {
+// The optional columns and row order of a single-repo listing.
+#[derive(Clone, Copy)]
+pub struct Options {
+    pub links: bool,
+    pub size: bool,
+    pub age: bool,
+    pub sort: Option<ListSort>,
+}
+
+pub fn run(
+    repo: Option<&Path>,
+    json: bool,
+    all: bool,
+    repo_filter: Option<&str>,
+    opts: Options,
+) -> Result<(), String> {
     if all {
-        return run_all(json);
+        return run_all(json, repo_filter);
     }
+    let Options {
+        links,
+        size,
+        age,
+        sort,
+    } = opts;
 
     let repo_root = Git::find_repo(repo)?;
+    let git = Git::new(&repo_root);
 
-    let output = Git::new(&repo_root).list_worktrees()?;
-    let worktrees = worktree::parse_porcelain(&output);
+    let (mut worktrees, independent) = rows(&git, &repo_root)?;
+    if let Some(key) = sort {
+        sort_rows(&mut worktrees, key);
+    }
+    if let Ok(common_dir) = git.common_dir() {
+        let paths: Vec<PathBuf> = worktrees.iter().map(|wt| wt.path.clone()).collect();
+        list_index::record(&common_dir, &paths);
+    }
     let cwd = resolve_cwd();
     let current_path = worktree::find_current_worktree(&worktrees, cwd.as_deref());
 
-    let infos = worktree::enrich_worktrees(&worktrees, current_path.as_deref());
+    let infos = {
+        let progress = terminal::Progress::new(worktrees.len());
+        worktree::enrich_worktrees(&worktrees, current_path.as_deref(), Some(&progress))
+    };
+    let independent: Vec<PathBuf> = independent.into_iter().map(|wt| wt.path).collect();
+    let link_health = links.then(|| check_links(&repo_root, &worktrees, &independent));
+    let sizes = size.then(|| per_worktree(&worktrees, |path| Some(worktree::dir_size(path))));
+    let ages = age.then(|| per_worktree(&worktrees, Git::commit_age));
 
     if json {
-        let entries = build_json_entries(&infos, None);
+        let mut entries = build_json_entries(&infos, None, &independent);
+        for (i, entry) in entries.iter_mut().enumerate() {
+            entry.links = link_health
+                .as_ref()
+                .and_then(|health| health[i])
+                .map(LinkHealth::label);
+            entry.size = sizes.as_ref().and_then(|sizes| sizes[i]);
+            entry.age = ages.as_ref().and_then(|ages| ages[i].clone());
+        }
         let json_str =
             serde_json::to_string(&entries).map_err(|e| format!("cannot serialize json: {e}"))?;
         println!("{json_str}");
@@ -48,13 +116,177 @@ pub fn run(repo: Option<&Path>, json: bool, all: bool) -> Result<(), String> {
 
     let cols = terminal::width();
     let clr = terminal::colors();
-    print_table(&infos, cols, &clr, "");
+    let extra = Extra {
+        links: link_health.as_deref(),
+        sizes: sizes.as_deref(),
+        ages: ages.as_deref(),
+    };
+    print_table(&infos, cols, &clr, "", &independent, extra);
 
     Ok(())
 }
 
-fn run_all(json: bool) -> Result<(), String> {
-    let repos = worktree::load_all(&worktree::worktrees_root()?)?;
+#[derive(Serialize)]
+struct RemoteEntry {
+    remote: String,
+    branch: String,
+    worktree: Option<String>,
+}
+
+// `--remote`: what could be checked out rather than what is. Each
+// remote-tracking branch is paired with the worktree that has its name
+// checked out, if any; the rest are candidates for `wt new`. This reads refs
+// as of the last fetch and never fetches itself.
+pub fn run_remote(repo: Option<&Path>, json: bool) -> Result<(), String> {
+    let repo_root = Git::find_repo(repo)?;
+    let git = Git::new(&repo_root);
+    let (worktrees, _) = rows(&git, &repo_root)?;
+    let entries: Vec<RemoteEntry> = git
+        .remote_branches()?
+        .into_iter()
+        .map(|(remote, branch)| {
+            let worktree = worktrees
+                .iter()
+                .find(|wt| wt.live() && !wt.bare && wt.branch.as_deref() == Some(&branch))
+                .map(|wt| wt.path.to_string_lossy().into_owned());
+            RemoteEntry {
+                remote,
+                branch,
+                worktree,
+            }
+        })
+        .collect();
+
+    if json {
+        let json_str =
+            serde_json::to_string(&entries).map_err(|e| format!("cannot serialize json: {e}"))?;
+        println!("{json_str}");
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        eprintln!("no remote branches");
+        return Ok(());
+    }
+    let branch_w = entries
+        .iter()
+        .map(|e| e.remote.chars().count() + 1 + e.branch.chars().count())
+        .max()
+        .unwrap_or(0)
+        .clamp(14, 40);
+    let clr = terminal::colors();
+    println!("{:<branch_w$}   WORKTREE", "REMOTE BRANCH");
+    for entry in &entries {
+        let name = trunc(&format!("{}/{}", entry.remote, entry.branch), branch_w);
+        match &entry.worktree {
+            Some(path) => println!(
+                "{name:<branch_w$}   {}{}{}",
+                clr.dim,
+                terminal::tilde_path(Path::new(path)),
+                clr.reset
+            ),
+            None => println!("{name:<branch_w$}   -"),
+        }
+    }
+    let candidates = entries.iter().filter(|e| e.worktree.is_none()).count();
+    match candidates {
+        1 => eprintln!("1 remote branch without a worktree"),
+        n => eprintln!("{n} remote branches without a worktree"),
+    }
+    Ok(())
+}
+
+// Every row `list` prints for a repo, in order: git's worktrees, then the
+// independent clones recorded for it.
+fn rows(git: &Git, repo_root: &Path) -> Result<(Vec<Worktree>, Vec<Worktree>), String> {
+    let output = git.list_worktrees()?;
+    let mut worktrees = worktree::parse_porcelain(&output);
+    let independent = worktree::load_independent(repo_root);
+    worktrees.extend(independent.iter().cloned());
+    Ok((worktrees, independent))
+}
+
+// `--sort` reorders rows before anything is computed or recorded per row, so
+// the table, JSON, and `--by-index` numbering agree. Git lists the primary
+// (or bare) entry first, and it stays there.
+fn sort_rows(worktrees: &mut [Worktree], key: ListSort) {
+    let Some((_, rest)) = worktrees.split_first_mut() else {
+        return;
+    };
+    match key {
+        ListSort::Branch => rest
+            .sort_by(|a, b| (a.branch.is_none(), &a.branch).cmp(&(b.branch.is_none(), &b.branch))),
+        ListSort::Path => rest.sort_by(|a, b| a.path.cmp(&b.path)),
+        ListSort::Committed => {
+            let times = per_worktree(rest, Git::commit_time);
+            let mut keyed: Vec<(Option<u64>, Worktree)> =
+                times.into_iter().zip(rest.iter().cloned()).collect();
+            // Newest first; a worktree without a readable HEAD sorts last.
+            keyed.sort_by_key(|(time, _)| std::cmp::Reverse(*time));
+            for (slot, (_, wt)) in rest.iter_mut().zip(keyed) {
+                *slot = wt;
+            }
+        }
+    }
+}
+
+// `--by-index`: the worktree on row `n` of this repo's last `wt list`.
+pub fn resolve_index(repo: Option<&Path>, n: &str) -> Result<PathBuf, String> {
+    let repo_root = Git::find_repo(repo)?;
+    let git = Git::new(&repo_root);
+    let (worktrees, _) = rows(&git, &repo_root)?;
+    let paths: Vec<PathBuf> = worktrees.into_iter().map(|wt| wt.path).collect();
+    list_index::lookup(&git.common_dir()?, &paths, n)
+}
+
+fn check_links(
+    repo_root: &Path,
+    worktrees: &[Worktree],
+    independent: &[PathBuf],
+) -> Vec<Option<LinkHealth>> {
+    let files = config::get_links(repo_root);
+    let primary = worktree::find_primary(worktrees, repo_root).map(|wt| wt.path.clone());
+    worktrees
+        .iter()
+        .map(|wt| {
+            let primary = primary.as_deref()?;
+            if files.is_empty()
+                || wt.bare
+                || wt.path == primary
+                || worktree::is_independent(independent, &wt.path)
+            {
+                return None;
+            }
+            Some(link::link_health(&files, primary, &wt.path))
+        })
+        .collect()
+}
+
+// Walking every file or running git per worktree is slow on big repos, so
+// each worktree gets its own thread, like the status checks in
+// `enrich_worktrees`. Bare and missing worktrees get `None` without asking.
+fn per_worktree<T: Send>(
+    worktrees: &[Worktree],
+    f: impl Fn(&Path) -> Option<T> + Sync,
+) -> Vec<Option<T>> {
+    let f = &f;
+    std::thread::scope(|s| {
+        let handles: Vec<_> = worktrees
+            .iter()
+            .map(|wt| s.spawn(move || (!wt.bare && wt.live()).then(|| f(&wt.path)).flatten()))
+            .collect();
+        handles
+            .into_iter()
+            .map(|h| h.join().unwrap_or_else(|e| std::panic::resume_unwind(e)))
+            .collect()
+    })
+}
+
+fn run_all(json: bool, repo_filter: Option<&str>) -> Result<(), String> {
+    let mut repos = worktree::load_all(&worktree::worktrees_root()?, true)?;
+    if let Some(pattern) = repo_filter {
+        repos.retain(|repo| fuzzy::glob_match(pattern, &repo.name));
+    }
     if repos.is_empty() {
         if json {
             println!("[]");
@@ -65,7 +297,10 @@ fn run_all(json: bool) -> Result<(), String> {
     if json {
         let entries: Vec<_> = repos
             .iter()
-            .flat_map(|repo| build_json_entries(&repo.worktrees, Some(&repo.name)))
+            .flat_map(|repo| {
+                let independent = config::get_independent(&repo.path);
+                build_json_entries(&repo.worktrees, Some(repo), &independent)
+            })
             .collect();
         let json_str =
             serde_json::to_string(&entries).map_err(|e| format!("cannot serialize json: {e}"))?;
@@ -78,7 +313,15 @@ fn run_all(json: bool) -> Result<(), String> {
                 println!();
             }
             println!("{}{}:{}", clr.bold, repo.name, clr.reset);
-            print_table(&repo.worktrees, cols, &clr, "  ");
+            let independent = config::get_independent(&repo.path);
+            print_table(
+                &repo.worktrees,
+                cols,
+                &clr,
+                "  ",
+                &independent,
+                Extra::default(),
+            );
         }
     }
 
@@ -91,7 +334,11 @@ fn resolve_cwd() -> Option<PathBuf> {
         .and_then(|p| p.canonicalize().ok())
 }
 
-fn build_json_entries(worktrees: &[WorktreeInfo], repo_name: Option<&str>) -> Vec<WorktreeEntry> {
+fn build_json_entries(
+    worktrees: &[WorktreeInfo],
+    repo: Option<&RepoInfo>,
+    independent: &[PathBuf],
+) -> Vec<WorktreeEntry> {
     worktrees
         .iter()
         .map(|wt| {
@@ -99,7 +346,8 @@ fn build_json_entries(worktrees: &[WorktreeInfo], repo_name: Option<&str>) -> Ve
             let branch = wt.branch.clone();
             let name = branch.clone().unwrap_or_else(|| path.clone());
             WorktreeEntry {
-                repo: repo_name.map(|s| s.to_string()),
+                repo: repo.map(|r| r.name.clone()),
+                repo_path: repo.map(|r| r.path.to_string_lossy().into_owned()),
                 name,
                 path,
                 branch,
@@ -111,45 +359,85 @@ fn build_json_entries(worktrees: &[WorktreeInfo], repo_name: Option<&str>) -> Ve
                 dirty: wt.dirty,
                 ahead: wt.ahead,
                 behind: wt.behind,
+                operation: pending_operation(wt),
                 current: wt.current,
+                independent: worktree::is_independent(independent, &wt.path),
+                links: None,
+                size: None,
+                age: None,
             }
         })
         .collect()
 }
 
-fn print_table(worktrees: &[WorktreeInfo], cols: usize, clr: &Colors, indent: &str) {
+fn print_table(
+    worktrees: &[WorktreeInfo],
+    cols: usize,
+    clr: &Colors,
+    indent: &str,
+    independent: &[PathBuf],
+    extra: Extra,
+) {
+    let Extra {
+        links: link_health,
+        sizes,
+        ages,
+    } = extra;
     let cur_w: usize = 1;
-    let branch_min: usize = 14;
-    let branch_max: usize = 24;
     let status_w: usize = 10;
-    let path_min: usize = 24;
     let indent_w = indent.len();
-    let avail = cols.saturating_sub(indent_w + cur_w + status_w + 7);
-
-    let extra = avail.saturating_sub(path_min + branch_min);
-    let branch_w = (branch_min + extra / 8).min(branch_max);
-    let path_w = avail.saturating_sub(branch_w);
+    let links_w: usize = 5;
+    let links_col_w = if link_health.is_some() {
+        links_w + 3
+    } else {
+        0
+    };
+    let size_w: usize = 7;
+    let size_col_w = if sizes.is_some() { size_w + 3 } else { 0 };
+    let age_w: usize = 14;
+    let age_col_w = if ages.is_some() { age_w + 3 } else { 0 };
+    let avail =
+        cols.saturating_sub(indent_w + cur_w + status_w + 7 + links_col_w + size_col_w + age_col_w);
+    let (branch_w, path_w) = branch_and_rest_widths(avail);
 
+    let links_header = if link_health.is_some() {
+        format!("{:<links_w$}   ", "LINKS")
+    } else {
+        String::new()
+    };
+    let size_header = if sizes.is_some() {
+        format!("{:>size_w$}   ", "SIZE")
+    } else {
+        String::new()
+    };
+    let age_header = if ages.is_some() {
+        format!("{:<age_w$}   ", "AGE")
+    } else {
+        String::new()
+    };
     println!(
-        "{indent}{:<cur_w$} {:<branch_w$}   {:<status_w$}   PATH",
+        "{indent}{:<cur_w$} {:<branch_w$}   {:<status_w$}   {links_header}{size_header}{age_header}PATH",
         "", "BRANCH", "STATUS",
     );
 
-    for wt in worktrees {
+    for (i, wt) in worktrees.iter().enumerate() {
         let branch = wt
             .branch
             .as_deref()
             .unwrap_or(if wt.bare { "(bare)" } else { "(detached)" });
         let branch_trunc = trunc(branch, branch_w);
 
-        let status = worktree::format_status(wt.bare, wt.dirty, wt.ahead, wt.behind)
-            .unwrap_or_else(|| "-".into());
+        let status = match pending_operation(wt) {
+            Some(op) => op.to_string(),
+            None => worktree::format_status(wt.bare, wt.dirty, wt.ahead, wt.behind)
+                .unwrap_or_else(|| "-".into()),
+        };
         let status_trunc = trunc(&status, status_w);
 
         let path_str = terminal::tilde_path(&wt.path);
         let path_trunc = trunc_tail(&path_str, path_w);
 
-        let badges = worktree_badges(wt, clr);
+        let badges = worktree_badges(wt, worktree::is_independent(independent, &wt.path), clr);
 
         let branch_pad = branch_w.saturating_sub(branch_trunc.chars().count());
         let branch_color = if wt.current { clr.green } else { "" };
@@ -163,6 +451,36 @@ fn print_table(worktrees: &[WorktreeInfo], cols: usize, clr: &Colors, indent: &s
 
         let cur_col = if wt.current { "*" } else { " " };
 
+        let links_col = match link_health {
+            Some(health) => {
+                let (symbol, color) = match health[i] {
+                    Some(h @ LinkHealth::Linked) => (h.symbol(), clr.green),
+                    Some(h @ LinkHealth::Partial) => (h.symbol(), clr.yellow),
+                    Some(h @ LinkHealth::Missing) => (h.symbol(), clr.red),
+                    None => ("-", ""),
+                };
+                format!("{color}{symbol}{}{}   ", clr.reset, " ".repeat(links_w - 1))
+            }
+            None => String::new(),
+        };
+
+        let size_col = match sizes {
+            Some(sizes) => {
+                let size = sizes[i].map_or_else(|| "-".to_string(), terminal::human_size);
+                format!("{size:>size_w$}   ")
+            }
+            None => String::new(),
+        };
+
+        let age_col = match ages {
+            Some(ages) => {
+                let age = trunc(ages[i].as_deref().unwrap_or("-"), age_w);
+                let pad = age_w.saturating_sub(age.chars().count());
+                format!("{age}{}   ", " ".repeat(pad))
+            }
+            None => String::new(),
+        };
+
         let row_suffix = if badges.is_empty() {
             path_trunc
         } else {
@@ -170,17 +488,39 @@ fn print_table(worktrees: &[WorktreeInfo], cols: usize, clr: &Colors, indent: &s
         };
 
         println!(
-            "{indent}{cur_col} {branch_col}   {:<status_w$}   {row_suffix}",
+            "{indent}{cur_col} {branch_col}   {:<status_w$}   {links_col}{size_col}{age_col}{row_suffix}",
             status_trunc,
         );
     }
 }
 
-fn worktree_badges(wt: &WorktreeInfo, clr: &Colors) -> String {
+// Splits the width left for a row between the branch column and the last one:
+// the branch grows a little with the terminal, up to a cap, and the last
+// column takes the rest.
+pub(crate) fn branch_and_rest_widths(avail: usize) -> (usize, usize) {
+    let branch_min: usize = 14;
+    let branch_max: usize = 24;
+    let rest_min: usize = 24;
+    let extra = avail.saturating_sub(rest_min + branch_min);
+    let branch_w = (branch_min + extra / 8).min(branch_max);
+    (branch_w, avail.saturating_sub(branch_w))
+}
+
+fn pending_operation(wt: &WorktreeInfo) -> Option<&'static str> {
+    if wt.bare || wt.prunable {
+        return None;
+    }
+    worktree::pending_operation(&wt.path)
+}
+
+fn worktree_badges(wt: &WorktreeInfo, independent: bool, clr: &Colors) -> String {
     if wt.bare {
         return String::new();
     }
     let mut badges = Vec::new();
+    if independent {
+        badges.push(format!("{}[independent]{}", clr.dim, clr.reset));
+    }
     if wt.detached {
         badges.push(format!("{}[detached]{}", clr.dim, clr.reset));
     }
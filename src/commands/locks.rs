@@ -0,0 +1,19 @@
+use crate::lock;
+use crate::terminal;
+
+pub fn run(clean: bool) -> Result<(), String> {
+    for info in lock::list()? {
+        let pid = info.pid.map_or("?".to_string(), |p| p.to_string());
+        let repo = terminal::tilde_path(&info.repo);
+        if !info.stale() {
+            println!("held\t{pid}\t{}\t{repo}", info.command);
+        } else if clean {
+            std::fs::remove_file(&info.path)
+                .map_err(|e| format!("cannot remove {}: {e}", info.path.display()))?;
+            eprintln!("removed stale lock for {repo} (pid {pid})");
+        } else {
+            println!("stale\t{pid}\t{}\t{repo}", info.command);
+        }
+    }
+    Ok(())
+}
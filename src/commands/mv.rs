@@ -0,0 +1,33 @@
+use std::path::Path;
+
+use crate::git::Git;
+use crate::lock;
+use crate::worktree;
+
+// Only the branch is renamed: managed paths are `<id>/<repo>`, so the
+// directory never names the branch and moving it would only break shells
+// and editors open in it (see docs/decisions.md).
+pub fn run(old: &str, new: &str, repo: Option<&Path>) -> Result<(), String> {
+    let repo_root = Git::find_repo(repo)?;
+    let git = Git::new(&repo_root);
+    let _lock = lock::acquire(&git, &repo_root, "move")?;
+
+    let output = git.list_worktrees()?;
+    let worktrees = worktree::parse_porcelain(&output);
+    let wt = worktree::resolve_named(&worktrees, old, &git)?;
+    let Some(branch) = wt.branch.as_deref() else {
+        return Err(format!(
+            "'{old}' is a detached worktree, it has no branch to rename"
+        ));
+    };
+
+    git.validate_branch_name(new)?;
+    if git.has_local_branch(new) {
+        return Err(format!("cannot rename to '{new}': branch already exists"));
+    }
+    git.rename_branch(branch, new)?;
+
+    eprintln!("renamed '{branch}' to '{new}'");
+    println!("{}", wt.path.display());
+    Ok(())
+}
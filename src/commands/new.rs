@@ -1,21 +1,105 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use crate::commands::link;
-use crate::git::Git;
+use crate::config;
+use crate::editor;
+use crate::fuzzy;
+use crate::git::{self, Git};
+use crate::hooks;
+use crate::lock;
+use crate::recency;
 use crate::terminal;
 use crate::worktree;
 
+// What `new` does once git has created the worktree, and how it reports it.
+#[derive(Clone, Copy)]
+pub struct Options<'a> {
+    // `Some(None)` copies every ignored file, `Some(Some(glob))` only matches.
+    pub copy_untracked: Option<Option<&'a str>>,
+    pub strict_setup: bool,
+    // Print `cd '<path>'` for a shell wrapper to eval instead of the bare path.
+    pub cd: bool,
+    // `--open`/`--no-open`; `None` follows wt.openOnCreate.
+    pub open: Option<bool>,
+    // `--open=CMD`: the editor command to use instead of $VISUAL/$EDITOR.
+    pub open_with: Option<&'a str>,
+    // Stash entry to apply to a branch created at the stash's base.
+    pub from_stash: Option<&'a str>,
+    // `Some(Some(ref))` sets the created branch's upstream to ref; `Some(None)`
+    // (`--no-track`) leaves it with none, whatever git inferred.
+    pub track: Option<Option<&'a str>>,
+    // `--sparse`: cone-mode directories to check out instead of the full tree.
+    pub sparse: Option<&'a [String]>,
+    // `--from-base`: with no explicit base, branch from the configured base
+    // (as `Git::base_ref` resolves it) instead of HEAD.
+    pub from_base: bool,
+}
+
 pub fn run(
     name: &str,
     create: bool,
     base: Option<&str>,
     repo: Option<&Path>,
+    independent: bool,
+    path: Option<&Path>,
+    opts: Options,
 ) -> Result<(), String> {
     let repo_root = Git::find_repo(repo)?;
     let git = Git::new(&repo_root);
-    let dest = worktree::create_dest(&repo_root, &git)?;
+    let _lock = lock::acquire(&git, &repo_root, "new")?;
+
+    if create {
+        git.validate_branch_name(name)?;
+    } else {
+        git::reject_leading_dash("ref", name)?;
+    }
+    if let Some(base) = base {
+        git::reject_leading_dash("ref", base)?;
+    }
+    config::load_repo(&repo_root)?;
+    let stash = opts
+        .from_stash
+        .map(|s| resolve_stash(&git, s))
+        .transpose()?;
+    let base = stash.as_ref().map_or(base, |(_, b)| Some(b.as_str()));
+    // An explicit base wins over --from-base; an unknown base only costs
+    // the flag, not the worktree.
+    let configured_base = match (base, create && opts.from_base) {
+        (None, true) => git
+            .base_ref()
+            .inspect_err(|e| eprintln!("{e}, branching from HEAD"))
+            .ok(),
+        _ => None,
+    };
+    let base = base.or(configured_base.as_deref());
+
+    if !create && !independent {
+        refuse_checked_out_branch(&git, &repo_root, name)?;
+    }
+    if create && base.is_none() {
+        require_commits(&git)?;
+    }
+    // Checked up front: finding out after `worktree add` would leave a
+    // worktree behind for a typo.
+    if let Some(Some(upstream)) = opts.track {
+        git::reject_leading_dash("ref", upstream)?;
+        if !git.has_local_branch(upstream) && !git.ref_exists(&format!("refs/remotes/{upstream}")) {
+            return Err(format!("cannot track '{upstream}': no such branch"));
+        }
+    }
+
+    for dir in opts.sparse.unwrap_or_default() {
+        link::validate_path(dir)?;
+    }
+
+    let dest = match path {
+        Some(path) => custom_dest(path)?,
+        None => worktree::create_dest(&repo_root, &git)?,
+    };
 
-    let result = if create {
+    let result = if independent {
+        git.local_clone(name, &dest)
+    } else if create {
         if git.has_local_branch(name) {
             let repo_flag = repo
                 .map(|r| format!(" --repo '{}'", r.display()))
@@ -24,27 +108,219 @@ pub fn run(
                 "cannot create branch '{name}': already exists, use 'wt new{repo_flag} {name}'"
             ))
         } else {
-            git.add_worktree(name, &dest, base)
+            git.add_worktree(name, &dest, base, opts.sparse)
         }
     } else {
-        git.checkout_worktree(name, &dest)
+        git.checkout_worktree(name, &dest, opts.sparse)
     };
 
     if let Err(e) = result {
-        worktree::cleanup_dest(&dest);
+        // A custom destination's parent belongs to the user; git already
+        // removes the directory it created on failure.
+        if path.is_none() {
+            worktree::cleanup_dest(&dest);
+        }
         return Err(e);
     }
 
-    if create {
+    if independent {
+        eprintln!("cloning '{name}' into an independent checkout");
+    } else if create {
         eprintln!("creating branch '{name}'");
+        note_if_behind(&git, base);
+        // --from-base picks a start point, not an upstream: `worktree add -b`
+        // would leave a remote base such as origin/develop tracked.
+        let track = opts.track.or(configured_base.as_ref().map(|_| None));
+        if let Some(track) = track {
+            let set = match track {
+                Some(upstream) => git.set_upstream(name, upstream),
+                None => git.unset_upstream(name),
+            };
+            if let Err(e) = set {
+                roll_back(&git, &dest, Some(name), false, path.is_some());
+                return Err(format!("{e}, removed the new worktree"));
+            }
+            if let Some(upstream) = track {
+                eprintln!("tracking '{upstream}'");
+            }
+        }
     } else {
         eprintln!("checking out '{name}'");
     }
 
-    link::auto_link(&repo_root, &dest, &repo_root);
+    // A conflicted apply still leaves a useful worktree: the conflicts are
+    // what the reviewer needs to look at.
+    if let (Some((commit, _)), Some(stash_ref)) = (&stash, opts.from_stash) {
+        match git.apply_stash(&dest, commit) {
+            Ok(()) => eprintln!("applied {stash_ref}"),
+            Err(e) => eprintln!("{e}, resolve the conflicts in the worktree"),
+        }
+    }
+
+    // Setup is best-effort unless --strict-setup asks for all or nothing.
+    // Links go first so a linked file that is also ignored stays a link.
+    let mut result = link::auto_link(&repo_root, &dest, &repo_root, false);
+    if let Some(glob) = opts.copy_untracked {
+        result = result.and(copy_ignored(&git, &repo_root, &dest, glob));
+    }
+    if let Err(e) = result
+        && opts.strict_setup
+    {
+        let created = (create && !independent).then_some(name);
+        roll_back(&git, &dest, created, independent, path.is_some());
+        return Err(format!("{e}, removed the new worktree (--strict-setup)"));
+    }
+
+    if independent && let Err(e) = config::add_independent(&repo_root, &dest) {
+        eprintln!("cannot save independent checkout: {e}");
+    }
+    if !independent {
+        recency::touch(&repo_root, &dest);
+    }
+    hooks::post_create(&repo_root, &dest);
+
+    if opts.cd {
+        println!("cd {}", terminal::shell_quote(&dest.to_string_lossy()));
+    } else {
+        println!("{}", dest.display());
+        terminal::print_cd_hint(name);
+    }
+    // Only an explicit `--open` may fail the command; wt.openOnCreate opens
+    // the way `switch` does, with a warning when there is no editor.
+    match opts.open {
+        Some(true) => editor::launch(&dest, opts.open_with)?,
+        Some(false) => {}
+        None if git.wt_flag("openOnCreate") => editor::open(&dest, false),
+        None => {}
+    }
+    Ok(())
+}
 
-    println!("{}", dest.display());
+// A stash entry is a merge commit whose first parent is the commit it was
+// made on; the second holds the index, so a plain commit is rejected.
+fn resolve_stash(git: &Git, stash: &str) -> Result<(String, String), String> {
+    git::reject_leading_dash("ref", stash)?;
+    match (
+        git.rev_parse(stash),
+        git.rev_parse(&format!("{stash}^1")),
+        git.rev_parse(&format!("{stash}^2")),
+    ) {
+        (Some(commit), Some(base), Some(_)) => Ok((commit, base)),
+        _ => Err(format!("not a stash entry: {stash}")),
+    }
+}
 
-    terminal::print_cd_hint(name);
+// An unborn HEAD has nothing to branch from; git's own error for this
+// ("not a valid object name: 'HEAD'") doesn't say what to do about it.
+pub(crate) fn require_commits(git: &Git) -> Result<(), String> {
+    if git.rev_parse("HEAD").is_none() {
+        return Err("repository has no commits yet, make an initial commit first".into());
+    }
     Ok(())
 }
+
+// Branches start from HEAD (or the given local base) as-is; this only points
+// out that the start point is stale, it never moves it.
+pub(crate) fn note_if_behind(git: &Git, base: Option<&str>) {
+    let start = match base {
+        Some(b) if git.has_local_branch(b) => b.to_string(),
+        Some(_) => return,
+        None => match git.current_branch() {
+            Some(b) => b,
+            None => return,
+        },
+    };
+    if let Some((upstream, behind)) = git.behind_upstream(&start) {
+        let commits = if behind == 1 { "commit" } else { "commits" };
+        terminal::eprintln_dim(&format!(
+            "note: '{start}' is {behind} {commits} behind '{upstream}', consider pulling first"
+        ));
+    }
+}
+
+// Copies the primary's gitignored files (.env, editor settings, build
+// config) that a fresh checkout lacks. Existing files are left alone, and
+// like auto-link each failure is reported as it happens.
+fn copy_ignored(git: &Git, primary: &Path, dest: &Path, glob: Option<&str>) -> Result<(), String> {
+    let files = git.ignored_files(primary)?;
+    let mut failed = 0usize;
+    for file in files
+        .iter()
+        .filter(|f| glob.is_none_or(|g| fuzzy::glob_match(g, f)))
+    {
+        let target = dest.join(file);
+        if target.symlink_metadata().is_ok() {
+            continue;
+        }
+        let copied = target
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| std::fs::copy(primary.join(file), &target));
+        match copied {
+            Ok(_) => eprintln!("copied {file}"),
+            Err(e) => {
+                eprintln!("cannot copy {file}: {e}");
+                failed += 1;
+            }
+        }
+    }
+
+    match failed {
+        0 => Ok(()),
+        1 => Err("cannot copy 1 file".into()),
+        n => Err(format!("cannot copy {n} files")),
+    }
+}
+
+fn roll_back(git: &Git, dest: &Path, created: Option<&str>, independent: bool, custom: bool) {
+    if independent {
+        let _ = std::fs::remove_dir_all(dest);
+    } else if let Err(e) = git.remove_worktree(dest, true) {
+        eprintln!("{e}");
+    }
+    if let Some(branch) = created
+        && let Err(e) = git.delete_branch(branch, true)
+    {
+        eprintln!("{e}");
+    }
+    if !custom {
+        worktree::cleanup_dest(dest);
+    }
+}
+
+fn custom_dest(path: &Path) -> Result<PathBuf, String> {
+    let dest =
+        std::path::absolute(path).map_err(|e| format!("cannot resolve {}: {e}", path.display()))?;
+    let occupied = if dest.is_dir() {
+        std::fs::read_dir(&dest).is_ok_and(|mut d| d.next().is_some())
+    } else {
+        dest.exists()
+    };
+    if occupied {
+        return Err(format!("path already exists: {}", dest.display()));
+    }
+    Ok(dest)
+}
+
+fn refuse_checked_out_branch(git: &Git, repo_root: &Path, name: &str) -> Result<(), String> {
+    let output = git.list_worktrees()?;
+    let worktrees = worktree::parse_porcelain(&output);
+    let Some(existing) = worktrees
+        .iter()
+        .find(|wt| wt.live() && wt.branch.as_deref() == Some(name))
+    else {
+        return Ok(());
+    };
+
+    let is_primary = worktree::find_primary(&worktrees, repo_root)
+        .is_some_and(|primary| primary.path == existing.path);
+    let location = if is_primary {
+        "the primary worktree"
+    } else {
+        "another worktree"
+    };
+    Err(format!(
+        "branch '{name}' is checked out in {location}: {}, use `wt switch {name}` to go there",
+        terminal::tilde_path(&existing.path)
+    ))
+}
@@ -0,0 +1,96 @@
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use crate::cli::Opener;
+use crate::commands::path;
+use crate::editor;
+use crate::git::Git;
+use crate::recency;
+use crate::worktree;
+
+pub fn run(name: &str, with: Opener, repo: Option<&Path>, by_index: bool) -> Result<(), String> {
+    let (repo_root, wt_path) = path::resolve(name, repo, by_index)?;
+    recency::touch(&repo_root, &wt_path);
+    match with {
+        Opener::Editor => editor::launch(&wt_path, None),
+        Opener::Files => open_files(&wt_path),
+        Opener::Tmux => open_tmux(&wt_path),
+    }
+}
+
+fn open_files(path: &Path) -> Result<(), String> {
+    let program = if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+    let mut cmd = Command::new(program);
+    cmd.arg(path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    finish(program, &mut cmd)
+}
+
+// Inside tmux the worktree gets a window in the current session; outside, a
+// session of its own that is attached to. Either is reused when it exists.
+fn open_tmux(path: &Path) -> Result<(), String> {
+    let name = tmux_name(
+        &Git::new(path)
+            .current_branch()
+            .unwrap_or_else(|| worktree::repo_basename(path)),
+    );
+    let mut cmd = Command::new("tmux");
+    if std::env::var_os("TMUX").is_some_and(|v| !v.is_empty()) {
+        if tmux_window_exists(&name) {
+            cmd.args(["select-window", "-t", &format!(":={name}")]);
+        } else {
+            cmd.args(["new-window", "-n", &name, "-c"]).arg(path);
+        }
+    } else {
+        cmd.args(["new-session", "-A", "-s", &name, "-c"]).arg(path);
+    }
+    finish("tmux", &mut cmd)
+}
+
+fn tmux_window_exists(name: &str) -> bool {
+    Command::new("tmux")
+        .args(["list-windows", "-F", "#{window_name}"])
+        .stderr(Stdio::null())
+        .output()
+        .is_ok_and(|o| {
+            o.status.success()
+                && String::from_utf8_lossy(&o.stdout)
+                    .lines()
+                    .any(|l| l == name)
+        })
+}
+
+// tmux reads `.` and `:` in a target as separators, so `release/1.2` can't
+// name a session as is.
+fn tmux_name(branch: &str) -> String {
+    branch.replace(['.', ':'], "-")
+}
+
+fn finish(program: &str, cmd: &mut Command) -> Result<(), String> {
+    let status = cmd
+        .status()
+        .map_err(|e| format!("cannot run {program}: {e}"))?;
+    match status.code() {
+        Some(0) => Ok(()),
+        Some(code) => Err(format!("{program} exited with status {code}")),
+        None => Err(format!("{program} was killed by a signal")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tmux_name_replaces_target_separators() {
+        assert_eq!(tmux_name("release/1.2"), "release/1-2");
+        assert_eq!(tmux_name("fix:a"), "fix-a");
+        assert_eq!(tmux_name("feat/login"), "feat/login");
+    }
+}
@@ -1,40 +1,40 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use crate::fuzzy;
+use crate::commands::list;
 use crate::git::Git;
-use crate::worktree::{self, Resolved};
+use crate::recency;
+use crate::worktree;
 
-pub fn run(name: &str, repo: Option<&Path>) -> Result<(), String> {
-    let repo_root = Git::find_repo(repo)?;
+pub fn run(name: &str, repo: Option<&Path>, by_index: bool) -> Result<(), String> {
+    let (repo_root, wt_path) = resolve(name, repo, by_index)?;
+    recency::touch(&repo_root, &wt_path);
+    println!("{}", wt_path.display());
+    Ok(())
+}
 
-    let git = Git::new(&repo_root);
-    let output = git.list_worktrees()?;
-    let worktrees = worktree::parse_porcelain(&output);
+// No name: the worktree the shell is standing in, as git resolves it.
+pub fn current() -> Result<(), String> {
+    let root = Git::toplevel(None).ok_or("not inside a worktree")?;
+    println!("{}", root.display());
+    Ok(())
+}
 
-    match worktree::resolve_worktree(&worktrees, name, &git) {
-        Resolved::Found(wt) => {
-            println!("{}", wt.path.display());
-            Ok(())
-        }
-        Resolved::Ambiguous { matches, kind } => {
-            eprintln!("ambiguous {kind} '{name}'; matches:");
-            for m in &matches {
-                eprintln!("  - {}", m.path.display());
-            }
-            Err("multiple worktrees match, specify a path instead".into())
-        }
-        Resolved::NotFound => {
-            let branches: Vec<&str> = worktrees
-                .iter()
-                .filter_map(|wt| wt.branch.as_deref())
-                .collect();
-            if let Some(suggestion) = fuzzy::close_match(name, &branches) {
-                Err(format!(
-                    "no worktree found for: {name}, did you mean '{suggestion}'?"
-                ))
-            } else {
-                Err(format!("no worktree found for: {name}"))
-            }
-        }
-    }
+// The repo root and the worktree `name` refers to, as `wt path` finds it.
+pub fn resolve(
+    name: &str,
+    repo: Option<&Path>,
+    by_index: bool,
+) -> Result<(PathBuf, PathBuf), String> {
+    let repo_root = Git::find_repo(repo)?;
+    let wt_path = if by_index {
+        list::resolve_index(Some(&repo_root), name)?
+    } else {
+        let git = Git::new(&repo_root);
+        let output = git.list_worktrees()?;
+        let worktrees = worktree::parse_porcelain(&output);
+        worktree::resolve_named(&worktrees, name, &git)?
+            .path
+            .clone()
+    };
+    Ok((repo_root, wt_path))
 }
@@ -2,99 +2,175 @@ use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use serde::Serialize;
+
+use crate::config;
+use crate::fuzzy;
 use crate::git::Git;
+use crate::lock;
+use crate::recency;
 use crate::terminal::{self, Colors};
 use crate::worktree;
 
-pub fn run(
+#[derive(Clone, Copy)]
+pub struct PruneOptions<'a> {
+    pub dry_run: bool,
+    pub interactive: bool,
+    pub gone: bool,
+    pub stale: bool,
+    // Seconds since a worktree was last handed out by `wt` (see recency.rs).
+    pub unused: Option<u64>,
+    // Seconds the worktree's HEAD commit must be older than for any removal.
+    pub older_than: Option<u64>,
+    // Worktrees whose only changes are untracked files count as clean.
+    pub ignore_untracked: bool,
+    pub squashed: bool,
+    pub keep_branches: bool,
+    pub prune_refs: bool,
+    pub base: Option<&'a str>,
+    pub report_file: Option<&'a Path>,
+}
+
+// `--report-file`: what a run did, as JSON, for tracking scheduled cleanups.
+// In a dry run the entries are what would have been removed.
+#[derive(Default, Serialize)]
+struct Report {
     dry_run: bool,
-    gone: bool,
-    stale: bool,
-    repo: Option<&Path>,
-    base: Option<&str>,
-) -> Result<(), String> {
-    let cwd = std::env::current_dir().and_then(|p| p.canonicalize()).ok();
+    repos: Vec<RepoReport>,
+    orphans: Vec<String>,
+    errors: Vec<String>,
+    counts: ReportCounts,
+}
 
-    let clr = terminal::stderr_colors();
+#[derive(Serialize)]
+struct RepoReport {
+    repo: String,
+    removed: Vec<RemovedWorktree>,
+    errors: Vec<String>,
+}
 
-    if let Some(repo_path) = repo {
-        let repo_root = Git::find_repo(Some(repo_path))?;
-        let git = Git::new(&repo_root);
-        let output = git.prune_worktrees(dry_run)?;
-        if !output.is_empty() {
-            for line in output.lines() {
-                eprintln!("{}", style_msg(line, &clr));
-            }
+#[derive(Serialize)]
+struct RemovedWorktree {
+    branch: String,
+    path: String,
+    reason: String,
+    branch_deleted: bool,
+}
+
+#[derive(Default, Serialize)]
+struct ReportCounts {
+    worktrees: usize,
+    branches: usize,
+    orphans: usize,
+    errors: usize,
+}
+
+impl RepoReport {
+    fn new(repo: &Path) -> Self {
+        Self {
+            repo: repo.display().to_string(),
+            removed: Vec::new(),
+            errors: Vec::new(),
         }
-        let mut msgs = Vec::new();
-        let result = prune_merged(&git, dry_run, gone, stale, cwd.as_deref(), base, &mut msgs);
-        for msg in &msgs {
-            eprintln!("{}", style_msg(msg, &clr));
+    }
+}
+
+impl Report {
+    fn finish(&mut self, result: &Result<(), String>) {
+        if let Err(e) = result
+            && !e.is_empty()
+        {
+            self.errors.push(e.clone());
         }
-        return result;
+        self.repos
+            .retain(|r| !r.removed.is_empty() || !r.errors.is_empty());
+        let removed = self.repos.iter().flat_map(|r| &r.removed);
+        self.counts = ReportCounts {
+            worktrees: removed.clone().count(),
+            branches: removed.filter(|r| r.branch_deleted).count(),
+            orphans: self.orphans.len(),
+            errors: self.errors.len() + self.repos.iter().map(|r| r.errors.len()).sum::<usize>(),
+        };
     }
+}
 
-    let wt_root = worktree::worktrees_root()?;
+// The report is a side channel: failing to write it only costs a warning.
+fn write_report(path: &Path, report: &Report) {
+    let written = serde_json::to_string_pretty(report)
+        .map_err(|e| e.to_string())
+        .and_then(|json| std::fs::write(path, json + "\n").map_err(|e| e.to_string()));
+    if let Err(e) = written {
+        eprintln!("cannot write report {}: {e}", path.display());
+    }
+}
 
-    if !wt_root.is_dir() {
-        return Ok(());
+pub fn run(opts: PruneOptions, repos: &[PathBuf]) -> Result<(), String> {
+    let mut report = Report {
+        dry_run: opts.dry_run,
+        ..Report::default()
+    };
+    let result = prune_all(opts, repos, &mut report);
+    if let Some(path) = opts.report_file {
+        report.finish(&result);
+        write_report(path, &report);
     }
-    let wt_root = worktree::canonicalize_or_self(&wt_root);
+    result
+}
 
-    let repos = worktree::discover_repos(&wt_root);
-    let mut errors = 0usize;
-    let mut printed = false;
-    for repo_path in &repos {
-        if !repo_path.exists() {
-            continue;
-        }
-        let git = Git::new(repo_path);
-        let mut repo_msgs: Vec<String> = Vec::new();
+fn prune_all(opts: PruneOptions, repos: &[PathBuf], report: &mut Report) -> Result<(), String> {
+    let dry_run = opts.dry_run;
+    let cwd = std::env::current_dir().and_then(|p| p.canonicalize()).ok();
 
-        match git.prune_worktrees(dry_run) {
-            Ok(output) if !output.is_empty() => {
+    let clr = terminal::stderr_colors();
+
+    if !repos.is_empty() {
+        let repo_roots = resolve_repos(repos)?;
+        if let [repo_root] = repo_roots.as_slice() {
+            let git = Git::new(repo_root);
+            let _lock = if dry_run {
+                None
+            } else {
+                Some(lock::acquire(&git, repo_root, "prune")?)
+            };
+            let output = git.prune_worktrees(dry_run)?;
+            if !output.is_empty() {
                 for line in output.lines() {
-                    repo_msgs.push(line.to_string());
+                    eprintln!("{}", style_msg(line, &clr));
                 }
             }
-            Err(e) => {
-                eprintln!(
-                    "{}cannot prune {}: {e}{}",
-                    clr.red,
-                    repo_path.display(),
-                    clr.reset
-                );
-                errors += 1;
-                continue;
-            }
-            _ => {}
-        }
-
-        if let Err(e) = prune_merged(
-            &git,
-            dry_run,
-            gone,
-            stale,
-            cwd.as_deref(),
-            base,
-            &mut repo_msgs,
-        ) {
-            repo_msgs.push(format!("cannot clean up: {e}"));
-            errors += 1;
-        }
-
-        if !repo_msgs.is_empty() {
-            if printed {
-                eprintln!();
+            let mut msgs = Vec::new();
+            let mut repo_report = RepoReport::new(repo_root);
+            let result = prune_merged(
+                &git,
+                repo_root,
+                None,
+                opts,
+                cwd.as_deref(),
+                &mut msgs,
+                &mut repo_report,
+            );
+            if let Err(e) = &result {
+                repo_report.errors.push(e.clone());
             }
-            let name = worktree::repo_basename(repo_path);
-            eprintln!("{}{}:{}", clr.bold, name, clr.reset);
-            for msg in &repo_msgs {
-                eprintln!("  {}", style_msg(msg, &clr));
+            report.repos.push(repo_report);
+            for msg in &msgs {
+                eprintln!("{}", style_msg(msg, &clr));
             }
-            printed = true;
+            return result;
         }
+        let (errors, _) = prune_repos(&repo_roots, opts, cwd.as_deref(), report);
+        return repo_errors(errors);
+    }
+
+    let wt_root = worktree::worktrees_root()?;
+
+    if !wt_root.is_dir() {
+        return Ok(());
     }
+    let wt_root = worktree::canonicalize_or_self(&wt_root);
+
+    let repos: Vec<PathBuf> = worktree::discover_repos(&wt_root).into_iter().collect();
+    let (errors, printed) = prune_repos(&repos, opts, cwd.as_deref(), report);
 
     let mut orphans = find_orphans(&wt_root);
     let mut has_orphan_output = false;
@@ -128,6 +204,7 @@ pub fn run(
                 "{}",
                 style_msg(&format!("would remove {} (orphan)", label.display()), &clr,)
             );
+            report.orphans.push(orphan.display().to_string());
         }
     } else {
         if printed && !has_orphan_output && !orphans.is_empty() {
@@ -141,10 +218,32 @@ pub fn run(
                 "{}",
                 style_msg(&format!("removed {} (orphan)", label.display()), &clr,)
             );
+            report.orphans.push(orphan.display().to_string());
         }
         cleanup_empty_parents(&orphans, &wt_root, cwd.as_deref(), &clr);
     }
 
+    let empty_dirs = find_empty_dirs(&wt_root)
+        .into_iter()
+        .filter(|dir| !orphans.contains(dir) && !worktree::is_cwd_inside(dir, cwd.as_deref()));
+    for dir in empty_dirs {
+        if dry_run {
+            let label = dir.strip_prefix(&wt_root).unwrap_or(&dir);
+            eprintln!(
+                "{}would remove empty directory {}{}",
+                clr.dim,
+                label.display(),
+                clr.reset
+            );
+        } else {
+            cleanup_dir_chain(&dir, &wt_root, cwd.as_deref(), &clr);
+        }
+    }
+
+    repo_errors(errors)
+}
+
+fn repo_errors(errors: usize) -> Result<(), String> {
     if errors > 0 {
         return Err(format!(
             "cannot prune {errors} {}",
@@ -155,6 +254,268 @@ pub fn run(
     Ok(())
 }
 
+// What pruning one repo produced. Output is held back until the repo is done
+// so a parallel run still prints repos in order.
+enum RepoOutcome {
+    // Another `wt` command holds the repo's lock.
+    Busy(String),
+    // `git worktree prune` failed, so nothing else was tried.
+    Failed(String, RepoReport),
+    Pruned {
+        msgs: Vec<String>,
+        report: RepoReport,
+        failed: bool,
+    },
+}
+
+fn prune_repos(
+    repos: &[PathBuf],
+    opts: PruneOptions,
+    cwd: Option<&Path>,
+    report: &mut Report,
+) -> (usize, bool) {
+    let progress = terminal::Progress::new(repos.len());
+    let mut errors = 0usize;
+    let mut printed = false;
+
+    // Prompts need the terminal to themselves, one repo at a time.
+    if opts.interactive {
+        for repo_path in repos {
+            let outcome = prune_repo(repo_path, opts, cwd, &progress);
+            print_outcome(
+                repo_path,
+                outcome,
+                &progress,
+                report,
+                &mut errors,
+                &mut printed,
+            );
+        }
+        return (errors, printed);
+    }
+
+    // Network fetches dominate a global prune, so repos are pruned on the
+    // shared worker pool and printed in order once all are done.
+    let outcomes = worktree::map_repos(repos, |repo_path| {
+        prune_repo(repo_path, opts, cwd, &progress)
+    });
+    for (repo_path, outcome) in repos.iter().zip(outcomes) {
+        print_outcome(
+            repo_path,
+            outcome,
+            &progress,
+            report,
+            &mut errors,
+            &mut printed,
+        );
+    }
+    (errors, printed)
+}
+
+// None when the repo has gone away since it was discovered.
+fn prune_repo(
+    repo_path: &Path,
+    opts: PruneOptions,
+    cwd: Option<&Path>,
+    progress: &terminal::Progress,
+) -> Option<RepoOutcome> {
+    let name = worktree::repo_basename(repo_path);
+    progress.tick(&name);
+    if !repo_path.exists() {
+        return None;
+    }
+    let git = Git::new(repo_path);
+    // A repo busy with another `wt` command is skipped, not waited on, so
+    // one long-running operation can't stall a prune of every repo.
+    let _lock = if opts.dry_run {
+        None
+    } else {
+        match lock::acquire(&git, repo_path, "prune") {
+            Ok(lock) => Some(lock),
+            // Not being able to lock a broken repo is reported by the
+            // prune below, with the usual per-repo error.
+            Err(e) if !lock::is_held_error(&e) => None,
+            Err(e) => return Some(RepoOutcome::Busy(e)),
+        }
+    };
+    let mut msgs: Vec<String> = Vec::new();
+    let mut report = RepoReport::new(repo_path);
+
+    match git.prune_worktrees(opts.dry_run) {
+        Ok(output) => msgs.extend(output.lines().map(str::to_string)),
+        Err(e) => return Some(RepoOutcome::Failed(e, report)),
+    }
+
+    // Prompts must not land on the progress line.
+    if opts.interactive {
+        progress.clear();
+    }
+    let mut failed = false;
+    if let Err(e) = prune_merged(
+        &git,
+        repo_path,
+        Some(&name),
+        opts,
+        cwd,
+        &mut msgs,
+        &mut report,
+    ) {
+        let e = format!("cannot clean up: {e}");
+        report.errors.push(e.clone());
+        msgs.push(e);
+        failed = true;
+    }
+    Some(RepoOutcome::Pruned {
+        msgs,
+        report,
+        failed,
+    })
+}
+
+fn print_outcome(
+    repo_path: &Path,
+    outcome: Option<RepoOutcome>,
+    progress: &terminal::Progress,
+    report: &mut Report,
+    errors: &mut usize,
+    printed: &mut bool,
+) {
+    let clr = terminal::stderr_colors();
+    match outcome {
+        None => {}
+        Some(RepoOutcome::Busy(e)) => {
+            progress.clear();
+            if *printed {
+                eprintln!();
+            }
+            eprintln!(
+                "{}skipping {}: {e}{}",
+                clr.yellow,
+                repo_path.display(),
+                clr.reset
+            );
+            *printed = true;
+        }
+        Some(RepoOutcome::Failed(e, mut repo_report)) => {
+            progress.clear();
+            eprintln!(
+                "{}cannot prune {}: {e}{}",
+                clr.red,
+                repo_path.display(),
+                clr.reset
+            );
+            repo_report.errors.push(e);
+            report.repos.push(repo_report);
+            *errors += 1;
+        }
+        Some(RepoOutcome::Pruned {
+            msgs,
+            report: repo_report,
+            failed,
+        }) => {
+            if failed {
+                *errors += 1;
+            }
+            report.repos.push(repo_report);
+            if !msgs.is_empty() {
+                progress.clear();
+                if *printed {
+                    eprintln!();
+                }
+                let name = worktree::repo_basename(repo_path);
+                eprintln!("{}{}:{}", clr.bold, name, clr.reset);
+                for msg in &msgs {
+                    eprintln!("  {}", style_msg(msg, &clr));
+                }
+                *printed = true;
+            }
+        }
+    }
+}
+
+fn resolve_repos(patterns: &[PathBuf]) -> Result<Vec<PathBuf>, String> {
+    let mut seen = BTreeSet::new();
+    let mut repos = Vec::new();
+    for pattern in patterns {
+        let pattern = expand_tilde(pattern);
+        if !has_glob(&pattern) {
+            let repo_root = Git::find_repo(Some(&pattern))?;
+            if seen.insert(worktree::canonicalize_or_self(&repo_root)) {
+                repos.push(repo_root);
+            }
+            continue;
+        }
+        let matches = expand_glob(&pattern);
+        let mut found = false;
+        for path in matches.iter().filter(|p| p.is_dir()) {
+            let Ok(repo_root) = Git::find_repo(Some(path)) else {
+                continue;
+            };
+            // A plain directory resolves to whatever repo encloses it, which
+            // the glob never named; only repo roots count as matches.
+            let canonical = worktree::canonicalize_or_self(&repo_root);
+            if canonical != worktree::canonicalize_or_self(path) {
+                continue;
+            }
+            found = true;
+            if seen.insert(canonical) {
+                repos.push(repo_root);
+            }
+        }
+        if !found {
+            return Err(format!("no repositories match: {}", pattern.display()));
+        }
+    }
+    Ok(repos)
+}
+
+fn expand_tilde(path: &Path) -> PathBuf {
+    if let Ok(rest) = path.strip_prefix("~")
+        && let Ok(home) = std::env::var("HOME")
+    {
+        return Path::new(&home).join(rest);
+    }
+    path.to_path_buf()
+}
+
+fn has_glob(path: &Path) -> bool {
+    path.to_string_lossy().contains(['*', '?'])
+}
+
+fn expand_glob(pattern: &Path) -> Vec<PathBuf> {
+    let mut paths = vec![PathBuf::new()];
+    for component in pattern.components() {
+        let part = component.as_os_str().to_string_lossy();
+        if !part.contains(['*', '?']) {
+            for path in &mut paths {
+                path.push(component);
+            }
+            continue;
+        }
+        let mut next = Vec::new();
+        for dir in &paths {
+            let read_from = if dir.as_os_str().is_empty() {
+                Path::new(".")
+            } else {
+                dir.as_path()
+            };
+            let Ok(entries) = fs::read_dir(read_from) else {
+                continue;
+            };
+            let mut names: Vec<String> = entries
+                .flatten()
+                .map(|e| e.file_name().to_string_lossy().into_owned())
+                .filter(|name| !name.starts_with('.') || part.starts_with('.'))
+                .filter(|name| fuzzy::glob_match(&part, name))
+                .collect();
+            names.sort();
+            next.extend(names.into_iter().map(|name| dir.join(name)));
+        }
+        paths = next;
+    }
+    paths
+}
+
 fn find_orphans(wt_root: &Path) -> Vec<PathBuf> {
     let mut orphans = Vec::new();
     scan_dir(wt_root, wt_root, &mut orphans);
@@ -191,7 +552,13 @@ fn scan_dir(dir: &Path, wt_root: &Path, orphans: &mut Vec<PathBuf>) {
 
         if dot_git.is_file() {
             if let Some(gitdir) = worktree::parse_gitdir(&dot_git) {
-                if !gitdir.exists() {
+                if !worktree::is_worktree_gitdir(&gitdir) {
+                    eprintln!(
+                        "{} points outside a repository's worktrees directory ({}), skipping",
+                        dot_git.display(),
+                        gitdir.display()
+                    );
+                } else if !gitdir.exists() {
                     orphans.push(path);
                 }
             } else {
@@ -200,7 +567,8 @@ fn scan_dir(dir: &Path, wt_root: &Path, orphans: &mut Vec<PathBuf>) {
         } else if !dot_git.is_dir() {
             if dir.parent() == Some(wt_root) {
                 // sole empty dir at the <id> level → zombie from interrupted create_dest
-                if fs::read_dir(&path).is_ok_and(|mut d| d.next().is_none())
+                if !is_fresh(dir)
+                    && fs::read_dir(&path).is_ok_and(|mut d| d.next().is_none())
                     && fs::read_dir(dir).is_ok_and(|mut d| d.next().is_some() && d.next().is_none())
                 {
                     orphans.push(path);
@@ -212,6 +580,51 @@ fn scan_dir(dir: &Path, wt_root: &Path, orphans: &mut Vec<PathBuf>) {
     }
 }
 
+// A `wt new` in another repo creates its `<id>/<repo>` before git fills it,
+// and that repo's lock doesn't cover the sweeps here, so an `<id>` changed
+// in the last few minutes is never taken for a leftover.
+const EMPTY_DIR_GRACE: std::time::Duration = std::time::Duration::from_secs(5 * 60);
+
+fn is_fresh(id_dir: &Path) -> bool {
+    fs::metadata(id_dir)
+        .and_then(|m| m.modified())
+        .is_ok_and(|t| t.elapsed().is_ok_and(|age| age < EMPTY_DIR_GRACE))
+}
+
+// Worktrees removed with raw `git worktree remove` leave their `<id>` (and
+// sometimes `<id>/<repo>`) directories behind. An `<id>` is swept only when it
+// holds nothing but empty directories, so user data beside a worktree survives.
+fn find_empty_dirs(wt_root: &Path) -> Vec<PathBuf> {
+    let is_empty_dir = |path: &Path| fs::read_dir(path).is_ok_and(|mut d| d.next().is_none());
+    let mut empty = Vec::new();
+    let Ok(ids) = fs::read_dir(wt_root) else {
+        return empty;
+    };
+    for id in ids.flatten() {
+        if !id.file_type().is_ok_and(|t| t.is_dir()) {
+            continue;
+        }
+        let id_path = id.path();
+        if is_fresh(&id_path) {
+            continue;
+        }
+        let Ok(children) = fs::read_dir(&id_path) else {
+            continue;
+        };
+        let children: Vec<_> = children.flatten().collect();
+        if children.is_empty() {
+            empty.push(id_path);
+        } else if children
+            .iter()
+            .all(|c| c.file_type().is_ok_and(|t| t.is_dir()) && is_empty_dir(&c.path()))
+        {
+            empty.extend(children.iter().map(|c| c.path()));
+        }
+    }
+    empty.sort();
+    empty
+}
+
 fn cleanup_empty_parents(orphans: &[PathBuf], wt_root: &Path, cwd: Option<&Path>, clr: &Colors) {
     let candidates: BTreeSet<&Path> = orphans.iter().filter_map(|p| p.parent()).collect();
     let mut sorted: Vec<&Path> = candidates.into_iter().collect();
@@ -246,21 +659,39 @@ fn cleanup_dir_chain(mut dir: &Path, wt_root: &Path, cwd: Option<&Path>, clr: &C
     }
 }
 
+// `fetch_label` is set when other repos are pruned alongside this one: the
+// fetch then labels its live output, and the rest waits in `messages`.
 fn prune_merged(
     git: &Git,
-    dry_run: bool,
-    gone: bool,
-    stale: bool,
+    repo_root: &Path,
+    fetch_label: Option<&str>,
+    opts: PruneOptions,
     cwd: Option<&Path>,
-    base_override: Option<&str>,
     messages: &mut Vec<String>,
+    report: &mut RepoReport,
 ) -> Result<(), String> {
+    let PruneOptions {
+        dry_run,
+        interactive,
+        gone,
+        stale,
+        unused,
+        older_than,
+        ignore_untracked,
+        squashed,
+        keep_branches,
+        prune_refs,
+        base: base_override,
+        report_file: _,
+    } = opts;
     struct PruneCandidate {
         branch: String,
         path: PathBuf,
         merged: bool,
         remote: Option<String>,
         no_upstream: bool,
+        unused: bool,
+        squash_merged: bool,
     }
 
     let base = if let Some(b) = base_override {
@@ -275,33 +706,76 @@ fn prune_merged(
     } else {
         match git.base_ref() {
             Ok(base) => Some(base),
+            Err(e) if config::is_repo_config_error(&e) => return Err(e),
             Err(e) => {
                 messages.push(format!("{e}, skipping merged worktree pruning"));
                 None
             }
         }
     };
-    let base_branch = base_override
-        .map(|b| {
-            if git.has_local_branch(b) {
-                b
-            } else {
-                b.split_once('/').map_or(b, |(_, rest)| rest)
-            }
-        })
-        .or_else(|| {
-            base.as_deref()
-                .and_then(|b| b.split_once('/').map(|(_, rest)| rest))
-        });
+    // The base's local name is what a worktree would have checked out. Only a
+    // real remote's prefix is stripped, so `feat/x` as a base stays intact
+    // whatever the remote is called.
+    let base_branch = match base_override {
+        Some(b) if git.has_local_branch(b) => Some(b),
+        _ => base
+            .as_deref()
+            .or(base_override)
+            .map(|b| git.strip_remote(b)),
+    };
+
+    // A worktree wt never recorded handing out has no known last access, so
+    // --unused leaves it alone rather than guessing.
+    let recency = match unused {
+        Some(_) => recency::load()?,
+        None => recency::Recency::default(),
+    };
+    let cutoff = unused.map(|secs| recency::now().saturating_sub(secs));
+    let is_unused = |path: &Path| {
+        cutoff.is_some_and(|cutoff| recency.accessed(path).is_some_and(|at| at < cutoff))
+    };
 
     let output = git.list_worktrees()?;
     let worktrees = worktree::parse_porcelain(&output);
+    let managed_root = worktree::worktrees_root()
+        .ok()
+        .map(|root| worktree::canonicalize_or_self(&root));
+    // Named like its worktrees are under the managed root: a bare
+    // `repo.git` labels its branches `repo:branch`.
+    let repo_name = repo_root
+        .file_name()
+        .map(|name| {
+            let name = name.to_string_lossy();
+            name.strip_suffix(".git").unwrap_or(&name).to_string()
+        })
+        .unwrap_or_default();
+    let collisions = worktree::canonical_collisions(&worktrees);
+    for group in &collisions {
+        let paths: Vec<String> = group
+            .iter()
+            .map(|wt| wt.path.display().to_string())
+            .collect();
+        messages.push(format!(
+            "skipping {} (same directory, see `wt doctor`)",
+            paths.join(", ")
+        ));
+    }
+    let colliding: BTreeSet<&Path> = collisions
+        .iter()
+        .flatten()
+        .map(|wt| wt.path.as_path())
+        .collect();
     let candidates: Vec<PruneCandidate> = worktrees
         .iter()
         .skip(1)
         .filter_map(|wt| {
             let branch = wt.branch.as_ref()?;
-            if wt.locked || wt.prunable || base_branch.is_some_and(|b| b == branch) {
+            if wt.locked
+                || wt.prunable
+                || colliding.contains(wt.path.as_path())
+                || base_branch.is_some_and(|b| b == branch)
+                || git.is_protected_branch(branch)
+            {
                 return None;
             }
 
@@ -309,27 +783,44 @@ fn prune_merged(
             let upstream = git.upstream_remote(branch);
 
             let no_upstream = upstream.is_none();
+            let unused = is_unused(&wt.path);
 
-            if no_upstream && base_override.is_none() && !stale {
-                let merged = base
+            let merged = base
+                .as_ref()
+                .is_some_and(|base_ref| git.is_ancestor(&branch_ref, base_ref));
+            // Unlike plain ancestry, a squash match needs commits of the
+            // branch's own, so it holds without an upstream too.
+            let squash_merged = squashed
+                && !merged
+                && base
                     .as_ref()
-                    .is_some_and(|base_ref| git.is_ancestor(&branch_ref, base_ref));
-                if merged {
+                    .is_some_and(|base_ref| git.is_squash_merged(&branch_ref, base_ref));
+
+            // Without an upstream, being an ancestor of the base may just mean
+            // nothing was committed yet, so it doesn't count as merged.
+            if no_upstream && base_override.is_none() && !stale {
+                if merged && !unused {
                     messages.push(format!("skipping {branch} (no upstream)"));
                 }
-                return None;
+                return (unused || squash_merged).then(|| PruneCandidate {
+                    branch: branch.clone(),
+                    path: wt.path.clone(),
+                    merged: false,
+                    remote: None,
+                    no_upstream: false,
+                    unused,
+                    squash_merged,
+                });
             }
 
-            let merged = base
-                .as_ref()
-                .is_some_and(|base_ref| git.is_ancestor(&branch_ref, base_ref));
-
             Some(PruneCandidate {
                 branch: branch.clone(),
                 path: wt.path.clone(),
                 merged,
                 remote: if gone { upstream } else { None },
                 no_upstream: no_upstream && stale,
+                unused,
+                squash_merged,
             })
         })
         .collect();
@@ -356,8 +847,17 @@ fn prune_merged(
                 ));
                 false
             } else {
-                terminal::eprintln_dim(&format!("fetching from '{remote}'"));
-                git.fetch_remote(&remote)
+                let fetched = match fetch_label {
+                    Some(label) => {
+                        messages.push(format!("fetching from '{remote}'"));
+                        git.fetch_remote_labeled(&remote, label)
+                    }
+                    None => {
+                        terminal::eprintln_dim(&format!("fetching from '{remote}'"));
+                        git.fetch_remote(&remote)
+                    }
+                };
+                fetched
                     .inspect_err(|e| {
                         let detail = if e.is_empty() { "fetch failed" } else { e };
                         messages.push(format!("{detail}, skipping upstream-gone pruning"));
@@ -368,12 +868,20 @@ fn prune_merged(
         }
     }
 
+    // Read after fetching so the answers reflect what the remotes now have.
+    let upstreams_gone = if gone {
+        git.upstreams_gone()
+    } else {
+        BTreeMap::new()
+    };
+    let is_upstream_gone = |branch: &str| upstreams_gone.get(branch).copied().unwrap_or(false);
+
     for wt in worktrees.iter().skip(1) {
         if !wt.locked || wt.prunable {
             continue;
         }
         let Some(branch) = &wt.branch else { continue };
-        if base_branch.is_some_and(|b| b == branch.as_str()) {
+        if base_branch.is_some_and(|b| b == branch.as_str()) || git.is_protected_branch(branch) {
             continue;
         }
         let branch_ref = format!("refs/heads/{branch}");
@@ -385,63 +893,152 @@ fn prune_merged(
         let gone_eligible = if !gone {
             false
         } else if dry_run {
-            git.is_upstream_gone(branch)
+            is_upstream_gone(branch)
         } else {
             upstream.as_ref().is_some_and(|remote| {
                 gone_remote_status
                     .get(remote.as_str())
                     .copied()
                     .unwrap_or(false)
-                    && git.is_upstream_gone(branch)
+                    && is_upstream_gone(branch)
             })
         };
         let stale_eligible = upstream.is_none() && stale;
         let merged_eligible =
             (base_override.is_some() || upstream.is_some() || stale_eligible) && is_merged;
-        if merged_eligible || gone_eligible || stale_eligible {
-            let reason = build_reason(merged_eligible, gone_eligible, stale_eligible);
+        let unused_eligible = is_unused(&wt.path);
+        // A locked worktree stays either way, so it isn't worth the squash
+        // check's diffing just to name one more reason.
+        if merged_eligible || gone_eligible || stale_eligible || unused_eligible {
+            let reason = build_reason(
+                merged_eligible,
+                gone_eligible,
+                stale_eligible,
+                unused_eligible,
+                false,
+            );
             messages.push(format!("skipping {branch} ({reason}, locked)"));
         }
     }
 
+    struct BranchFailure {
+        branch: String,
+        reason: String,
+    }
+
     let mut errors = 0usize;
+    let mut branch_failures: Vec<BranchFailure> = Vec::new();
+    let mut removed_by_reason: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut refs_remotes: BTreeSet<String> = BTreeSet::new();
+    let mut kept_branches: Vec<String> = Vec::new();
+    let tracked_by = git.local_upstream_dependents();
 
     for candidate in candidates {
         let upstream_gone = if !gone {
             false
         } else if dry_run {
-            git.is_upstream_gone(&candidate.branch)
+            is_upstream_gone(&candidate.branch)
         } else {
             candidate.remote.as_ref().is_some_and(|remote| {
                 gone_remote_status.get(remote).copied().unwrap_or(false)
-                    && git.is_upstream_gone(&candidate.branch)
+                    && is_upstream_gone(&candidate.branch)
             })
         };
 
-        if !candidate.merged && !upstream_gone && !candidate.no_upstream {
+        if !candidate.merged
+            && !upstream_gone
+            && !candidate.no_upstream
+            && !candidate.unused
+            && !candidate.squash_merged
+        {
             continue;
         }
 
-        let reason = build_reason(candidate.merged, upstream_gone, candidate.no_upstream);
+        let reason = build_reason(
+            candidate.merged,
+            upstream_gone,
+            candidate.no_upstream,
+            candidate.unused,
+            candidate.squash_merged,
+        );
+        // Deleting a branch other local branches track would leave them
+        // pointing at nothing, so only its worktree goes.
+        let dependents = (!keep_branches)
+            .then(|| tracked_by.get(&candidate.branch))
+            .flatten();
+        let kept_note = match dependents {
+            Some(branches) => format!("branch kept, upstream of {}", branches.join(", ")),
+            None => "branch kept".to_string(),
+        };
+        // Going unused says nothing about the branch's commits, so on its own
+        // it only removes the worktree.
+        let keep_branch = keep_branches
+            || dependents.is_some()
+            || !(candidate.merged
+                || upstream_gone
+                || candidate.no_upstream
+                || candidate.squash_merged);
 
-        let label = &candidate.branch;
+        let label = &worktree_label(
+            &candidate.branch,
+            &candidate.path,
+            managed_root.as_deref(),
+            &repo_name,
+        );
 
         if worktree::is_cwd_inside(&candidate.path, cwd) {
             messages.push(format!("skipping {label} ({reason}, current directory)"));
             continue;
         }
 
-        if git.is_dirty(&candidate.path) {
+        if git.is_dirty(&candidate.path, ignore_untracked) {
             messages.push(format!("skipping {label} ({reason}, dirty)"));
             continue;
         }
 
+        // A HEAD with no readable commit date has no age to compare.
+        if let Some(secs) = older_than
+            && Git::commit_time(&candidate.path)
+                .is_none_or(|at| at > recency::now().saturating_sub(secs))
+        {
+            messages.push(format!("skipping {label} ({reason}, too recent)"));
+            continue;
+        }
+
+        let removed = |branch_deleted| RemovedWorktree {
+            branch: candidate.branch.clone(),
+            path: candidate.path.display().to_string(),
+            reason: reason.clone(),
+            branch_deleted,
+        };
+
         if dry_run {
-            messages.push(format!("would remove {label} ({reason})"));
+            if dependents.is_some() {
+                messages.push(format!("would remove {label} ({reason}, {kept_note})"));
+            } else {
+                messages.push(format!("would remove {label} ({reason})"));
+            }
+            report.removed.push(removed(!keep_branch));
             continue;
         }
 
-        if let Err(e) = git.remove_worktree(&candidate.path, false) {
+        if interactive
+            && !terminal::confirm(&format!(
+                "remove {label} ({reason}) at {}?",
+                terminal::tilde_path(&candidate.path)
+            ))
+        {
+            messages.push(format!("skipping {label} ({reason}, declined)"));
+            continue;
+        }
+
+        let removal = if ignore_untracked {
+            git.remove_worktree_past_untracked(&candidate.path)
+        } else {
+            git.remove_worktree(&candidate.path, false)
+        };
+        if let Err(e) = removal {
+            report.errors.push(e.clone());
             messages.push(e);
             errors += 1;
             continue;
@@ -449,13 +1046,89 @@ fn prune_merged(
 
         worktree::cleanup_empty_parent(&candidate.path, cwd);
 
+        if prune_refs && let Some(remote) = git.upstream_remote(&candidate.branch) {
+            refs_remotes.insert(remote);
+        }
+
+        if keep_branch {
+            if prune_refs {
+                kept_branches.push(candidate.branch.clone());
+            }
+            messages.push(format!("removed {label} ({reason}, {kept_note})"));
+            report.removed.push(removed(false));
+            removed_by_reason
+                .entry(reason)
+                .or_default()
+                .push(label.clone());
+            continue;
+        }
+
         if let Err(e) = git.delete_branch(&candidate.branch, true) {
-            messages.push(e);
-            errors += 1;
+            messages.push(format!("removed {label} ({reason}, branch kept)"));
+            report.removed.push(removed(false));
+            report.errors.push(e.clone());
+            removed_by_reason
+                .entry(reason.clone())
+                .or_default()
+                .push(label.clone());
+            let detail = e
+                .split_once(&format!("branch '{}': ", candidate.branch))
+                .map_or(e.as_str(), |(_, rest)| rest);
+            branch_failures.push(BranchFailure {
+                reason: detail.to_string(),
+                branch: candidate.branch,
+            });
             continue;
         }
 
         messages.push(format!("removed {label} ({reason})"));
+        report.removed.push(removed(true));
+        removed_by_reason
+            .entry(reason)
+            .or_default()
+            .push(label.clone());
+    }
+
+    for remote in refs_remotes {
+        match git.prune_remote(&remote) {
+            Ok(()) => messages.push(format!("pruned stale refs from '{remote}'")),
+            Err(e) => messages.push(e),
+        }
+    }
+    // Only once the remote is pruned is it clear whose upstream is gone; a
+    // kept branch whose upstream still exists keeps tracking it.
+    if !kept_branches.is_empty() {
+        let gone_now = git.upstreams_gone();
+        for branch in kept_branches {
+            if gone_now.get(&branch).copied().unwrap_or(false)
+                && let Err(e) = git.unset_upstream(&branch)
+            {
+                report.errors.push(e.clone());
+                messages.push(e);
+            }
+        }
+    }
+
+    if gone && !removed_by_reason.is_empty() {
+        let mut groups: Vec<_> = removed_by_reason.into_iter().collect();
+        groups.sort_by_key(|(reason, _)| reason.matches(", ").count());
+        for (reason, branches) in groups {
+            messages.push(format!("{}:", reason_heading(&reason)));
+            for branch in branches {
+                messages.push(format!("  {branch}"));
+            }
+        }
+    }
+
+    if !branch_failures.is_empty() {
+        let count = branch_failures.len();
+        messages.push(format!(
+            "cannot delete {count} {}:",
+            if count == 1 { "branch" } else { "branches" }
+        ));
+        for failure in &branch_failures {
+            messages.push(format!("  {}: {}", failure.branch, failure.reason));
+        }
     }
 
     if errors > 0 {
@@ -465,20 +1138,40 @@ fn prune_merged(
         ));
     }
 
+    if !branch_failures.is_empty() {
+        let count = branch_failures.len();
+        return Err(format!(
+            "cannot delete {count} {}",
+            if count == 1 { "branch" } else { "branches" }
+        ));
+    }
+
     Ok(())
 }
 
-fn build_reason(merged: bool, upstream_gone: bool, no_upstream: bool) -> String {
+fn build_reason(
+    merged: bool,
+    upstream_gone: bool,
+    no_upstream: bool,
+    unused: bool,
+    squash_merged: bool,
+) -> String {
     let mut parts: Vec<&str> = Vec::new();
     if merged {
         parts.push("merged");
     }
+    if squash_merged {
+        parts.push("squash-merged");
+    }
     if upstream_gone {
         parts.push("upstream gone");
     }
     if no_upstream {
         parts.push("no upstream");
     }
+    if unused {
+        parts.push("unused");
+    }
     debug_assert!(
         !parts.is_empty(),
         "build_reason called with no active flags"
@@ -486,15 +1179,42 @@ fn build_reason(merged: bool, upstream_gone: bool, no_upstream: bool) -> String
     parts.join(", ")
 }
 
+// A worktree under the managed root is named by its branch, as its path
+// already places it in a repo. One living elsewhere gets `<repo>:<branch>`
+// so its messages stay unambiguous when several repos are pruned at once.
+fn worktree_label(branch: &str, path: &Path, managed_root: Option<&Path>, repo: &str) -> String {
+    let managed =
+        managed_root.is_some_and(|root| worktree::canonicalize_or_self(path).starts_with(root));
+    if managed || repo.is_empty() {
+        branch.to_string()
+    } else {
+        format!("{repo}:{branch}")
+    }
+}
+
+fn reason_heading(reason: &str) -> String {
+    let heading = reason.replace(", ", " + ");
+    let mut chars = heading.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_uppercase().chain(chars).collect()
+    })
+}
+
 fn style_msg(msg: &str, clr: &Colors) -> String {
     if let Some(rest) = msg.strip_prefix("removed ") {
         style_action(clr.green, "removed", rest, clr)
+    } else if let Some(rest) = msg.strip_prefix("pruned ") {
+        style_action(clr.green, "pruned", rest, clr)
     } else if let Some(rest) = msg.strip_prefix("would remove ") {
         style_action(clr.yellow, "would remove", rest, clr)
     } else if let Some(rest) = msg.strip_prefix("skipping ") {
         style_action(clr.yellow, "skipping", rest, clr)
     } else if msg.starts_with("Removing ") {
         format!("{}{msg}{}", clr.dim, clr.reset)
+    } else if msg.starts_with("  ") {
+        msg.to_string()
+    } else if msg.ends_with(':') && msg.starts_with(char::is_uppercase) {
+        format!("{}{msg}{}", clr.bold, clr.reset)
     } else {
         format!("{}{msg}{}", clr.red, clr.reset)
     }
@@ -512,3 +1232,45 @@ fn style_action(verb_clr: &str, verb: &str, rest: &str, clr: &Colors) -> String
         format!("{verb_clr}{verb}{} {rest}", clr.reset)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reason_heading_capitalizes_and_joins() {
+        assert_eq!(reason_heading("merged"), "Merged");
+        assert_eq!(reason_heading("upstream gone"), "Upstream gone");
+        assert_eq!(
+            reason_heading("merged, upstream gone"),
+            "Merged + upstream gone"
+        );
+    }
+
+    #[test]
+    fn worktree_label_adds_repo_outside_managed_root() {
+        let root = Path::new("/home/u/.wt/worktrees");
+        assert_eq!(
+            worktree_label("feat", &root.join("ab12/repo"), Some(root), "repo"),
+            "feat"
+        );
+        assert_eq!(
+            worktree_label("feat", Path::new("/src/repo-feat"), Some(root), "repo"),
+            "repo:feat"
+        );
+        assert_eq!(
+            worktree_label("feat", Path::new("/src/repo-feat"), None, "repo"),
+            "repo:feat"
+        );
+    }
+
+    #[test]
+    fn expand_glob_matches_sorted_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["b", "a", ".hidden"] {
+            fs::create_dir(dir.path().join(name)).unwrap();
+        }
+        let matches = expand_glob(&dir.path().join("*"));
+        assert_eq!(matches, vec![dir.path().join("a"), dir.path().join("b")]);
+    }
+}
@@ -0,0 +1,52 @@
+use std::path::Path;
+
+use crate::commands::link;
+use crate::deleted;
+use crate::git::Git;
+use crate::lock;
+use crate::recency;
+use crate::terminal;
+use crate::worktree;
+
+pub fn run(branch: &str, repo: Option<&Path>, yes: bool) -> Result<(), String> {
+    let repo_root = Git::find_repo(repo)?;
+    let git = Git::new(&repo_root);
+    let _lock = lock::acquire(&git, &repo_root, "restore")?;
+
+    if git.has_local_branch(branch) {
+        return Err(format!(
+            "branch '{branch}' already exists, use `wt switch {branch}`"
+        ));
+    }
+
+    let common_dir = git.common_dir()?;
+    let tip = deleted::lookup(&common_dir, branch)
+        .filter(|sha| git.rev_parse(&format!("{sha}^{{commit}}")).is_some())
+        .or_else(|| git.reflog_tip(branch))
+        .ok_or_else(|| format!("no record of branch '{branch}' in wt's history or the reflog"))?;
+    let summary = git.commit_summary(&tip).unwrap_or_else(|| tip.clone());
+
+    eprintln!("found '{branch}' at {summary}");
+    if !yes && !terminal::confirm(&format!("restore '{branch}' and create a worktree?")) {
+        return Err("restore not confirmed, use --yes to skip the prompt".into());
+    }
+
+    let dest = worktree::create_dest(&repo_root, &git)?;
+    if let Err(e) = git.add_worktree(branch, &dest, Some(&tip), None) {
+        worktree::cleanup_dest(&dest);
+        return Err(e);
+    }
+    eprintln!("restored branch '{branch}'");
+    if let Err(e) = deleted::forget(&common_dir, branch) {
+        eprintln!("{e}");
+    }
+
+    // Best-effort: each failed link was already reported.
+    let _ = link::auto_link(&repo_root, &dest, &repo_root, false);
+    recency::touch(&repo_root, &dest);
+
+    println!("{}", dest.display());
+
+    terminal::print_cd_hint(branch);
+    Ok(())
+}
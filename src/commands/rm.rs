@@ -1,60 +1,143 @@
 use std::path::{Path, PathBuf};
 
+use crate::commands::list;
+use crate::config;
+use crate::deleted;
 use crate::fuzzy;
 use crate::git::Git;
+use crate::lock;
 use crate::terminal;
 use crate::worktree::{self, Resolved, Worktree};
 
+// How each target is removed, the same for every name and for --merged.
+#[derive(Clone, Copy)]
+pub struct Options {
+    pub force: bool,
+    pub keep_branch: bool,
+    // Run every check but report instead of removing.
+    pub dry_run: bool,
+    // With several targets, stop at the first one that fails instead of
+    // carrying on and reporting the failures together.
+    pub fail_fast: bool,
+    // Untracked files don't make a worktree dirty; they're removed with it.
+    pub ignore_untracked: bool,
+}
+
 pub fn run(
     names: &[String],
     repo: Option<&Path>,
-    force: bool,
-    keep_branch: bool,
+    opts: Options,
+    delete_branch: Option<&str>,
+    merged: bool,
+    by_index: bool,
 ) -> Result<(), String> {
+    if merged {
+        return remove_merged(repo, opts);
+    }
+    // Resolve every index before removing anything: the first removal
+    // changes the set, which invalidates the rest.
+    let resolved: Vec<String>;
+    let names = if by_index {
+        resolved = names
+            .iter()
+            .map(|n| list::resolve_index(repo, n).map(|p| p.display().to_string()))
+            .collect::<Result<_, _>>()?;
+        &resolved
+    } else {
+        names
+    };
     if names.len() == 1 {
-        return remove_one(&names[0], repo, force, keep_branch);
+        return remove_one(&names[0], repo, opts, delete_branch);
+    }
+    if delete_branch.is_some() {
+        return Err("--delete-branch takes a single worktree".into());
     }
     let mut errors = 0usize;
-    for name in names {
-        if let Err(e) = remove_one(name, repo, force, keep_branch) {
+    let mut stopped = false;
+    for (i, name) in names.iter().enumerate() {
+        if let Err(e) = remove_one(name, repo, opts, None) {
             eprintln!("{e}");
             errors += 1;
+            if opts.fail_fast {
+                stopped = i + 1 < names.len();
+                break;
+            }
         }
     }
-    if errors > 0 {
+    removal_errors(errors, stopped)
+}
+
+fn removal_errors(errors: usize, stopped: bool) -> Result<(), String> {
+    if errors == 0 {
+        return Ok(());
+    }
+    let noun = if errors == 1 { "worktree" } else { "worktrees" };
+    if stopped {
         Err(format!(
-            "cannot remove {errors} {}",
-            if errors == 1 { "worktree" } else { "worktrees" }
+            "cannot remove {errors} {noun}, stopped at the first failure (--fail-fast)"
         ))
     } else {
-        Ok(())
+        Err(format!("cannot remove {errors} {noun}"))
     }
 }
 
 fn remove_one(
     name_or_path: &str,
     repo: Option<&Path>,
-    force: bool,
-    keep_branch: bool,
+    opts: Options,
+    delete_branch: Option<&str>,
 ) -> Result<(), String> {
+    let Options {
+        force,
+        keep_branch,
+        dry_run,
+        fail_fast: _,
+        ignore_untracked,
+    } = opts;
     let (target, admin_repo, worktrees) = resolve_target(name_or_path, repo)?;
 
     let git = Git::new(&admin_repo);
+    let _lock = if dry_run {
+        None
+    } else {
+        Some(lock::acquire(&git, &admin_repo, "rm")?)
+    };
 
     let wt = worktree::find_by_path(&worktrees, &target)
         .ok_or_else(|| format!("not a registered worktree: {}", target.display()))?;
 
+    // In a bare repo the first entry is the repository itself, and every
+    // worktree is a linked one.
     if let Some(main_wt) = worktrees.first() {
         let main_path = worktree::canonicalize_or_self(&main_wt.path);
         if main_path == target {
+            let what = if main_wt.bare {
+                "the bare repository"
+            } else {
+                "the primary worktree"
+            };
+            return Err(format!("cannot remove {what}: {}", target.display()));
+        }
+    }
+
+    let cwd = std::env::current_dir().and_then(|p| p.canonicalize()).ok();
+    if worktree::is_independent(&config::get_independent(&admin_repo), &target) {
+        return remove_independent(&admin_repo, &target, opts, cwd.as_deref());
+    }
+
+    let branch = match delete_branch {
+        Some(_) if !wt.detached => {
             return Err(format!(
-                "cannot remove the primary worktree: {}",
+                "--delete-branch only applies to detached worktrees: {}",
                 target.display()
             ));
         }
-    }
-
-    let branch = wt.branch.clone();
+        Some(name) if !git.has_local_branch(name) => {
+            return Err(format!("branch '{name}' not found"));
+        }
+        Some(name) => Some(name.to_string()),
+        None => wt.branch.clone(),
+    };
     let branch_exists = branch.as_ref().is_some_and(|b| git.has_local_branch(b));
 
     if let Some(branch) = &branch
@@ -66,8 +149,6 @@ fn remove_one(
         ));
     }
 
-    let cwd = std::env::current_dir().and_then(|p| p.canonicalize()).ok();
-
     if worktree::is_cwd_inside(&target, cwd.as_deref()) {
         return Err(format!(
             "cannot remove {}: current directory is inside the worktree",
@@ -76,7 +157,14 @@ fn remove_one(
     }
 
     if !force {
-        if git.is_dirty(&target) {
+        if let Some(branch) = &branch
+            && git.is_protected_branch(branch)
+        {
+            return Err(format!(
+                "branch '{branch}' is protected (wt.protectedBranches), use --force to remove"
+            ));
+        }
+        if git.is_dirty(&target, ignore_untracked) {
             return Err("worktree has local changes, use --force to remove".into());
         }
         if let Some(branch) = &branch
@@ -90,26 +178,208 @@ fn remove_one(
         }
     }
 
-    git.remove_worktree(&target, force)?;
+    let kept = branch.clone().filter(|_| branch_exists && keep_branch);
+    let branch = branch.filter(|_| branch_exists && !keep_branch);
+    if dry_run {
+        would_remove(&target, branch.as_deref());
+        return Ok(());
+    }
+    remove_checked(
+        &git,
+        &target,
+        branch.as_deref(),
+        kept.as_deref(),
+        force,
+        ignore_untracked,
+        cwd.as_deref(),
+    )
+}
 
-    worktree::cleanup_empty_parent(&target, cwd.as_deref());
+// Same guards as prune: only clean, unlocked worktrees whose branch is
+// already in the base, never the base itself, a protected branch, or the
+// worktree the shell is standing in.
+fn remove_merged(repo: Option<&Path>, opts: Options) -> Result<(), String> {
+    let repo_root = Git::find_repo(repo)?;
+    let git = Git::new(&repo_root);
+    let _lock = if opts.dry_run {
+        None
+    } else {
+        Some(lock::acquire(&git, &repo_root, "rm")?)
+    };
+    let base = git
+        .base_ref()
+        .map_err(|e| format!("cannot find merged worktrees: {e}"))?;
+    let base_branch = git.strip_remote(&base);
 
-    let path_display = terminal::tilde_path(&target);
-    if let Some(branch) = &branch
-        && branch_exists
-        && !keep_branch
-    {
-        git.delete_branch(branch, force)?;
+    let output = git.list_worktrees()?;
+    let worktrees = worktree::parse_porcelain(&output);
+    let primary = worktree::find_primary(&worktrees, &repo_root).map(|wt| wt.path.clone());
+    let cwd = std::env::current_dir().and_then(|p| p.canonicalize()).ok();
+
+    let mut removed = 0usize;
+    let mut errors = 0usize;
+    let mut stopped = false;
+    for (i, wt) in worktrees.iter().enumerate() {
+        let Some(branch) = wt.branch.as_deref() else {
+            continue;
+        };
+        if wt.bare
+            || !wt.live()
+            || primary.as_ref() == Some(&wt.path)
+            || branch == base_branch
+            || git.is_protected_branch(branch)
+            || !git.is_ancestor(&format!("refs/heads/{branch}"), &base)
+        {
+            continue;
+        }
+        let skip = if wt.locked {
+            Some("locked")
+        } else if worktree::is_cwd_inside(&wt.path, cwd.as_deref()) {
+            Some("current directory")
+        } else if git.is_dirty(&wt.path, opts.ignore_untracked) {
+            Some("local changes")
+        } else {
+            None
+        };
+        if let Some(reason) = skip {
+            eprintln!("skipping {branch} ({reason})");
+            continue;
+        }
+
+        let kept = opts.keep_branch.then_some(branch);
+        let branch = (!opts.keep_branch).then_some(branch);
+        if opts.dry_run {
+            would_remove(&wt.path, branch);
+            removed += 1;
+            continue;
+        }
+        match remove_checked(
+            &git,
+            &wt.path,
+            branch,
+            kept,
+            false,
+            opts.ignore_untracked,
+            cwd.as_deref(),
+        ) {
+            Ok(()) => removed += 1,
+            Err(e) => {
+                eprintln!("{e}");
+                errors += 1;
+                if opts.fail_fast {
+                    stopped = i + 1 < worktrees.len();
+                    break;
+                }
+            }
+        }
+    }
+
+    removal_errors(errors, stopped)?;
+    if removed == 0 {
+        eprintln!("no merged worktrees");
+    }
+    Ok(())
+}
+
+fn would_remove(target: &Path, branch: Option<&str>) {
+    let path_display = terminal::tilde_path(target);
+    match branch {
+        Some(branch) => eprintln!("would remove worktree and branch '{branch}' ({path_display})"),
+        None => eprintln!("would remove worktree ({path_display})"),
+    }
+}
+
+// Removes a worktree whose checks have passed, then its branch if one is
+// given, remembering the branch's tip for `wt restore`. A `kept` branch is
+// only named in the message, to make plain that it survived. `git worktree
+// remove` refuses untracked files on its own, so `ignore_untracked` gets
+// past them without --force's disregard for tracked changes.
+fn remove_checked(
+    git: &Git,
+    target: &Path,
+    branch: Option<&str>,
+    kept: Option<&str>,
+    force: bool,
+    ignore_untracked: bool,
+    cwd: Option<&Path>,
+) -> Result<(), String> {
+    if ignore_untracked && !force {
+        git.remove_worktree_past_untracked(target)?;
+    } else {
+        git.remove_worktree(target, force)?;
+    }
+
+    worktree::cleanup_empty_parent(target, cwd);
+
+    let path_display = terminal::tilde_path(target);
+    if let Some(branch) = branch {
+        // Merge status was already checked by the caller; `git branch -d` would
+        // re-check against HEAD only and reject branches merged into the base elsewhere.
+        let tip = git.rev_parse(&format!("refs/heads/{branch}"));
+        git.delete_branch(branch, true)?;
+        if let Some(tip) = tip
+            && let Err(e) = git
+                .common_dir()
+                .and_then(|dir| deleted::record(&dir, branch, &tip))
+        {
+            eprintln!("cannot remember deleted branch for `wt restore`: {e}");
+        }
         eprintln!(
             "removed worktree and branch '{}' ({})",
             branch, path_display
         );
+    } else if let Some(kept) = kept {
+        eprintln!("removed worktree ({path_display}), branch '{kept}' kept");
     } else {
         eprintln!("removed worktree ({})", path_display);
     }
     Ok(())
 }
 
+// An independent checkout is its own clone: git doesn't know it as a
+// worktree, and its branch lives in the clone, so the whole directory goes.
+// Commits it has not pushed back to the repo would go with it.
+fn remove_independent(
+    admin_repo: &Path,
+    target: &Path,
+    opts: Options,
+    cwd: Option<&Path>,
+) -> Result<(), String> {
+    if worktree::is_cwd_inside(target, cwd) {
+        return Err(format!(
+            "cannot remove {}: current directory is inside the worktree",
+            target.display()
+        ));
+    }
+    if !opts.force {
+        let git = Git::new(target);
+        if git.is_dirty(target, opts.ignore_untracked) {
+            return Err("worktree has local changes, use --force to remove".into());
+        }
+        if let (_, Some(ahead), _) = Git::worktree_status(target)
+            && ahead > 0
+        {
+            let commits = if ahead == 1 { "commit" } else { "commits" };
+            return Err(format!(
+                "independent checkout has {ahead} unpushed {commits}, use --force to remove"
+            ));
+        }
+    }
+    let path_display = terminal::tilde_path(target);
+    if opts.dry_run {
+        eprintln!("would remove independent checkout ({path_display})");
+        return Ok(());
+    }
+    std::fs::remove_dir_all(target)
+        .map_err(|e| format!("cannot remove {}: {e}", target.display()))?;
+    if let Err(e) = config::remove_independent(admin_repo, target) {
+        eprintln!("cannot forget independent checkout: {e}");
+    }
+    worktree::cleanup_empty_parent(target, cwd);
+    eprintln!("removed independent checkout ({path_display})");
+    Ok(())
+}
+
 fn resolve_target(
     name_or_path: &str,
     repo: Option<&Path>,
@@ -120,7 +390,8 @@ fn resolve_target(
     if let Some(repo_root) = repo_root {
         let git = Git::new(&repo_root);
         let output = git.list_worktrees()?;
-        let worktrees = worktree::parse_porcelain(&output);
+        let mut worktrees = worktree::parse_porcelain(&output);
+        worktrees.extend(worktree::load_independent(&repo_root));
 
         match worktree::resolve_worktree(&worktrees, name_or_path, &git) {
             Resolved::Found(wt) => return Ok((wt.path.clone(), repo_root, worktrees)),
@@ -154,6 +425,15 @@ fn resolve_target(
     }
 
     let input = Path::new(name_or_path);
+    if input.exists()
+        && let Ok(target) = std::fs::canonicalize(input)
+        && let Some(owner) = config::independent_owner(&target)
+    {
+        let git = Git::new(&owner);
+        let mut worktrees = worktree::parse_porcelain(&git.list_worktrees()?);
+        worktrees.extend(worktree::load_independent(&owner));
+        return Ok((target, owner, worktrees));
+    }
     if input.exists() {
         let target = resolve_path(input)?;
         let (admin, worktrees) = load_worktrees(&target)?;
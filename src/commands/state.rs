@@ -0,0 +1,11 @@
+use std::path::Path;
+
+use crate::recency;
+use crate::terminal;
+
+pub fn prune() -> Result<(), String> {
+    for path in recency::prune()? {
+        eprintln!("forgot {}", terminal::tilde_path(Path::new(&path)));
+    }
+    Ok(())
+}
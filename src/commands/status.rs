@@ -0,0 +1,306 @@
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::commands::list;
+use crate::git::Git;
+use crate::terminal::{self, trunc};
+use crate::worktree::{self, WorktreeInfo};
+
+#[derive(Serialize)]
+struct StatusEntry {
+    branch: Option<String>,
+    path: String,
+    dirty: bool,
+    ahead: Option<u64>,
+    behind: Option<u64>,
+    merged: bool,
+    upstream_gone: bool,
+}
+
+#[derive(Default, Serialize)]
+struct Counts {
+    worktrees: usize,
+    dirty: usize,
+    merged: usize,
+    upstream_gone: usize,
+}
+
+impl Counts {
+    fn of(entries: &[StatusEntry]) -> Self {
+        Self {
+            worktrees: entries.len(),
+            dirty: entries.iter().filter(|e| e.dirty).count(),
+            merged: entries.iter().filter(|e| e.merged).count(),
+            upstream_gone: entries.iter().filter(|e| e.upstream_gone).count(),
+        }
+    }
+
+    fn total<'a>(all: impl Iterator<Item = &'a Counts>) -> Self {
+        all.fold(Self::default(), |sum, c| Self {
+            worktrees: sum.worktrees + c.worktrees,
+            dirty: sum.dirty + c.dirty,
+            merged: sum.merged + c.merged,
+            upstream_gone: sum.upstream_gone + c.upstream_gone,
+        })
+    }
+
+    fn summary(&self) -> String {
+        format!(
+            "{} {}: {} dirty, {} merged, {} upstream-gone",
+            self.worktrees,
+            if self.worktrees == 1 {
+                "worktree"
+            } else {
+                "worktrees"
+            },
+            self.dirty,
+            self.merged,
+            self.upstream_gone
+        )
+    }
+}
+
+#[derive(Serialize)]
+struct Status {
+    worktrees: Vec<StatusEntry>,
+    counts: Counts,
+}
+
+#[derive(Serialize)]
+struct RepoStatus {
+    repo: String,
+    path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    worktrees: Vec<StatusEntry>,
+    counts: Counts,
+}
+
+#[derive(Serialize)]
+struct AllStatus {
+    repos: Vec<RepoStatus>,
+    counts: Counts,
+}
+
+// Reports state rather than judging it: once the repo is found this exits 0,
+// and CI gates on the JSON counts instead.
+pub fn run(repo: Option<&Path>, all: bool, json: bool) -> Result<(), String> {
+    if all {
+        return run_all(json);
+    }
+    let repo_root = Git::find_repo(repo)?;
+    let git = Git::new(&repo_root);
+    let cwd = std::env::current_dir()
+        .ok()
+        .and_then(|p| p.canonicalize().ok());
+    let (infos, entries) = collect(&git, cwd.as_deref(), true)?;
+    let counts = Counts::of(&entries);
+
+    if json {
+        let status = Status {
+            worktrees: entries,
+            counts,
+        };
+        let json_str =
+            serde_json::to_string(&status).map_err(|e| format!("cannot serialize json: {e}"))?;
+        println!("{json_str}");
+        return Ok(());
+    }
+
+    print_table(&infos, &entries, terminal::width());
+    eprintln!("{}", counts.summary());
+    Ok(())
+}
+
+// Every managed repo at once, on the shared worker pool. A repo that can't be
+// read (deleted, corrupt) still gets an entry carrying its error, so a
+// dashboard sees it rather than silently losing it.
+fn run_all(json: bool) -> Result<(), String> {
+    let wt_root = worktree::canonicalize_or_self(&worktree::worktrees_root()?);
+    let admin_repos: Vec<_> = worktree::discover_repos(&wt_root).into_iter().collect();
+    let cwd = std::env::current_dir()
+        .ok()
+        .and_then(|p| p.canonicalize().ok());
+    let progress = terminal::Progress::new(admin_repos.len());
+
+    let repos: Vec<(Vec<WorktreeInfo>, RepoStatus)> =
+        worktree::map_repos(&admin_repos, |repo_path| {
+            let name = worktree::repo_basename(repo_path);
+            let result = collect(&Git::new(repo_path), cwd.as_deref(), false);
+            progress.tick(&name);
+            let (infos, entries, error) = match result {
+                Ok((infos, entries)) => (infos, entries, None),
+                Err(e) => (vec![], vec![], Some(e)),
+            };
+            let status = RepoStatus {
+                repo: name,
+                path: repo_path.display().to_string(),
+                error,
+                counts: Counts::of(&entries),
+                worktrees: entries,
+            };
+            (infos, status)
+        });
+    progress.clear();
+
+    let counts = Counts::total(repos.iter().map(|(_, r)| &r.counts));
+    if json {
+        let status = AllStatus {
+            repos: repos.into_iter().map(|(_, r)| r).collect(),
+            counts,
+        };
+        let json_str =
+            serde_json::to_string(&status).map_err(|e| format!("cannot serialize json: {e}"))?;
+        println!("{json_str}");
+        return Ok(());
+    }
+
+    let cols = terminal::width();
+    let clr = terminal::colors();
+    for (i, (infos, repo)) in repos.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!("{}{}:{}", clr.bold, repo.repo, clr.reset);
+        match &repo.error {
+            Some(e) => println!("  {}{e}{}", clr.red, clr.reset),
+            None => print_table(infos, &repo.worktrees, cols),
+        }
+    }
+    eprintln!("{}", counts.summary());
+    Ok(())
+}
+
+// One repo's non-bare worktrees and their status. A `standalone` run shows
+// per-worktree progress and reports a missing base branch; under `--all` the
+// repo is one of many, so it stays quiet and just skips merged detection.
+fn collect(
+    git: &Git,
+    cwd: Option<&Path>,
+    standalone: bool,
+) -> Result<(Vec<WorktreeInfo>, Vec<StatusEntry>), String> {
+    let output = git.list_worktrees()?;
+    let worktrees: Vec<_> = worktree::parse_porcelain(&output)
+        .into_iter()
+        .filter(|wt| !wt.bare)
+        .collect();
+    let current_path = worktree::find_current_worktree(&worktrees, cwd);
+    let infos = {
+        let progress = standalone.then(|| terminal::Progress::new(worktrees.len()));
+        worktree::enrich_worktrees(&worktrees, current_path.as_deref(), progress.as_ref())
+    };
+
+    // Without a base branch nothing counts as merged; the rest still shows.
+    let base = git
+        .base_ref()
+        .inspect_err(|e| {
+            if standalone {
+                eprintln!("{e}, not checking merged status");
+            }
+        })
+        .ok();
+    let base_branch = base.as_deref().map(|b| git.strip_remote(b));
+    let upstreams_gone = git.upstreams_gone();
+
+    let entries: Vec<StatusEntry> = infos
+        .iter()
+        .map(|wt| {
+            let branch = wt.branch.as_deref();
+            let merged = branch.is_some_and(|branch| {
+                base_branch != Some(branch)
+                    && base
+                        .as_deref()
+                        .is_some_and(|base| git.is_ancestor(&format!("refs/heads/{branch}"), base))
+            });
+            StatusEntry {
+                branch: wt.branch.clone(),
+                path: wt.path.display().to_string(),
+                dirty: wt.dirty,
+                ahead: wt.ahead,
+                behind: wt.behind,
+                merged,
+                upstream_gone: branch
+                    .and_then(|b| upstreams_gone.get(b).copied())
+                    .unwrap_or(false),
+            }
+        })
+        .collect();
+    Ok((infos, entries))
+}
+
+fn print_table(infos: &[WorktreeInfo], entries: &[StatusEntry], cols: usize) {
+    let clr = terminal::colors();
+    let state_w: usize = 5;
+    let sync_w: usize = 9;
+    let (branch_w, notes_w) =
+        list::branch_and_rest_widths(cols.saturating_sub(state_w + sync_w + 11));
+    println!(
+        "  {:<branch_w$}   {:<state_w$}   {:<sync_w$}   NOTES",
+        "BRANCH", "STATE", "SYNC"
+    );
+    for (wt, entry) in infos.iter().zip(entries) {
+        let marker = if wt.current { "*" } else { " " };
+        let branch = trunc(wt.branch.as_deref().unwrap_or("(detached)"), branch_w);
+        let branch_pad = branch_w.saturating_sub(branch.chars().count());
+        let branch_color = if wt.current { clr.green } else { "" };
+        let (state, state_color) = if wt.prunable {
+            ("-", "")
+        } else if wt.dirty {
+            ("dirty", clr.yellow)
+        } else {
+            ("clean", "")
+        };
+        let mut notes = Vec::new();
+        if wt.prunable {
+            notes.push("missing");
+        }
+        if entry.merged {
+            notes.push("merged");
+        }
+        if entry.upstream_gone {
+            notes.push("upstream gone");
+        }
+        let line = format!(
+            "{marker} {branch_color}{branch}{}{}   {state_color}{state:<state_w$}{}   {:<sync_w$}   {}",
+            clr.reset,
+            " ".repeat(branch_pad),
+            clr.reset,
+            sync_label(wt.ahead, wt.behind),
+            trunc(&notes.join(", "), notes_w)
+        );
+        println!("{}", line.trim_end());
+    }
+}
+
+// `-` means no upstream to compare against; `=` means level with it.
+fn sync_label(ahead: Option<u64>, behind: Option<u64>) -> String {
+    if ahead.is_none() && behind.is_none() {
+        return "-".into();
+    }
+    let mut parts = Vec::new();
+    if let Some(a) = ahead.filter(|&a| a > 0) {
+        parts.push(format!("↑{a}"));
+    }
+    if let Some(b) = behind.filter(|&b| b > 0) {
+        parts.push(format!("↓{b}"));
+    }
+    if parts.is_empty() {
+        "=".into()
+    } else {
+        parts.join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sync_label_marks_missing_and_level_upstreams() {
+        assert_eq!(sync_label(None, None), "-");
+        assert_eq!(sync_label(Some(0), Some(0)), "=");
+        assert_eq!(sync_label(Some(2), Some(0)), "↑2");
+        assert_eq!(sync_label(Some(1), Some(3)), "↑1 ↓3");
+    }
+}
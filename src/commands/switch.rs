@@ -1,18 +1,127 @@
 use std::path::Path;
+use std::process::Command;
 
-use crate::commands::link;
+use crate::commands::{link, list, new, tui};
+use crate::config;
+use crate::editor;
 use crate::fuzzy;
-use crate::git::Git;
+use crate::git::{self, Git};
+use crate::history;
+use crate::hooks;
+use crate::recency;
 use crate::terminal;
 use crate::worktree;
 
-pub fn run(name: &str, create: bool, repo: Option<&Path>) -> Result<(), String> {
+// How the worktree is handed over once it is found or created.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Handoff {
+    // Print its path.
+    Path,
+    // `--shell`: run $SHELL in it.
+    Shell,
+    // `--eval`: print shell code that cds there and exports WT_BRANCH and WT_REPO.
+    Eval,
+}
+
+// How `run` ended. `Dismissed` means the picker was closed without a choice;
+// `main` exits 130 for it, like an interrupted command, having printed
+// nothing so `cd "$(wt switch)"` stays put.
+pub enum Outcome {
+    Entered,
+    Dismissed,
+}
+
+pub fn run(
+    name: Option<&str>,
+    create: bool,
+    repo: Option<&Path>,
+    handoff: Handoff,
+    path_only: bool,
+    open: Option<bool>,
+    by_index: bool,
+) -> Result<Outcome, String> {
+    match name {
+        Some(name) => switch(name, create, repo, handoff, path_only, open, by_index)
+            .map(|()| Outcome::Entered),
+        // The picker needs someone at the keyboard; scripts must name a branch.
+        None if terminal::is_stdin_tty() && terminal::is_stderr_tty() => {
+            pick(repo, handoff, path_only, open)
+        }
+        None => Err("no branch given".into()),
+    }
+}
+
+fn pick(
+    repo: Option<&Path>,
+    handoff: Handoff,
+    path_only: bool,
+    open: Option<bool>,
+) -> Result<Outcome, String> {
+    let repo_root = Git::find_repo(repo)?;
+    let git = Git::new(&repo_root);
+    let output = git.list_worktrees()?;
+    let worktrees = worktree::parse_porcelain(&output);
+
+    let live: Vec<_> = worktrees
+        .iter()
+        .filter(|wt| wt.live() && !wt.bare)
+        .cloned()
+        .collect();
+    let Some(path) = tui::pick_worktree(&repo_root, &live)? else {
+        return Ok(Outcome::Dismissed);
+    };
+    let wt = worktree::find_by_path(&worktrees, &path).ok_or("no worktree selected")?;
+    let label = branch_label(wt);
+    enter_existing(&git, &repo_root, &wt.path, &label, handoff, path_only, open)?;
+    Ok(Outcome::Entered)
+}
+
+fn switch(
+    name: &str,
+    create: bool,
+    repo: Option<&Path>,
+    handoff: Handoff,
+    path_only: bool,
+    open: Option<bool>,
+    by_index: bool,
+) -> Result<(), String> {
+    // Warnings and errors still reach stderr; --path-only only drops progress.
+    let status = |msg: &str| {
+        if !path_only {
+            eprintln!("{msg}");
+        }
+    };
+    if name != "-" && !by_index {
+        git::reject_leading_dash("branch name", name)?;
+    }
     let repo_root = Git::find_repo(repo)?;
     let git = Git::new(&repo_root);
 
     let output = git.list_worktrees()?;
     let worktrees = worktree::parse_porcelain(&output);
 
+    if by_index {
+        let path = list::resolve_index(Some(&repo_root), name)?;
+        let wt = worktree::find_by_path(&worktrees, &path)
+            .filter(|wt| wt.live() && !wt.bare)
+            .ok_or_else(|| format!("row {name} is not a worktree to switch to"))?;
+        let label = branch_label(wt);
+        return enter_existing(&git, &repo_root, &wt.path, &label, handoff, path_only, open);
+    }
+
+    if name == "-" {
+        if create {
+            return Err("cannot create a branch for `-`, it means the previous worktree".into());
+        }
+        let previous = history::previous(&git, |path| {
+            worktree::find_by_path(&worktrees, path).is_some_and(|wt| wt.live())
+        })
+        .ok_or("no previous worktree")?;
+        let wt = worktree::find_by_path(&worktrees, &previous).ok_or("no previous worktree")?;
+        let label = branch_label(wt);
+        return enter_existing(&git, &repo_root, &wt.path, &label, handoff, path_only, open);
+    }
+
     let branch_matches: Vec<_> = worktrees
         .iter()
         .filter(|wt| wt.branch.as_deref() == Some(name))
@@ -23,13 +132,12 @@ pub fn run(name: &str, create: bool, repo: Option<&Path>) -> Result<(), String>
     match matches.as_slice() {
         [one] => {
             if has_prunable {
-                eprintln!("pruning stale worktree metadata");
+                status("pruning stale worktree metadata");
                 if let Err(e) = git.prune_worktrees(false) {
                     eprintln!("{e}");
                 }
             }
-            println!("{}", one.path.display());
-            return Ok(());
+            return enter_existing(&git, &repo_root, &one.path, name, handoff, path_only, open);
         }
         [_, _, ..] => {
             eprintln!("ambiguous name '{name}'; matches:");
@@ -42,7 +150,7 @@ pub fn run(name: &str, create: bool, repo: Option<&Path>) -> Result<(), String>
     }
 
     if has_prunable {
-        eprintln!("pruning stale worktree metadata");
+        status("pruning stale worktree metadata");
         git.prune_worktrees(false)?;
     }
 
@@ -77,12 +185,18 @@ pub fn run(name: &str, create: bool, repo: Option<&Path>) -> Result<(), String>
         }
     }
 
+    if !is_branch {
+        git.validate_branch_name(name)?;
+        new::require_commits(&git)?;
+    }
+    config::load_repo(&repo_root)?;
+
     let dest = worktree::create_dest(&repo_root, &git)?;
 
     let result = if is_branch {
-        git.checkout_worktree(name, &dest)
+        git.checkout_worktree(name, &dest, None)
     } else {
-        git.add_worktree(name, &dest, None)
+        git.add_worktree(name, &dest, None, None)
     };
 
     if let Err(e) = result {
@@ -91,17 +205,111 @@ pub fn run(name: &str, create: bool, repo: Option<&Path>) -> Result<(), String>
     }
 
     if is_branch {
-        eprintln!("checking out '{name}'");
+        status(&format!("checking out '{name}'"));
     } else {
-        eprintln!("creating branch '{name}'");
+        status(&format!("creating branch '{name}'"));
+        if !path_only {
+            new::note_if_behind(&git, None);
+        }
     }
 
     let primary_path = worktree::find_primary(&worktrees, &repo_root)
         .map_or(repo_root.as_path(), |wt| wt.path.as_path());
-    link::auto_link(&repo_root, &dest, primary_path);
+    // Best-effort: each failed link was already reported.
+    let _ = link::auto_link(&repo_root, &dest, primary_path, path_only);
+    hooks::post_create(&repo_root, &dest);
+
+    recency::touch(&repo_root, &dest);
+    history::record(&git, &dest);
+    let open = open.unwrap_or_else(|| git.wt_flag("openOnCreate"));
+    if handoff == Handoff::Shell && terminal::is_stdout_tty() {
+        if open {
+            editor::open(&dest, path_only);
+        }
+        return open_shell(&dest, name, &repo_root);
+    }
+    print_handoff(&dest, name, &repo_root, handoff);
+
+    if !path_only && handoff != Handoff::Eval {
+        terminal::print_cd_hint(name);
+    }
+    if open {
+        editor::open(&dest, path_only);
+    }
+    Ok(())
+}
 
-    println!("{}", dest.display());
+// What WT_BRANCH is set to for a worktree reached by something other than
+// its branch name.
+fn branch_label(wt: &worktree::Worktree) -> String {
+    wt.branch
+        .clone()
+        .unwrap_or_else(|| worktree::repo_basename(&wt.path))
+}
 
-    terminal::print_cd_hint(name);
+fn enter_existing(
+    git: &Git,
+    repo_root: &Path,
+    path: &Path,
+    branch: &str,
+    handoff: Handoff,
+    path_only: bool,
+    open: Option<bool>,
+) -> Result<(), String> {
+    recency::touch(repo_root, path);
+    history::record(git, path);
+    // wt.openOnCreate is about new worktrees; an existing one only opens
+    // when asked.
+    let open = open == Some(true);
+    if handoff == Handoff::Shell && terminal::is_stdout_tty() {
+        if open {
+            editor::open(path, path_only);
+        }
+        return open_shell(path, branch, repo_root);
+    }
+    print_handoff(path, branch, repo_root, handoff);
+    if open {
+        editor::open(path, path_only);
+    }
     Ok(())
 }
+
+// `--eval` sets the same context `--shell` does, for `eval "$(...)"` in the
+// calling shell. Everything is single-quoted, so spaces and `$` survive.
+fn print_handoff(path: &Path, branch: &str, repo_root: &Path, handoff: Handoff) {
+    if handoff != Handoff::Eval {
+        println!("{}", path.display());
+        return;
+    }
+    println!(
+        "cd {}; export WT_BRANCH={}; export WT_REPO={}",
+        terminal::shell_quote(&path.to_string_lossy()),
+        terminal::shell_quote(branch),
+        terminal::shell_quote(&repo_root.to_string_lossy()),
+    );
+}
+
+// Without a terminal there is no one to type into the shell, so callers that
+// capture stdout get the path as usual.
+fn open_shell(path: &Path, branch: &str, repo_root: &Path) -> Result<(), String> {
+    let shell = std::env::var_os("SHELL")
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| "/bin/sh".into());
+    let mut cmd = Command::new(&shell);
+    cmd.current_dir(path)
+        .env("WT_BRANCH", branch)
+        .env("WT_REPO", repo_root);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        let e = cmd.exec();
+        Err(format!("cannot run {}: {e}", shell.to_string_lossy()))
+    }
+    #[cfg(not(unix))]
+    {
+        cmd.status()
+            .map(|_| ())
+            .map_err(|e| format!("cannot run {}: {e}", shell.to_string_lossy()))
+    }
+}
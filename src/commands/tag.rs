@@ -0,0 +1,27 @@
+use std::path::Path;
+
+use crate::git::{self, Git};
+use crate::worktree;
+
+// Only for detached worktrees: on a branch, `git tag` already does the job
+// and a wrapper would just hide which commit got tagged.
+pub fn run(name: &str, message: Option<&str>, repo: Option<&Path>) -> Result<(), String> {
+    let repo_root = Git::find_repo(repo)?;
+    let git = Git::new(&repo_root);
+    let output = git.list_worktrees()?;
+    let worktrees = worktree::parse_porcelain(&output);
+    let wt = worktree::find_by_path(&worktrees, &repo_root)
+        .ok_or_else(|| format!("not inside a worktree: {}", repo_root.display()))?;
+    if let Some(branch) = &wt.branch {
+        return Err(format!(
+            "worktree is on branch '{branch}', wt tag only tags a detached HEAD"
+        ));
+    }
+
+    git::reject_leading_dash("tag name", name)?;
+    git.create_tag(name, &wt.head, message)?;
+
+    let short = wt.head.get(..7).unwrap_or(&wt.head);
+    eprintln!("tagged {short} as '{name}'");
+    Ok(())
+}
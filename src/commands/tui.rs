@@ -10,7 +10,8 @@ use ratatui::widgets::{HighlightSpacing, List, ListItem, ListState};
 
 use crate::fuzzy;
 use crate::terminal::{self as term, trunc, trunc_tail};
-use crate::worktree;
+use crate::tui::Target;
+use crate::worktree::{self, Worktree};
 
 const EMPTY_HINT: &str = "no matches · backspace to edit";
 
@@ -105,6 +106,8 @@ struct App {
     collapsed: bool,
     content_width: u16,
     match_count: usize,
+    // One repo with no repos pane, for `wt switch` without a name; esc quits.
+    scoped: bool,
 }
 
 impl App {
@@ -159,9 +162,19 @@ impl App {
             collapsed: false,
             content_width,
             match_count,
+            scoped: false,
         }
     }
 
+    fn scoped(repo: RepoData) -> Self {
+        let mut app = Self::new(vec![repo]);
+        app.scoped = true;
+        app.active_pane = Pane::Worktrees;
+        app.content_width = app.content_width.saturating_sub(app.repos_w + 2);
+        app.color = term::color_enabled(term::is_stderr_tty());
+        app
+    }
+
     fn fg(&self, color: Color) -> Style {
         if self.color {
             Style::new().fg(color)
@@ -334,7 +347,7 @@ fn handle_key(app: &mut App, key: event::KeyEvent) {
             if !app.filter.is_empty() {
                 app.filter.clear();
                 app.refilter();
-            } else if app.active_pane == Pane::Worktrees && !app.collapsed {
+            } else if app.active_pane == Pane::Worktrees && !app.collapsed && !app.scoped {
                 app.active_pane = Pane::Repos;
             } else {
                 app.quit = true;
@@ -359,8 +372,8 @@ fn handle_key(app: &mut App, key: event::KeyEvent) {
                 app.quit = true;
             }
         },
-        KeyCode::Tab | KeyCode::BackTab if !app.collapsed => app.next_pane(),
-        KeyCode::Left => app.active_pane = Pane::Repos,
+        KeyCode::Tab | KeyCode::BackTab if !app.collapsed && !app.scoped => app.next_pane(),
+        KeyCode::Left if !app.scoped => app.active_pane = Pane::Repos,
         KeyCode::Right if !app.collapsed => app.active_pane = Pane::Worktrees,
         KeyCode::Up => app.cursor_move(-1),
         KeyCode::Down => app.cursor_move(1),
@@ -451,10 +464,12 @@ fn render(frame: &mut Frame, app: &mut App) {
     // Always update collapsed so refilter() and handle_key() see a current value
     // even when filtered_repo_indices is empty (the empty branch renders nothing
     // that would otherwise set this flag).
-    app.collapsed = pane_w < wt_min + spacing + app.repos_w;
+    app.collapsed = !app.scoped && pane_w < wt_min + spacing + app.repos_w;
 
     if app.filtered_repo_indices.is_empty() {
         frame.render_widget(EMPTY_HINT.dim(), pane_area);
+    } else if app.scoped {
+        render_worktrees(frame, app, pane_area);
     } else if app.collapsed {
         render_repos(frame, app, pane_area);
     } else {
@@ -608,6 +623,7 @@ fn footer_line(app: &App, width: u16, visible_rows: u16) -> Line<'static> {
     let effective_pane = app.effective_pane();
     let (enter_action, esc_action) = match effective_pane {
         Pane::Repos => (" open", " quit"),
+        Pane::Worktrees if app.scoped => (" select", " quit"),
         Pane::Worktrees => (" select", " back"),
     };
 
@@ -639,10 +655,11 @@ fn footer_line(app: &App, width: u16, visible_rows: u16) -> Line<'static> {
     let filter_w = span_w(&filter_tier);
     let pos_w = pos_tier.as_ref().map_or(0, |t| span_w(t));
 
-    let effective_tab_w = if app.collapsed { 0 } else { tab_w };
+    let no_tab = app.collapsed || app.scoped;
+    let effective_tab_w = if no_tab { 0 } else { tab_w };
     let mut spans = base;
     if w >= base_w + effective_tab_w {
-        if !app.collapsed {
+        if !no_tab {
             spans.extend(tab_tier);
         }
         if w >= span_w(&spans) + filter_w + pos_w {
@@ -686,7 +703,7 @@ fn build_repos(infos: Vec<worktree::RepoInfo>) -> Vec<RepoData> {
         .collect()
 }
 
-fn event_loop(terminal: &mut crate::tui::StdoutTerminal, app: &mut App) -> io::Result<()> {
+fn event_loop(terminal: &mut crate::tui::PickerTerminal, app: &mut App) -> io::Result<()> {
     loop {
         terminal.draw(|frame| render(frame, app))?;
         loop {
@@ -711,7 +728,7 @@ pub fn run() -> Result<(), String> {
         return Err("cannot launch picker, stdout is not a terminal".into());
     }
 
-    let repo_infos = worktree::load_all(&worktree::worktrees_root()?)?;
+    let repo_infos = worktree::load_all(&worktree::worktrees_root()?, false)?;
     let repos = build_repos(repo_infos);
     if repos.is_empty() {
         return Err("no worktrees".into());
@@ -719,8 +736,10 @@ pub fn run() -> Result<(), String> {
     let mut app = App::new(repos);
     let height = viewport_height(&app);
 
-    crate::tui::run(height, |terminal| event_loop(terminal, &mut app))
-        .map_err(|e| format!("cannot run picker: {e}"))?;
+    crate::tui::run(Target::Stdout, height, |terminal| {
+        event_loop(terminal, &mut app)
+    })
+    .map_err(|e| format!("cannot run picker: {e}"))?;
 
     if let Some(path) = &app.selected_path
         && let Ok(f) = std::env::var("__WT_CD")
@@ -733,6 +752,30 @@ pub fn run() -> Result<(), String> {
     Ok(())
 }
 
+// `wt switch` with no name: the picker over one repo's worktrees, drawn on
+// stderr. None when it is dismissed without a choice.
+pub fn pick_worktree(repo_root: &Path, worktrees: &[Worktree]) -> Result<Option<PathBuf>, String> {
+    let cwd = std::env::current_dir().and_then(|p| p.canonicalize()).ok();
+    let current = worktree::find_current_worktree(worktrees, cwd.as_deref());
+    let infos = worktree::enrich_worktrees(worktrees, current.as_deref(), None);
+    let Some(repo) = build_repos(vec![worktree::RepoInfo {
+        name: worktree::repo_basename(repo_root),
+        path: repo_root.to_path_buf(),
+        worktrees: infos,
+    }])
+    .pop() else {
+        return Err("no worktrees".into());
+    };
+    let mut app = App::scoped(repo);
+    let height = viewport_height(&app);
+
+    crate::tui::run(Target::Stderr, height, |terminal| {
+        event_loop(terminal, &mut app)
+    })
+    .map_err(|e| format!("cannot run picker: {e}"))?;
+    Ok(app.selected_path)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -916,6 +959,22 @@ mod tests {
         assert_eq!(app.active_pane, Pane::Repos);
     }
 
+    #[test]
+    fn scoped_picker_stays_in_worktrees_and_esc_quits() {
+        let mut app = App::scoped(test_repos().remove(0));
+        assert_eq!(app.active_pane, Pane::Worktrees);
+        for code in [KeyCode::Left, KeyCode::Tab, KeyCode::BackTab] {
+            handle_key(&mut app, event::KeyEvent::new(code, KeyModifiers::NONE));
+            assert_eq!(app.active_pane, Pane::Worktrees, "{code:?} moved panes");
+        }
+        handle_key(
+            &mut app,
+            event::KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE),
+        );
+        assert!(app.quit);
+        assert!(app.selected_path.is_none());
+    }
+
     #[test]
     fn ctrl_c_quits_when_filter_empty() {
         let mut app = App::new(test_repos());
@@ -1481,6 +1540,7 @@ mod tests {
     fn build_repos_filters_bare_prunable_and_sorts() {
         let infos = vec![worktree::RepoInfo {
             name: "repo".into(),
+            path: PathBuf::from("/repo"),
             worktrees: vec![
                 worktree::WorktreeInfo {
                     path: PathBuf::from("/admin"),
@@ -1548,6 +1608,7 @@ mod tests {
     fn build_repos_drops_empty() {
         let infos = vec![worktree::RepoInfo {
             name: "bare-only".into(),
+            path: PathBuf::from("/bare-only"),
             worktrees: vec![worktree::WorktreeInfo {
                 path: PathBuf::from("/admin"),
                 head: "abc".into(),
@@ -1948,6 +2009,7 @@ mod tests {
     fn build_repos_drops_all_prunable() {
         let infos = vec![worktree::RepoInfo {
             name: "stale-repo".into(),
+            path: PathBuf::from("/stale-repo"),
             worktrees: vec![worktree::WorktreeInfo {
                 path: PathBuf::from("/wt/stale"),
                 head: "abc".into(),
@@ -7,8 +7,24 @@ use serde::{Deserialize, Serialize};
 pub struct Config {
     #[serde(default)]
     pub links: BTreeMap<String, Vec<String>>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub independent: BTreeMap<String, Vec<String>>,
 }
 
+// Optional per-repo defaults checked in at the repo root. `wt` only ever
+// reads it; personal settings stay in ~/.wt/config and git config.
+#[derive(Debug, Default, Deserialize)]
+pub struct RepoConfig {
+    pub base: Option<String>,
+    #[serde(default)]
+    pub link: Vec<String>,
+    pub post_create: Option<String>,
+}
+
+pub const REPO_CONFIG_FILE: &str = ".wt.toml";
+// Prefix of every parse error for REPO_CONFIG_FILE.
+const INVALID_REPO_CONFIG: &str = "invalid .wt.toml";
+
 fn config_path() -> Result<PathBuf, String> {
     crate::worktree::wt_home().map(|p| p.join("config"))
 }
@@ -35,9 +51,14 @@ fn save_to(config: &Config, path: &Path) -> Result<(), String> {
     }
     let content =
         toml::to_string_pretty(config).map_err(|e| format!("cannot serialize config: {e}"))?;
+    write_atomic(path, &content)
+}
+
+// Write-then-rename so a concurrent reader never sees a half-written file.
+pub(crate) fn write_atomic(path: &Path, content: &str) -> Result<(), String> {
     let id = crate::worktree::random_id()?;
     let tmp = path.with_extension(format!("tmp.{id}"));
-    std::fs::write(&tmp, &content).map_err(|e| format!("cannot write {}: {e}", tmp.display()))?;
+    std::fs::write(&tmp, content).map_err(|e| format!("cannot write {}: {e}", tmp.display()))?;
     std::fs::rename(&tmp, path).map_err(|e| {
         let _ = std::fs::remove_file(&tmp);
         format!("cannot write {}: {e}", path.display())
@@ -81,6 +102,81 @@ pub fn get_links(repo: &Path) -> Vec<String> {
         .unwrap_or_default()
 }
 
+pub fn load_repo(repo_root: &Path) -> Result<RepoConfig, String> {
+    let path = repo_root.join(REPO_CONFIG_FILE);
+    match std::fs::read_to_string(&path) {
+        Ok(content) => {
+            toml::from_str(&content).map_err(|e| format!("{INVALID_REPO_CONFIG}: {}", e.message()))
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(RepoConfig::default()),
+        Err(e) => Err(format!("cannot read {}: {e}", path.display())),
+    }
+}
+
+// For callers that would otherwise fall back to a default: a broken .wt.toml
+// aborts instead.
+pub fn is_repo_config_error(err: &str) -> bool {
+    err.starts_with(INVALID_REPO_CONFIG)
+}
+
+pub fn add_independent(repo: &Path, checkout: &Path) -> Result<(), String> {
+    let mut config = load()?;
+    let path = repo_key(checkout);
+    let existing = config.independent.entry(repo_key(repo)).or_default();
+    if !existing.contains(&path) {
+        existing.push(path);
+    }
+    save(&config)
+}
+
+pub fn remove_independent(repo: &Path, checkout: &Path) -> Result<(), String> {
+    let mut config = load()?;
+    let key = repo_key(repo);
+    let Some(existing) = config.independent.get_mut(&key) else {
+        return Ok(());
+    };
+    let path = repo_key(checkout);
+    existing.retain(|p| *p != path);
+    if existing.is_empty() {
+        config.independent.remove(&key);
+    }
+    save(&config)
+}
+
+// Repos with an independent checkout still on disk, so `--all` finds them
+// even when they have no linked worktree under ~/.wt/worktrees.
+pub fn independent_repos() -> Vec<PathBuf> {
+    load()
+        .map(|config| config.independent)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|(_, checkouts)| checkouts.iter().any(|p| Path::new(p).join(".git").is_dir()))
+        .map(|(repo, _)| PathBuf::from(repo))
+        .collect()
+}
+
+// The repo an independent checkout was cloned from, for `rm <path>` run
+// from outside that repo.
+pub fn independent_owner(checkout: &Path) -> Option<PathBuf> {
+    let path = repo_key(checkout);
+    load()
+        .ok()?
+        .independent
+        .into_iter()
+        .find(|(_, checkouts)| checkouts.contains(&path))
+        .map(|(repo, _)| PathBuf::from(repo))
+}
+
+pub fn get_independent(repo: &Path) -> Vec<PathBuf> {
+    load()
+        .ok()
+        .and_then(|config| config.independent.get(&repo_key(repo)).cloned())
+        .unwrap_or_default()
+        .into_iter()
+        .map(PathBuf::from)
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -104,6 +200,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_config_with_independent() {
+        let toml = r#"
+[independent]
+"/tmp/repo" = ["/tmp/wt/abc123/repo"]
+"#;
+        let config: Config = toml::from_str(toml).unwrap();
+        assert!(config.links.is_empty());
+        assert_eq!(
+            config.independent.get("/tmp/repo"),
+            Some(&vec!["/tmp/wt/abc123/repo".to_string()])
+        );
+    }
+
+    #[test]
+    fn empty_independent_is_not_serialized() {
+        let config = Config::default();
+        let serialized = toml::to_string_pretty(&config).unwrap();
+        assert!(!serialized.contains("independent"));
+    }
+
+    #[test]
+    fn parse_repo_config() {
+        let toml = r#"
+base = "develop"
+link = [".env", "config/local.json"]
+"#;
+        let config: RepoConfig = toml::from_str(toml).unwrap();
+        assert_eq!(config.base.as_deref(), Some("develop"));
+        assert_eq!(config.link, vec![".env", "config/local.json"]);
+
+        let empty: RepoConfig = toml::from_str("").unwrap();
+        assert!(empty.base.is_none() && empty.link.is_empty());
+    }
+
+    #[test]
+    fn invalid_repo_config_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(load_repo(dir.path()).unwrap().base.is_none());
+
+        std::fs::write(dir.path().join(REPO_CONFIG_FILE), "link = \".env\"\n").unwrap();
+        let err = load_repo(dir.path()).unwrap_err();
+        assert!(err.starts_with("invalid .wt.toml: "), "{err}");
+    }
+
     #[test]
     fn serialize_roundtrip() {
         let mut config = Config::default();
@@ -0,0 +1,43 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::config::repo_key;
+use crate::state;
+
+// Tips of branches that `wt rm` deleted, keyed by git common dir (the same
+// from every worktree of a repo), so `wt restore` can find them after the
+// branch's own reflog is gone. The commits themselves are only as safe as
+// git gc allows.
+type Deleted = BTreeMap<String, BTreeMap<String, String>>;
+
+const FILE: &str = "deleted-branches.toml";
+
+pub fn record(common_dir: &Path, branch: &str, sha: &str) -> Result<(), String> {
+    state::update(FILE, |deleted: &mut Deleted| {
+        deleted
+            .entry(repo_key(common_dir))
+            .or_default()
+            .insert(branch.to_string(), sha.to_string());
+    })
+}
+
+pub fn lookup(common_dir: &Path, branch: &str) -> Option<String> {
+    state::load::<Deleted>(FILE)
+        .ok()?
+        .get(&repo_key(common_dir))?
+        .get(branch)
+        .cloned()
+}
+
+pub fn forget(common_dir: &Path, branch: &str) -> Result<(), String> {
+    let key = repo_key(common_dir);
+    state::update(FILE, |deleted: &mut Deleted| {
+        let Some(branches) = deleted.get_mut(&key) else {
+            return;
+        };
+        branches.remove(branch);
+        if branches.is_empty() {
+            deleted.remove(&key);
+        }
+    })
+}
@@ -0,0 +1,78 @@
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use crate::terminal;
+
+// $VISUAL wins over $EDITOR, as with git. The value may carry flags
+// (`code --new-window`), so it is split on whitespace rather than run whole.
+fn editor_command() -> Option<String> {
+    ["VISUAL", "EDITOR"]
+        .into_iter()
+        .filter_map(|key| std::env::var(key).ok())
+        .find(|value| !value.trim().is_empty())
+}
+
+// At a terminal, `new --open` hands it to the editor and fails with it, so
+// `wt new x --open && ...` only continues after a clean exit. When stdout is
+// captured, the editor is started in the background as in `open`.
+pub fn launch(path: &Path, command: Option<&str>) -> Result<(), String> {
+    if !terminal::is_stdout_tty() {
+        match command {
+            Some(command) => spawn_command(command, path, false),
+            None => open(path, false),
+        }
+        return Ok(());
+    }
+    let editor = command
+        .map(String::from)
+        .or_else(editor_command)
+        .ok_or("no editor found; set $EDITOR or pass a command")?;
+    let mut words = editor.split_whitespace();
+    let program = words
+        .next()
+        .ok_or("no editor found; set $EDITOR or pass a command")?;
+    eprintln!("opening in {program}");
+    let status = Command::new(program)
+        .args(words)
+        .arg(path)
+        .status()
+        .map_err(|e| format!("cannot run {program}: {e}"))?;
+    match status.code() {
+        Some(0) => Ok(()),
+        Some(code) => Err(format!("{program} exited with status {code}")),
+        None => Err(format!("{program} was killed by a signal")),
+    }
+}
+
+// Starts the editor and returns without waiting: the worktree path has
+// already been printed, and a caller capturing stdout must not hang on an
+// editing session. Not having an editor only costs a warning.
+pub fn open(path: &Path, quiet: bool) {
+    let Some(editor) = editor_command() else {
+        eprintln!("cannot open editor: neither $VISUAL nor $EDITOR is set");
+        return;
+    };
+    spawn_command(&editor, path, quiet);
+}
+
+fn spawn_command(command: &str, path: &Path, quiet: bool) {
+    let mut words = command.split_whitespace();
+    if let Some(program) = words.next() {
+        spawn(program, words, path, quiet);
+    }
+}
+
+fn spawn<'a>(program: &str, args: impl Iterator<Item = &'a str>, path: &Path, quiet: bool) {
+    let spawned = Command::new(program)
+        .args(args)
+        .arg(path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    match spawned {
+        Ok(_) if quiet => {}
+        Ok(_) => eprintln!("opening in {program}"),
+        Err(e) => eprintln!("cannot run {program}: {e}"),
+    }
+}
@@ -85,6 +85,29 @@ pub fn close_match<'a>(name: &str, candidates: &[&'a str]) -> Option<&'a str> {
         .map(|(_, name)| name)
 }
 
+pub fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, n));
+            p += 1;
+        } else if let Some((bp, bn)) = backtrack {
+            p = bp + 1;
+            n = bn + 1;
+            backtrack = Some((bp, bn + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -295,4 +318,24 @@ mod tests {
         assert_eq!(levenshtein("caf\u{00e9}", "cafe"), 1);
         assert_eq!(levenshtein("\u{00fc}ber", "\u{00fc}ber"), 0);
     }
+
+    #[test]
+    fn glob_match_star() {
+        assert!(glob_match("*", "repo"));
+        assert!(glob_match("app-*", "app-web"));
+        assert!(glob_match("*-web", "app-web"));
+        assert!(!glob_match("app-*", "lib-web"));
+    }
+
+    #[test]
+    fn glob_match_question_mark() {
+        assert!(glob_match("repo?", "repo1"));
+        assert!(!glob_match("repo?", "repo"));
+    }
+
+    #[test]
+    fn glob_match_backtracks() {
+        assert!(glob_match("a*b*c", "axxbyyc"));
+        assert!(!glob_match("a*b*c", "axxbyy"));
+    }
 }
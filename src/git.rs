@@ -1,23 +1,58 @@
+use std::collections::BTreeMap;
+use std::io::BufRead;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Output, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::config;
+use crate::fuzzy;
 
 fn git_err(context: impl AsRef<str>, output: &Output) -> String {
-    let context = context.as_ref();
-    let stderr = String::from_utf8_lossy(&output.stderr);
-    let line = stderr
+    format_git_err(
+        context.as_ref(),
+        &String::from_utf8_lossy(&output.stderr),
+        VERBOSE.load(Ordering::Relaxed),
+    )
+}
+
+// The message is git's first line that isn't a `hint:`, which is the error
+// itself. Advice follows on later lines; `-v` keeps it, indented below.
+fn format_git_err(context: &str, stderr: &str, verbose: bool) -> String {
+    let lines: Vec<&str> = stderr
         .lines()
         .map(str::trim)
-        .find(|l| !l.is_empty())
-        .unwrap_or("");
+        .filter(|l| !l.is_empty())
+        .collect();
+    let primary = lines
+        .iter()
+        .position(|l| !l.starts_with("hint:"))
+        .or((!lines.is_empty()).then_some(0));
+    let line = primary.map_or("", |i| lines[i]);
     let msg = line
         .strip_prefix("fatal: ")
         .or_else(|| line.strip_prefix("error: "))
         .unwrap_or(line);
-    if msg.is_empty() {
+    let mut out = if msg.is_empty() {
         context.into()
     } else {
         format!("{context}: {msg}")
+    };
+    if verbose {
+        for (i, detail) in lines.iter().enumerate() {
+            if Some(i) != primary {
+                out.push_str(&format!("\n  {detail}"));
+            }
+        }
     }
+    out
+}
+
+// Set once from the global `--verbose` flag before any command runs.
+static VERBOSE: AtomicBool = AtomicBool::new(false);
+
+struct SparseSpec {
+    cone: bool,
+    paths: Vec<String>,
 }
 
 pub struct Git {
@@ -40,20 +75,83 @@ impl Git {
     }
 
     pub fn find_repo(path: Option<&Path>) -> Result<PathBuf, String> {
+        if let Some(root) = Self::toplevel(path).or_else(|| Self::bare_dir(path)) {
+            return Ok(root);
+        }
+        Err("not a git repository, use --repo or run inside one".into())
+    }
+
+    pub fn enable_verbose() {
+        VERBOSE.store(true, Ordering::Relaxed);
+    }
+
+    pub fn toplevel(path: Option<&Path>) -> Option<PathBuf> {
         let mut cmd = Command::new("git");
         if let Some(p) = path {
             cmd.arg("-C").arg(p);
         }
         cmd.args(["rev-parse", "--show-toplevel"]);
-        let output = cmd
-            .stderr(Stdio::null())
-            .output()
-            .map_err(|e| format!("cannot run git: {e}"))?;
+        let output = cmd.stderr(Stdio::null()).output().ok()?;
         if !output.status.success() {
-            return Err("not a git repository, use --repo or run inside one".into());
+            return None;
         }
         let s = String::from_utf8_lossy(&output.stdout).trim().to_string();
-        Ok(PathBuf::from(s))
+        Some(PathBuf::from(s))
+    }
+
+    // A bare repo has no toplevel; its worktrees are managed from the bare
+    // directory itself, so that stands in for the repo root.
+    fn bare_dir(path: Option<&Path>) -> Option<PathBuf> {
+        let mut cmd = Command::new("git");
+        if let Some(p) = path {
+            cmd.arg("-C").arg(p);
+        }
+        cmd.args(["rev-parse", "--is-bare-repository", "--absolute-git-dir"]);
+        let output = cmd.stderr(Stdio::null()).output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let (bare, git_dir) = stdout.trim_end().split_once('\n')?;
+        (bare == "true").then(|| PathBuf::from(git_dir))
+    }
+
+    // `--discover`: from a directory like ~/projects that only holds a repo,
+    // use that repo. Exactly one child must qualify so the choice is never a guess.
+    pub fn discover_child_repo(path: Option<&Path>) -> Result<PathBuf, String> {
+        let parent = match path {
+            Some(p) => p.to_path_buf(),
+            None => std::env::current_dir()
+                .map_err(|e| format!("cannot read current directory: {e}"))?,
+        };
+        let entries = std::fs::read_dir(&parent)
+            .map_err(|e| format!("cannot read {}: {e}", parent.display()))?;
+        let mut repos: Vec<PathBuf> = entries
+            .filter_map(Result::ok)
+            .map(|e| e.path())
+            .filter(|p| p.is_dir() && p.join(".git").exists())
+            .collect();
+        repos.sort();
+        match repos.as_slice() {
+            [] => Err(format!(
+                "not a git repository, and no repository found in {}",
+                parent.display()
+            )),
+            [one] => Self::toplevel(Some(one))
+                .ok_or_else(|| format!("not a git repository: {}", one.display())),
+            _ => {
+                let names: Vec<String> = repos
+                    .iter()
+                    .filter_map(|p| p.file_name())
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .collect();
+                Err(format!(
+                    "multiple repositories found in {}: {}, use --repo to pick one",
+                    parent.display(),
+                    names.join(", ")
+                ))
+            }
+        }
     }
 
     pub fn remote_url(&self, remote: &str) -> Option<String> {
@@ -88,33 +186,190 @@ impl Git {
         Ok(())
     }
 
+    // `fetch_remote` for a fetch running alongside others: git's output still
+    // streams as it comes, but each line starts with `label` so the repos it
+    // belongs to can be told apart.
+    pub fn fetch_remote_labeled(&self, remote: &str, label: &str) -> Result<(), String> {
+        let mut child = self
+            .cmd()
+            .args(["fetch", "--prune", remote])
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("cannot run git fetch: {e}"))?;
+        if let Some(stderr) = child.stderr.take() {
+            for line in std::io::BufReader::new(stderr)
+                .split(b'\n')
+                .map_while(Result::ok)
+            {
+                eprintln!("{label}: {}", String::from_utf8_lossy(&line).trim_end());
+            }
+        }
+        let status = child
+            .wait()
+            .map_err(|e| format!("cannot run git fetch: {e}"))?;
+        if !status.success() {
+            return Err(String::new()); // detail already shown, labeled
+        }
+        Ok(())
+    }
+
     pub fn base_ref(&self) -> Result<String, String> {
+        let remotes = self.base_remotes();
+        // Read .wt.toml even when git config wins, so a broken file never
+        // goes unnoticed.
+        let repo_base = config::load_repo(&self.repo)?.base;
+        let (branch, source) = match (self.wt_config("baseBranch"), repo_base) {
+            (Some(branch), _) => (branch, "wt.baseBranch"),
+            (None, Some(branch)) => (branch, config::REPO_CONFIG_FILE),
+            (None, None) => return self.default_ref_on_any(&remotes),
+        };
+        if let Some(remote) = remotes
+            .iter()
+            .find(|remote| self.ref_exists(&format!("refs/remotes/{remote}/{branch}")))
+        {
+            return Ok(format!("{remote}/{branch}"));
+        }
+        if self.rev_parse(&branch).is_some() {
+            return Ok(branch);
+        }
+        Err(format!(
+            "configured base branch '{branch}' not found ({source})"
+        ))
+    }
+
+    // WT_CONFIG points at an isolated git-config file that replaces the normal
+    // local/global lookup for `wt.*` keys, so a user's global config can't leak in.
+    pub fn wt_config(&self, key: &str) -> Option<String> {
+        let mut cmd = self.cmd();
+        cmd.arg("config");
+        if let Some(file) = std::env::var_os("WT_CONFIG") {
+            cmd.arg("--file").arg(file);
+        }
+        let output = cmd
+            .args(["--get", &format!("wt.{key}")])
+            .stderr(Stdio::null())
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (!value.is_empty()).then_some(value)
+    }
+
+    // Boolean `wt.*` keys accept git's spellings of true; anything else is false.
+    pub fn wt_flag(&self, key: &str) -> bool {
+        self.wt_config(key)
+            .is_some_and(|v| matches!(v.to_ascii_lowercase().as_str(), "true" | "yes" | "on" | "1"))
+    }
+
+    // Globs from `wt.protectedBranches`, separated by commas or whitespace.
+    pub fn is_protected_branch(&self, branch: &str) -> bool {
+        self.wt_config("protectedBranches").is_some_and(|list| {
+            list.split([',', ' ', '\t'])
+                .filter(|p| !p.is_empty())
+                .any(|pattern| fuzzy::glob_match(pattern, branch))
+        })
+    }
+
+    // `origin/main` -> `main`, but only when `origin` is a real remote, so a
+    // local `feat/x` keeps its prefix whatever the remotes are called.
+    pub fn strip_remote<'a>(&self, reference: &'a str) -> &'a str {
+        reference
+            .split_once('/')
+            .filter(|(remote, _)| self.has_remote(remote))
+            .map_or(reference, |(_, rest)| rest)
+    }
+
+    // `wt.remote` pins the remote. Otherwise `checkout.defaultRemote` comes
+    // first, then `origin`, then the rest in `git remote` order, so a fork
+    // whose only remote is `upstream` still has a base branch.
+    fn base_remotes(&self) -> Vec<String> {
+        if let Some(remote) = self.wt_config("remote") {
+            return vec![remote];
+        }
+        let mut remotes = self.remotes();
+        if remotes.is_empty() {
+            return vec!["origin".to_string()];
+        }
+        let default_remote = self
+            .cmd()
+            .args(["config", "--get", "checkout.defaultRemote"])
+            .stderr(Stdio::null())
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string());
+        let preferred = [default_remote.as_deref(), Some("origin")];
+        // Stable sort: preferred remotes move to the front, in order.
+        remotes.sort_by_key(|remote| {
+            preferred
+                .iter()
+                .position(|p| *p == Some(remote.as_str()))
+                .unwrap_or(preferred.len())
+        });
+        remotes
+    }
+
+    fn remotes(&self) -> Vec<String> {
+        let Ok(output) = self.cmd().arg("remote").stderr(Stdio::null()).output() else {
+            return vec![];
+        };
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::to_string)
+            .collect()
+    }
+
+    // One remote keeps the detailed "tried ..." error; with several, it would
+    // be a wall of refs, so only the remotes are named.
+    fn default_ref_on_any(&self, remotes: &[String]) -> Result<String, String> {
+        if let [remote] = remotes {
+            return self.remote_default_ref(remote);
+        }
+        remotes
+            .iter()
+            .find_map(|remote| self.remote_default_ref(remote).ok())
+            .ok_or_else(|| {
+                format!(
+                    "cannot determine default branch (tried remotes {})",
+                    remotes.join(", ")
+                )
+            })
+    }
+
+    pub fn remote_default_ref(&self, remote: &str) -> Result<String, String> {
         let output = self
             .cmd()
-            .args(["symbolic-ref", "--quiet", "refs/remotes/origin/HEAD"])
+            .args([
+                "symbolic-ref",
+                "--quiet",
+                &format!("refs/remotes/{remote}/HEAD"),
+            ])
             .stderr(Stdio::null())
             .output()
             .map_err(|e| format!("cannot run git: {e}"))?;
 
         if output.status.success() {
             let head_ref = String::from_utf8_lossy(&output.stdout).trim().to_string();
-            if let Some(branch) = head_ref.strip_prefix("refs/remotes/origin/")
-                && self.ref_exists(&format!("refs/remotes/origin/{branch}"))
+            let prefix = format!("refs/remotes/{remote}/");
+            if let Some(branch) = head_ref.strip_prefix(&prefix)
+                && self.ref_exists(&format!("{prefix}{branch}"))
             {
-                return Ok(format!("origin/{branch}"));
+                return Ok(format!("{remote}/{branch}"));
             }
         }
 
         for name in ["main", "master"] {
-            if self.ref_exists(&format!("refs/remotes/origin/{name}")) {
-                return Ok(format!("origin/{name}"));
+            if self.ref_exists(&format!("refs/remotes/{remote}/{name}")) {
+                return Ok(format!("{remote}/{name}"));
             }
         }
 
-        Err(
-            "cannot determine default branch (tried origin/HEAD, origin/main, origin/master)"
-                .into(),
-        )
+        Err(format!(
+            "cannot determine default branch (tried {remote}/HEAD, {remote}/main, {remote}/master)"
+        ))
     }
 
     pub fn ref_exists(&self, refname: &str) -> bool {
@@ -147,6 +402,33 @@ impl Git {
         }
     }
 
+    // Every remote-tracking branch as (remote, branch), skipping the
+    // symbolic `<remote>/HEAD`. Remote names are matched against `git remote`
+    // so one containing a slash still splits in the right place.
+    pub fn remote_branches(&self) -> Result<Vec<(String, String)>, String> {
+        let output = self
+            .cmd()
+            .args(["for-each-ref", "--format=%(refname)", "refs/remotes/"])
+            .output()
+            .map_err(|e| format!("cannot run git for-each-ref: {e}"))?;
+        if !output.status.success() {
+            return Err(git_err("cannot list remote branches", &output));
+        }
+        let mut remotes = self.remotes();
+        remotes.sort_by_key(|r| std::cmp::Reverse(r.len()));
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| {
+                let rest = line.strip_prefix("refs/remotes/")?;
+                let remote = remotes
+                    .iter()
+                    .find(|r| rest.starts_with(&format!("{r}/")))?;
+                let branch = &rest[remote.len() + 1..];
+                (branch != "HEAD").then(|| (remote.clone(), branch.to_string()))
+            })
+            .collect())
+    }
+
     pub fn remotes_with_branch(&self, name: &str) -> Result<Vec<String>, String> {
         if name == "HEAD" {
             return Ok(vec![]);
@@ -172,17 +454,54 @@ impl Git {
             .collect())
     }
 
+    // `sparse` names the cone-mode directories to check out, overriding
+    // whatever sparse-checkout the worktree would otherwise get.
     pub fn add_worktree(
         &self,
         branch: &str,
         dest: &Path,
         base_ref: Option<&str>,
+        sparse: Option<&[String]>,
+    ) -> Result<(), String> {
+        self.worktree_add(Some(branch), dest, base_ref, sparse)
+    }
+
+    pub fn checkout_worktree(
+        &self,
+        branch: &str,
+        dest: &Path,
+        sparse: Option<&[String]>,
     ) -> Result<(), String> {
+        self.worktree_add(None, dest, Some(branch), sparse)
+    }
+
+    // A sparse worktree is added without a checkout, narrowed, and only then
+    // populated, so the full tree never touches the disk.
+    fn worktree_add(
+        &self,
+        new_branch: Option<&str>,
+        dest: &Path,
+        commit: Option<&str>,
+        sparse: Option<&[String]>,
+    ) -> Result<(), String> {
+        let sparse = match sparse {
+            Some(paths) => Some(SparseSpec {
+                cone: true,
+                paths: paths.to_vec(),
+            }),
+            None => self.sparse_spec(),
+        };
         let mut cmd = self.cmd();
-        cmd.args(["worktree", "add", "--quiet", "-b", branch])
-            .arg(dest);
-        if let Some(base) = base_ref {
-            cmd.arg(base);
+        cmd.args(["worktree", "add", "--quiet"]);
+        if sparse.is_some() {
+            cmd.arg("--no-checkout");
+        }
+        if let Some(branch) = new_branch {
+            cmd.args(["-b", branch]);
+        }
+        cmd.arg("--").arg(dest);
+        if let Some(commit) = commit {
+            cmd.arg(commit);
         }
         let output = cmd
             .stdout(Stdio::null())
@@ -191,24 +510,170 @@ impl Git {
         if !output.status.success() {
             return Err(git_err("cannot create worktree", &output));
         }
-        Ok(())
+        let Some(sparse) = sparse else {
+            return Ok(());
+        };
+        // Leave nothing behind, as when `worktree add` itself fails.
+        Self::sparse_checkout(dest, &sparse).inspect_err(|_| {
+            let _ = self.remove_worktree(dest, true);
+            if let Some(branch) = new_branch {
+                let _ = self.delete_branch(branch, true);
+            }
+        })
     }
 
-    pub fn checkout_worktree(&self, branch: &str, dest: &Path) -> Result<(), String> {
+    // `wt.sparsePaths` (cone-mode directories, separated by commas or
+    // whitespace) wins; otherwise a new worktree copies the sparse-checkout of
+    // the worktree `wt` runs from. None means a full checkout.
+    fn sparse_spec(&self) -> Option<SparseSpec> {
+        if let Some(list) = self.wt_config("sparsePaths") {
+            let paths: Vec<String> = list
+                .split([',', ' ', '\t'])
+                .filter(|p| !p.is_empty())
+                .map(String::from)
+                .collect();
+            return (!paths.is_empty()).then_some(SparseSpec { cone: true, paths });
+        }
+        let config_true = |key: &str| {
+            self.cmd()
+                .args(["config", "--bool", key])
+                .stderr(Stdio::null())
+                .output()
+                .is_ok_and(|o| o.status.success() && o.stdout.trim_ascii() == b"true")
+        };
+        if !config_true("core.sparseCheckout") {
+            return None;
+        }
         let output = self
             .cmd()
-            .args(["worktree", "add", "--quiet"])
+            .args(["sparse-checkout", "list"])
+            .stderr(Stdio::null())
+            .output()
+            .ok()
+            .filter(|o| o.status.success())?;
+        let paths = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(String::from)
+            .collect();
+        Some(SparseSpec {
+            cone: config_true("core.sparseCheckoutCone"),
+            paths,
+        })
+    }
+
+    fn sparse_checkout(dest: &Path, sparse: &SparseSpec) -> Result<(), String> {
+        let mode = if sparse.cone { "--cone" } else { "--no-cone" };
+        let output = Self::cmd_in(dest)
+            .args(["sparse-checkout", "set", mode, "--"])
+            .args(&sparse.paths)
+            .stdout(Stdio::null())
+            .output()
+            .map_err(|e| format!("cannot run git sparse-checkout: {e}"))?;
+        if !output.status.success() {
+            return Err(git_err("cannot set up sparse checkout", &output));
+        }
+        let output = Self::cmd_in(dest)
+            .args(["reset", "--quiet", "--hard"])
+            .stdout(Stdio::null())
+            .output()
+            .map_err(|e| format!("cannot run git reset: {e}"))?;
+        if !output.status.success() {
+            return Err(git_err("cannot check out sparse worktree", &output));
+        }
+        Ok(())
+    }
+
+    pub fn local_clone(&self, branch: &str, dest: &Path) -> Result<(), String> {
+        let output = Command::new("git")
+            .args(["clone", "--quiet", "--branch", branch])
+            .arg(&self.repo)
             .arg(dest)
-            .arg(branch)
             .stdout(Stdio::null())
             .output()
-            .map_err(|e| format!("cannot run git worktree add: {e}"))?;
+            .map_err(|e| format!("cannot run git clone: {e}"))?;
         if !output.status.success() {
-            return Err(git_err("cannot create worktree", &output));
+            return Err(git_err("cannot create independent checkout", &output));
+        }
+        Ok(())
+    }
+
+    pub fn create_tag(
+        &self,
+        name: &str,
+        commit: &str,
+        message: Option<&str>,
+    ) -> Result<(), String> {
+        let mut cmd = self.cmd();
+        cmd.arg("tag");
+        if let Some(message) = message {
+            cmd.args(["--annotate", "--message", message]);
+        }
+        let output = cmd
+            .args([name, commit])
+            .output()
+            .map_err(|e| format!("cannot run git tag: {e}"))?;
+        if !output.status.success() {
+            return Err(git_err(format!("cannot create tag '{name}'"), &output));
+        }
+        Ok(())
+    }
+
+    // Checked before any git call: a leading dash reaches git as an option
+    // (`-bad` as `-b ad`) before it could be rejected as a name.
+    pub fn validate_branch_name(&self, name: &str) -> Result<(), String> {
+        reject_leading_dash("branch name", name)?;
+        let valid = self
+            .cmd()
+            .args(["check-ref-format", "--branch", name])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|s| s.success());
+        if !valid {
+            return Err(format!("invalid branch name '{name}'"));
         }
         Ok(())
     }
 
+    pub fn rename_branch(&self, old: &str, new: &str) -> Result<(), String> {
+        let output = self
+            .cmd()
+            .args(["branch", "-m", "--end-of-options", old, new])
+            .output()
+            .map_err(|e| format!("cannot run git branch: {e}"))?;
+        if !output.status.success() {
+            return Err(git_err(format!("cannot rename branch '{old}'"), &output));
+        }
+        Ok(())
+    }
+
+    pub fn current_branch(&self) -> Option<String> {
+        let output = self
+            .cmd()
+            .args(["symbolic-ref", "--quiet", "--short", "HEAD"])
+            .stderr(Stdio::null())
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (!branch.is_empty()).then_some(branch)
+    }
+
+    pub fn common_dir(&self) -> Result<PathBuf, String> {
+        let output = self
+            .cmd()
+            .args(["rev-parse", "--path-format=absolute", "--git-common-dir"])
+            .output()
+            .map_err(|e| format!("cannot run git: {e}"))?;
+        if !output.status.success() {
+            return Err(git_err("cannot find git directory", &output));
+        }
+        let s = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        Ok(PathBuf::from(s))
+    }
+
     pub fn list_worktrees(&self) -> Result<String, String> {
         let output = self
             .cmd()
@@ -241,6 +706,19 @@ impl Git {
         Ok(())
     }
 
+    // Getting past untracked files takes --force, which would drop tracked
+    // changes too, so those are looked for again right before removing: the
+    // caller's own check may be as old as a prompt's wait.
+    pub fn remove_worktree_past_untracked(&self, path: &Path) -> Result<(), String> {
+        if self.is_dirty(path, true) {
+            return Err(format!(
+                "cannot remove worktree: {}: tracked files changed",
+                path.display()
+            ));
+        }
+        self.remove_worktree(path, true)
+    }
+
     pub fn delete_branch(&self, branch: &str, force: bool) -> Result<(), String> {
         let flag = if force { "-D" } else { "-d" };
         let output = self
@@ -274,14 +752,66 @@ impl Git {
         Ok(String::from_utf8_lossy(&output.stderr).trim().to_string())
     }
 
-    pub fn is_dirty(&self, worktree_path: &Path) -> bool {
+    // With `ignore_untracked`, only changes to tracked files count, so a
+    // worktree holding nothing but build output is clean.
+    pub fn is_dirty(&self, worktree_path: &Path, ignore_untracked: bool) -> bool {
+        let untracked = if ignore_untracked {
+            "--untracked-files=no"
+        } else {
+            "--untracked-files=normal"
+        };
         Self::cmd_in(worktree_path)
-            .args(["status", "--porcelain", "--untracked-files=normal"])
+            .args(["status", "--porcelain", untracked])
             .stderr(Stdio::null())
             .output()
             .map_or(true, |o| !o.stdout.is_empty())
     }
 
+    // Every ignored file individually, not collapsed into its directory, so
+    // callers can pick single files out of e.g. an ignored `config/`.
+    pub fn ignored_files(&self, worktree_path: &Path) -> Result<Vec<String>, String> {
+        let output = Self::cmd_in(worktree_path)
+            .args([
+                "status",
+                "--porcelain",
+                "-z",
+                "--ignored",
+                "--untracked-files=all",
+            ])
+            .output()
+            .map_err(|e| format!("cannot run git status: {e}"))?;
+        if !output.status.success() {
+            return Err(git_err("cannot list ignored files", &output));
+        }
+        Ok(parse_ignored(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    pub fn clean(&self, worktree_path: &Path, force: bool) -> Result<String, String> {
+        let flag = if force { "-xdf" } else { "-xdn" };
+        let output = Self::cmd_in(worktree_path)
+            .args(["clean", flag])
+            .output()
+            .map_err(|e| format!("cannot run git clean: {e}"))?;
+        if !output.status.success() {
+            return Err(git_err(
+                format!("cannot clean worktree: {}", worktree_path.display()),
+                &output,
+            ));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    pub fn apply_stash(&self, worktree_path: &Path, stash: &str) -> Result<(), String> {
+        let output = Self::cmd_in(worktree_path)
+            .args(["stash", "apply", "--quiet", stash])
+            .output()
+            .map_err(|e| format!("cannot run git stash: {e}"))?;
+        if !output.status.success() {
+            return Err(git_err("cannot apply stash cleanly", &output));
+        }
+        Ok(())
+    }
+
     pub fn is_branch_merged(&self, branch: &str) -> bool {
         let branch_ref = format!("refs/heads/{branch}");
 
@@ -291,9 +821,33 @@ impl Git {
             return self.is_ancestor(&branch_ref, &upstream);
         }
 
+        // Without an upstream, "merged" means merged into the repo's base branch;
+        // HEAD is only a fallback because it may be any side checkout.
+        if let Ok(base) = self.base_ref() {
+            return self.is_ancestor(&branch_ref, &base);
+        }
+
         self.is_ancestor(&branch_ref, "HEAD")
     }
 
+    pub fn diff_stat(&self, base: &str, head: &str) -> Result<String, String> {
+        let output = self
+            .cmd()
+            .args(["diff", "--stat"])
+            .arg(format!("{base}...{head}"))
+            .output()
+            .map_err(|e| format!("cannot run git diff: {e}"))?;
+        if !output.status.success() {
+            return Err(git_err(
+                format!("cannot diff {head} against {base}"),
+                &output,
+            ));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .trim_end()
+            .to_string())
+    }
+
     pub fn is_ancestor(&self, ancestor: &str, descendant: &str) -> bool {
         self.cmd()
             .args(["merge-base", "--is-ancestor", ancestor, descendant])
@@ -303,10 +857,88 @@ impl Git {
             .is_ok_and(|s| s.success())
     }
 
+    pub fn merge_base(&self, a: &str, b: &str) -> Option<String> {
+        let output = self
+            .cmd()
+            .args(["merge-base", a, b])
+            .stderr(Stdio::null())
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let sha = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (!sha.is_empty()).then_some(sha)
+    }
+
+    // A squash merge leaves no ancestry behind. Instead, compare the patch-id
+    // of the branch's changes since it forked from `base` with those of the
+    // commits `base` gained since, like `git cherry` would for a squashed
+    // commit, without writing one. A branch with no commits of its own is
+    // never squash-merged.
+    pub fn is_squash_merged(&self, branch_ref: &str, base: &str) -> bool {
+        let output = self
+            .cmd()
+            .args(["merge-base", base, branch_ref])
+            .stderr(Stdio::null())
+            .output();
+        let Some(fork) = output
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        else {
+            return false;
+        };
+        if self.rev_parse(branch_ref).is_none_or(|tip| tip == fork) {
+            return false;
+        }
+        let Some(squashed) = self
+            .patch_ids(&["diff-tree", "-p", "--no-color", &fork, branch_ref])
+            .and_then(|ids| ids.into_iter().next())
+        else {
+            return false;
+        };
+        let since_fork = format!("{fork}..{base}");
+        self.patch_ids(&["log", "-p", "--no-merges", "--no-color", &since_fork])
+            .is_some_and(|ids| ids.contains(&squashed))
+    }
+
+    // Stable patch-ids of the diff that `git <args>` prints, one per patch.
+    fn patch_ids(&self, args: &[&str]) -> Option<Vec<String>> {
+        let mut diff = self
+            .cmd()
+            .args(args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .ok()?;
+        let output = self
+            .cmd()
+            .args(["patch-id", "--stable"])
+            .stdin(diff.stdout.take()?)
+            .stderr(Stdio::null())
+            .output();
+        let diffed = diff.wait().is_ok_and(|s| s.success());
+        let output = output.ok().filter(|o| diffed && o.status.success())?;
+        Some(
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .filter_map(|line| line.split_whitespace().next())
+                .map(String::from)
+                .collect(),
+        )
+    }
+
     pub fn rev_parse(&self, refname: &str) -> Option<String> {
         let output = self
             .cmd()
-            .args(["rev-parse", "--verify", "--quiet", refname])
+            .args([
+                "rev-parse",
+                "--verify",
+                "--quiet",
+                "--end-of-options",
+                refname,
+            ])
             .stderr(Stdio::null())
             .output()
             .ok()?;
@@ -317,6 +949,63 @@ impl Git {
         (!sha.is_empty()).then_some(sha)
     }
 
+    pub fn commit_summary(&self, sha: &str) -> Option<String> {
+        let output = self
+            .cmd()
+            .args(["log", "-1", "--format=%h %s", sha])
+            .stderr(Stdio::null())
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let summary = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (!summary.is_empty()).then_some(summary)
+    }
+
+    // Where `branch` was when HEAD last moved away from it, going by HEAD's
+    // reflog: the entry just before "checkout: moving from <branch> to ...".
+    pub fn reflog_tip(&self, branch: &str) -> Option<String> {
+        let output = self
+            .cmd()
+            .args(["reflog", "show", "--format=%H %gs", "HEAD"])
+            .stderr(Stdio::null())
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let text = String::from_utf8_lossy(&output.stdout);
+        parse_reflog_tip(&text, branch)
+    }
+
+    // Relative commit date of HEAD ("3 weeks ago"); `None` on an unborn branch.
+    pub fn commit_age(worktree_path: &Path) -> Option<String> {
+        let output = Self::cmd_in(worktree_path)
+            .args(["log", "-1", "--format=%cr"])
+            .stderr(Stdio::null())
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let age = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (!age.is_empty()).then_some(age)
+    }
+
+    // HEAD's committer date as a Unix timestamp; `None` on an unborn branch.
+    pub fn commit_time(worktree_path: &Path) -> Option<u64> {
+        let output = Self::cmd_in(worktree_path)
+            .args(["log", "-1", "--format=%ct"])
+            .stderr(Stdio::null())
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+    }
+
     pub fn worktree_status(worktree_path: &Path) -> (bool, Option<u64>, Option<u64>) {
         let output = Self::cmd_in(worktree_path)
             .args([
@@ -337,10 +1026,86 @@ impl Git {
         parse_porcelain_status(&text)
     }
 
-    pub fn is_upstream_gone(&self, branch: &str) -> bool {
-        let branch_ref = format!("refs/heads/{branch}");
-        self.upstream_for(&branch_ref)
-            .is_some_and(|upstream| self.rev_parse(&upstream).is_none())
+    // Gone-ness of every local branch's upstream from one for-each-ref, so
+    // prune doesn't rev-parse once per candidate. Branches without an
+    // upstream are absent. `lstrip=2` rather than `:short`, which turns into
+    // `heads/<name>` when a tag shares the branch's name.
+    pub fn upstreams_gone(&self) -> BTreeMap<String, bool> {
+        let output = self
+            .cmd()
+            .args([
+                "for-each-ref",
+                "--format=%(refname:lstrip=2) %(upstream:short) %(upstream:track)",
+                "refs/heads/",
+            ])
+            .stderr(Stdio::null())
+            .output();
+        match output {
+            Ok(o) if o.status.success() => {
+                parse_upstreams_gone(&String::from_utf8_lossy(&o.stdout))
+            }
+            _ => BTreeMap::new(),
+        }
+    }
+
+    // Local branches that other local branches track (`branch.<x>.merge` with
+    // remote `.`), each mapped to the branches tracking it.
+    pub fn local_upstream_dependents(&self) -> BTreeMap<String, Vec<String>> {
+        let output = self
+            .cmd()
+            .args([
+                "for-each-ref",
+                "--format=%(refname:lstrip=2) %(upstream)",
+                "refs/heads/",
+            ])
+            .stderr(Stdio::null())
+            .output();
+        match output {
+            Ok(o) if o.status.success() => {
+                parse_local_upstream_dependents(&String::from_utf8_lossy(&o.stdout))
+            }
+            _ => BTreeMap::new(),
+        }
+    }
+
+    pub fn set_upstream(&self, branch: &str, upstream: &str) -> Result<(), String> {
+        let output = self
+            .cmd()
+            .args([
+                "branch",
+                "--quiet",
+                &format!("--set-upstream-to={upstream}"),
+                branch,
+            ])
+            .output()
+            .map_err(|e| format!("cannot run git branch: {e}"))?;
+        if !output.status.success() {
+            return Err(git_err(
+                format!("cannot set upstream of '{branch}' to '{upstream}'"),
+                &output,
+            ));
+        }
+        Ok(())
+    }
+
+    // A branch without an upstream is already what the caller wants; git
+    // would call that an error.
+    pub fn unset_upstream(&self, branch: &str) -> Result<(), String> {
+        if self.upstream_for(&format!("refs/heads/{branch}")).is_none() {
+            return Ok(());
+        }
+        let output = self
+            .cmd()
+            .args(["branch", "--unset-upstream", branch])
+            .output()
+            .map_err(|e| format!("cannot run git branch: {e}"))?;
+        if !output.status.success() {
+            return Err(git_err(
+                format!("cannot unset upstream of '{branch}'"),
+                &output,
+            ));
+        }
+        Ok(())
     }
 
     pub fn upstream_remote(&self, branch: &str) -> Option<String> {
@@ -357,9 +1122,22 @@ impl Git {
         (!remote.is_empty()).then_some(remote)
     }
 
-    pub fn bare_clone(url: &str, dest: &Path) -> Result<(), String> {
-        let status = Command::new("git")
-            .args(["clone", "--bare", url])
+    pub fn bare_clone(
+        url: &str,
+        dest: &Path,
+        reference: Option<&Path>,
+        dissociate: bool,
+    ) -> Result<(), String> {
+        let mut cmd = Command::new("git");
+        cmd.args(["clone", "--bare"]);
+        if let Some(reference) = reference {
+            cmd.arg("--reference").arg(reference);
+        }
+        if dissociate {
+            cmd.arg("--dissociate");
+        }
+        let status = cmd
+            .arg(url)
             .arg(dest)
             .stdout(Stdio::null())
             .stderr(Stdio::inherit())
@@ -384,6 +1162,22 @@ impl Git {
         Ok(())
     }
 
+    // Talks to the remote, so like `fetch_remote` its errors stream to the
+    // terminal as git prints them.
+    pub fn prune_remote(&self, remote: &str) -> Result<(), String> {
+        let status = self
+            .cmd()
+            .args(["remote", "prune", remote])
+            .stdout(Stdio::null())
+            .stderr(Stdio::inherit())
+            .status()
+            .map_err(|e| format!("cannot run git remote: {e}"))?;
+        if !status.success() {
+            return Err(format!("cannot prune refs from '{remote}'"));
+        }
+        Ok(())
+    }
+
     pub fn set_remote_head(&self, remote: &str) -> Result<(), String> {
         let output = self
             .cmd()
@@ -400,6 +1194,29 @@ impl Git {
         Ok(())
     }
 
+    pub fn upstream(&self, branch: &str) -> Option<String> {
+        self.upstream_for(&format!("refs/heads/{branch}"))
+    }
+
+    pub fn behind_upstream(&self, branch: &str) -> Option<(String, u64)> {
+        let upstream = self.upstream_for(&format!("refs/heads/{branch}"))?;
+        let output = self
+            .cmd()
+            .args(["rev-list", "--count"])
+            .arg(format!("refs/heads/{branch}..{upstream}"))
+            .stderr(Stdio::null())
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let behind: u64 = String::from_utf8_lossy(&output.stdout)
+            .trim()
+            .parse()
+            .ok()?;
+        (behind > 0).then_some((upstream, behind))
+    }
+
     fn upstream_for(&self, refspec: &str) -> Option<String> {
         let output = self
             .cmd()
@@ -412,6 +1229,62 @@ impl Git {
     }
 }
 
+fn parse_reflog_tip(text: &str, branch: &str) -> Option<String> {
+    let moved_away = format!("checkout: moving from {branch} to ");
+    let mut lines = text.lines();
+    lines.find(|line| {
+        line.split_once(' ')
+            .is_some_and(|(_, subject)| subject.starts_with(&moved_away))
+    })?;
+    let (sha, _) = lines.next()?.split_once(' ')?;
+    Some(sha.to_string())
+}
+
+fn parse_upstreams_gone(text: &str) -> BTreeMap<String, bool> {
+    text.lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(3, ' ');
+            let branch = parts.next().filter(|b| !b.is_empty())?;
+            parts.next().filter(|u| !u.is_empty())?;
+            let gone = parts.next().is_some_and(|track| track.trim() == "[gone]");
+            Some((branch.to_string(), gone))
+        })
+        .collect()
+}
+
+fn parse_local_upstream_dependents(text: &str) -> BTreeMap<String, Vec<String>> {
+    let mut dependents: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for line in text.lines() {
+        let Some((branch, upstream)) = line.split_once(' ') else {
+            continue;
+        };
+        if let Some(base) = upstream.trim().strip_prefix("refs/heads/")
+            && base != branch
+        {
+            dependents
+                .entry(base.to_string())
+                .or_default()
+                .push(branch.to_string());
+        }
+    }
+    dependents
+}
+
+pub fn reject_leading_dash(what: &str, name: &str) -> Result<(), String> {
+    if name.starts_with('-') {
+        return Err(format!("invalid {what} '{name}': cannot start with '-'"));
+    }
+    Ok(())
+}
+
+fn parse_ignored(text: &str) -> Vec<String> {
+    text.split('\0')
+        .filter_map(|entry| entry.strip_prefix("!! "))
+        .filter(|path| !path.ends_with('/'))
+        .map(str::to_string)
+        .collect()
+}
+
 fn parse_porcelain_status(text: &str) -> (bool, Option<u64>, Option<u64>) {
     let mut dirty = false;
     let mut ahead = None;
@@ -447,6 +1320,12 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_ignored_keeps_only_ignored_files() {
+        let text = "?? new.txt\0!! .env\0 M src/main.rs\0!! config/local.toml\0!! empty/\0";
+        assert_eq!(parse_ignored(text), vec![".env", "config/local.toml"]);
+    }
+
     #[test]
     fn git_err_strips_fatal_prefix() {
         let out = fake_output("fatal: invalid reference: aaaa\n");
@@ -492,6 +1371,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn git_err_skips_leading_hints() {
+        let stderr = "hint: check the path\nfatal: 'x' is a missing but already registered worktree;\nuse 'add -f' to override\n";
+        assert_eq!(
+            format_git_err("cannot create worktree", stderr, false),
+            "cannot create worktree: 'x' is a missing but already registered worktree;"
+        );
+    }
+
+    #[test]
+    fn git_err_verbose_keeps_advice() {
+        let stderr = "fatal: bad object: abc\nhint: use --force\n\nhint: or not\n";
+        assert_eq!(
+            format_git_err("cannot create worktree", stderr, true),
+            "cannot create worktree: bad object: abc\n  hint: use --force\n  hint: or not"
+        );
+        assert_eq!(
+            format_git_err("cannot create worktree", "hint: only advice\n", true),
+            "cannot create worktree: hint: only advice"
+        );
+    }
+
     #[test]
     fn parse_status_clean_with_upstream() {
         let text = "# branch.oid abc123\n# branch.head main\n# branch.upstream origin/main\n# branch.ab +0 -0\n";
@@ -522,6 +1423,45 @@ mod tests {
         assert_eq!(parse_porcelain_status(text), (false, None, None));
     }
 
+    #[test]
+    fn parse_reflog_tip_takes_entry_before_leaving_branch() {
+        let text = "aaa checkout: moving from feat/x to main\n\
+                    bbb commit: work on x\n\
+                    ccc checkout: moving from main to feat/x\n";
+        assert_eq!(parse_reflog_tip(text, "feat/x"), Some("bbb".to_string()));
+        assert_eq!(parse_reflog_tip(text, "feat"), None);
+        assert_eq!(parse_reflog_tip(text, "other"), None);
+    }
+
+    #[test]
+    fn parse_upstreams_gone_classifies_branches() {
+        let text = "feat/gone origin/feat/gone [gone]\n\
+                    feat/live origin/feat/live [ahead 1, behind 2]\n\
+                    main origin/main \n\
+                    local-only  \n";
+        let map = parse_upstreams_gone(text);
+        assert_eq!(map.get("feat/gone"), Some(&true));
+        assert_eq!(map.get("feat/live"), Some(&false));
+        assert_eq!(map.get("main"), Some(&false));
+        assert_eq!(map.get("local-only"), None);
+    }
+
+    #[test]
+    fn parse_local_upstream_dependents_keeps_local_bases() {
+        let text = "feat/a refs/heads/integration\n\
+                    feat/b refs/heads/integration\n\
+                    feat/c refs/remotes/origin/feat/c\n\
+                    integration refs/remotes/origin/integration\n\
+                    loop refs/heads/loop\n\
+                    local-only \n";
+        let map = parse_local_upstream_dependents(text);
+        assert_eq!(
+            map.get("integration"),
+            Some(&vec!["feat/a".to_string(), "feat/b".to_string()])
+        );
+        assert_eq!(map.len(), 1);
+    }
+
     #[test]
     fn parse_status_detached_head() {
         let text = "# branch.oid abc123\n# branch.head (detached)\n";
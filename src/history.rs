@@ -0,0 +1,46 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::config::repo_key;
+use crate::git::Git;
+use crate::state;
+
+// Worktrees `wt switch` handed out, oldest first, keyed by git common dir
+// like deleted.rs so every worktree of a repo shares one history. Only the
+// tail matters for `wt switch -`, so it is capped.
+type History = BTreeMap<String, Vec<String>>;
+
+const MAX_ENTRIES: usize = 20;
+
+const FILE: &str = "switch-history.toml";
+
+// Best-effort, like recency::touch: history is a convenience, never a reason
+// for a switch to fail.
+pub fn record(git: &Git, worktree_path: &Path) {
+    let Ok(common_dir) = git.common_dir() else {
+        return;
+    };
+    let _ = state::update(FILE, |history: &mut History| {
+        let entries = history.entry(repo_key(&common_dir)).or_default();
+        let path = repo_key(worktree_path);
+        if entries.last() != Some(&path) {
+            entries.push(path);
+        }
+        let excess = entries.len().saturating_sub(MAX_ENTRIES);
+        entries.drain(..excess);
+    });
+}
+
+// The newest entry is where the last switch went; the one to return to is
+// the most recent different worktree that `is_live` still accepts.
+pub fn previous(git: &Git, is_live: impl Fn(&Path) -> bool) -> Option<PathBuf> {
+    let history = state::load::<History>(FILE).ok()?;
+    let entries = history.get(&repo_key(&git.common_dir().ok()?))?;
+    let (last, earlier) = entries.split_last()?;
+    earlier
+        .iter()
+        .rev()
+        .filter(|p| *p != last)
+        .map(PathBuf::from)
+        .find(|p| is_live(p))
+}
@@ -0,0 +1,64 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::commands::link;
+use crate::config;
+use crate::git::Git;
+use crate::terminal;
+use crate::worktree;
+
+// A repo's `.wt.toml` can name its own post-create script (relative to the
+// repo root); otherwise `~/.wt/hooks/post-create` runs if it exists. The
+// `.wt.toml` comes with every clone, so its script only runs for users who
+// set wt.allowRepoHooks themselves.
+fn post_create_hook(repo_root: &Path) -> Option<PathBuf> {
+    let repo_hook = config::load_repo(repo_root)
+        .ok()
+        .and_then(|c| c.post_create)
+        .filter(|script| {
+            if let Err(e) = link::validate_path(script) {
+                eprintln!("ignoring post_create in {}: {e}", config::REPO_CONFIG_FILE);
+                return false;
+            }
+            if !Git::new(repo_root).wt_flag("allowRepoHooks") {
+                terminal::eprintln_dim(&format!(
+                    "not running post_create from {}, set wt.allowRepoHooks to allow it",
+                    config::REPO_CONFIG_FILE
+                ));
+                return false;
+            }
+            true
+        })
+        .map(|script| repo_root.join(script));
+    repo_hook.or_else(|| {
+        let global = worktree::wt_home().ok()?.join("hooks").join("post-create");
+        global.is_file().then_some(global)
+    })
+}
+
+// Runs once a new worktree and its links are in place. Its stdout goes to
+// stderr so `cd "$(wt new ...)"` still captures only the path. A failing
+// hook is reported, never undone: the worktree is still usable. WT_BRANCH
+// is left unset for a detached checkout such as a tag.
+pub fn post_create(repo_root: &Path, wt_path: &Path) {
+    let Some(hook) = post_create_hook(repo_root) else {
+        return;
+    };
+    let mut cmd = Command::new(&hook);
+    cmd.arg(wt_path)
+        .current_dir(wt_path)
+        .env_remove("WT_BRANCH")
+        .env("WT_REPO", repo_root)
+        .env("WT_PATH", wt_path)
+        .stdout(std::io::stderr());
+    if let Some(branch) = Git::new(wt_path).current_branch() {
+        cmd.env("WT_BRANCH", branch);
+    }
+    let status = cmd.status();
+    match status.map(|s| s.code()) {
+        Ok(Some(0)) => {}
+        Ok(Some(code)) => eprintln!("post-create hook failed (exit {code})"),
+        Ok(None) => eprintln!("post-create hook was killed by a signal"),
+        Err(e) => eprintln!("cannot run post-create hook {}: {e}", hook.display()),
+    }
+}
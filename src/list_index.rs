@@ -0,0 +1,43 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::config::repo_key;
+use crate::state;
+
+// The rows of each repo's most recent `wt list`, in display order, keyed by
+// git common dir so every worktree of a repo sees the same numbering.
+type Index = BTreeMap<String, Vec<String>>;
+
+const FILE: &str = "list-index.toml";
+
+// Best-effort: a listing must not fail because its numbering can't be saved.
+pub fn record(common_dir: &Path, rows: &[PathBuf]) {
+    let _ = state::update(FILE, |index: &mut Index| {
+        index.insert(
+            repo_key(common_dir),
+            rows.iter().map(|p| repo_key(p)).collect(),
+        );
+    });
+}
+
+// `n` counts rows from 1. The numbering only holds while the repo has exactly
+// the worktrees that were listed; after any add or remove it is refused
+// rather than silently pointing at a different row.
+pub fn lookup(common_dir: &Path, rows: &[PathBuf], n: &str) -> Result<PathBuf, String> {
+    let listed = state::load::<Index>(FILE)?
+        .remove(&repo_key(common_dir))
+        .ok_or("no numbered worktrees, run `wt list` first")?;
+    let mut current: Vec<String> = rows.iter().map(|p| repo_key(p)).collect();
+    let mut expected = listed.clone();
+    current.sort();
+    expected.sort();
+    if current != expected {
+        return Err("worktrees changed since the last `wt list`, run it again".into());
+    }
+    n.parse::<usize>()
+        .ok()
+        .and_then(|i| i.checked_sub(1))
+        .and_then(|i| listed.get(i))
+        .map(PathBuf::from)
+        .ok_or_else(|| format!("no row {n} in the last `wt list`"))
+}
@@ -0,0 +1,177 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::git::Git;
+use crate::worktree;
+
+// Advisory per-repo lock: commands that add or remove worktrees hold it for
+// their whole run so two `wt` processes don't rewrite the same repo at once.
+// The file is removed on drop; a crash leaves it behind for `wt locks --clean`.
+pub struct RepoLock {
+    path: PathBuf,
+}
+
+impl Drop for RepoLock {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+pub struct LockInfo {
+    pub path: PathBuf,
+    pub pid: Option<u32>,
+    pub command: String,
+    pub repo: PathBuf,
+}
+
+impl LockInfo {
+    pub fn stale(&self) -> bool {
+        !self.pid.is_some_and(pid_alive)
+    }
+}
+
+fn locks_dir() -> Result<PathBuf, String> {
+    worktree::state_dir().map(|d| d.join("locks"))
+}
+
+pub fn acquire(git: &Git, repo_root: &Path, command: &str) -> Result<RepoLock, String> {
+    let common_dir = git.common_dir()?;
+    let dir = locks_dir()?;
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("cannot create directory {}: {e}", dir.display()))?;
+    let name = lock_file_name(&worktree::canonicalize_or_self(&common_dir));
+    let path = dir.join(&name);
+
+    // The lock is written in full under a private name and then linked into
+    // place, so `wt locks --clean` never finds it without a pid and takes it
+    // for stale. Linking fails if the name is taken, like `create_new`.
+    let pid = std::process::id();
+    let tmp = dir.join(format!("{name}.{pid}.tmp"));
+    let written = std::fs::File::create(&tmp).and_then(|mut file| {
+        writeln!(file, "{pid}\n{command}\n{}", repo_root.display())?;
+        file.sync_all()
+    });
+    if let Err(e) = written {
+        let _ = std::fs::remove_file(&tmp);
+        return Err(format!("cannot write lock {}: {e}", path.display()));
+    }
+    let linked = std::fs::hard_link(&tmp, &path);
+    let _ = std::fs::remove_file(&tmp);
+    match linked {
+        Ok(()) => Ok(RepoLock { path }),
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+            Err(locked_message(&read_lock(&path)))
+        }
+        Err(e) => Err(format!("cannot create lock {}: {e}", path.display())),
+    }
+}
+
+pub fn list() -> Result<Vec<LockInfo>, String> {
+    let dir = locks_dir()?;
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(format!("cannot read {}: {e}", dir.display())),
+    };
+    let mut locks: Vec<LockInfo> = entries
+        .filter_map(Result::ok)
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "lock"))
+        .map(|p| read_lock(&p))
+        .collect();
+    locks.sort_by(|a, b| a.repo.cmp(&b.repo));
+    Ok(locks)
+}
+
+fn read_lock(path: &Path) -> LockInfo {
+    let content = std::fs::read_to_string(path).unwrap_or_default();
+    let mut lines = content.lines();
+    let pid = lines.next().and_then(|l| l.trim().parse().ok());
+    let command = lines.next().unwrap_or("").to_string();
+    let repo = PathBuf::from(lines.next().unwrap_or(""));
+    LockInfo {
+        path: path.to_path_buf(),
+        pid,
+        command,
+        repo,
+    }
+}
+
+// Prefix of the error when another wt holds the lock.
+pub const LOCKED_BY: &str = "repository is locked by";
+
+// Tells "someone else holds the lock" apart from failing to take it at all.
+pub fn is_held_error(err: &str) -> bool {
+    err.starts_with(LOCKED_BY)
+}
+
+fn locked_message(info: &LockInfo) -> String {
+    let owner = match info.pid {
+        Some(pid) => format!("`wt {}` (pid {pid})", info.command),
+        None => "an unreadable lockfile".to_string(),
+    };
+    if info.stale() {
+        format!(
+            "{LOCKED_BY} {owner}, which is no longer running, use `wt locks --clean` to remove it"
+        )
+    } else {
+        format!("{LOCKED_BY} {owner}")
+    }
+}
+
+// One file per admin repo, named after its git common dir. Percent-encoding
+// keeps the name reversible, so `/a-b` and `/a/b` never share a lock.
+fn lock_file_name(common_dir: &Path) -> String {
+    let mut name = String::new();
+    for byte in common_dir.to_string_lossy().bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.') {
+            name.push(byte as char);
+        } else {
+            name.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    name.push_str(".lock");
+    name
+}
+
+#[cfg(unix)]
+fn pid_alive(pid: u32) -> bool {
+    let Ok(pid) = libc::pid_t::try_from(pid) else {
+        return false;
+    };
+    if pid <= 0 {
+        return false;
+    }
+    // Signal 0 only checks for existence; EPERM means it exists but belongs
+    // to another user.
+    let alive = unsafe { libc::kill(pid, 0) } == 0;
+    alive || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+#[cfg(not(unix))]
+fn pid_alive(_pid: u32) -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lock_file_name_encodes_separators() {
+        assert_eq!(
+            lock_file_name(Path::new("/home/u/repo/.git")),
+            "%2Fhome%2Fu%2Frepo%2F.git.lock"
+        );
+        assert_ne!(
+            lock_file_name(Path::new("/a-b")),
+            lock_file_name(Path::new("/a/b"))
+        );
+    }
+
+    #[test]
+    fn own_pid_is_alive() {
+        assert!(pid_alive(std::process::id()));
+        assert!(!pid_alive(0));
+    }
+}
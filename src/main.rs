@@ -1,57 +1,262 @@
 mod cli;
 mod commands;
 mod config;
+mod deleted;
+mod editor;
 mod fuzzy;
 mod git;
+mod history;
+mod hooks;
+mod list_index;
+mod lock;
+mod recency;
+mod state;
 mod terminal;
 mod tui;
 mod worktree;
 
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
 use std::process;
 
-use clap::Parser;
+use clap::{CommandFactory, FromArgMatches};
 
-use crate::cli::{Cli, Command};
+use crate::cli::{Cli, Command, StateCommand};
 
 fn main() {
-    let cli = Cli::parse();
+    let mut cli = parse_cli();
+    if cli.verbose {
+        git::Git::enable_verbose();
+    }
+    if let Some(repo) = cli.command.as_mut().and_then(command_repo)
+        && let Err(e) = fill_repo(repo, cli.discover)
+    {
+        fail(&e);
+    }
 
     let result = match &cli.command {
         None => commands::tui::run(),
-        Some(Command::Clone { url }) => commands::clone::run(url),
-        Some(Command::Init { shell }) => commands::init::run(*shell),
+        Some(Command::Clone {
+            url,
+            name,
+            reference,
+            dissociate,
+        }) => commands::clone::run(url, name.as_deref(), reference.as_deref(), *dissociate),
+        Some(Command::Doctor { repo }) => commands::doctor::run(repo.as_deref()),
+        Some(Command::Locks { clean }) => commands::locks::run(*clean),
+        Some(Command::State {
+            action: StateCommand::Prune,
+        }) => commands::state::prune(),
+        Some(Command::Init { shell, write_rc }) => commands::init::run(*shell, *write_rc),
+        Some(Command::Complete { repo }) => commands::complete::run(repo.as_deref()),
         Some(Command::New {
             name,
             create,
             base,
             repo,
-        }) => commands::new::run(name, *create, base.as_deref(), repo.as_deref()),
-        Some(Command::List { repo, json, all }) => {
-            commands::list::run(repo.as_deref(), *json, *all)
+            independent,
+            path,
+            copy_untracked,
+            strict_setup,
+            cd,
+            open,
+            no_open,
+            from_stash_ref,
+            as_branch,
+            track,
+            no_track,
+            sparse,
+            from_base,
+        }) => commands::new::run(
+            // clap requires one of the two, and --as always creates a branch.
+            as_branch
+                .as_ref()
+                .or(name.as_ref())
+                .map_or("", String::as_str),
+            *create || as_branch.is_some(),
+            base.as_deref(),
+            repo.as_deref(),
+            *independent,
+            path.as_deref(),
+            commands::new::Options {
+                copy_untracked: copy_untracked.as_ref().map(Option::as_deref),
+                strict_setup: *strict_setup,
+                cd: *cd,
+                open: open_flag(open.is_some(), *no_open),
+                open_with: open.as_ref().and_then(Option::as_deref),
+                from_stash: from_stash_ref.as_deref(),
+                track: if *no_track {
+                    Some(None)
+                } else {
+                    track.as_deref().map(Some)
+                },
+                sparse: (!sparse.is_empty()).then_some(sparse.as_slice()),
+                from_base: *from_base,
+            },
+        ),
+        Some(Command::List {
+            repo,
+            json,
+            all,
+            repo_filter,
+            links,
+            size,
+            age,
+            sort,
+            remote,
+        }) => {
+            if *remote {
+                commands::list::run_remote(repo.as_deref(), *json)
+            } else {
+                let opts = commands::list::Options {
+                    links: *links,
+                    size: *size,
+                    age: *age,
+                    sort: *sort,
+                };
+                commands::list::run(repo.as_deref(), *json, *all, repo_filter.as_deref(), opts)
+            }
         }
         Some(Command::Remove {
             names,
             repo,
             force,
             keep_branch,
-        }) => commands::rm::run(names, repo.as_deref(), *force, *keep_branch),
+            delete_branch,
+            merged,
+            by_index,
+            dry_run,
+            fail_fast,
+            ignore_untracked,
+        }) => commands::rm::run(
+            names,
+            repo.as_deref(),
+            commands::rm::Options {
+                force: *force,
+                keep_branch: *keep_branch,
+                dry_run: *dry_run,
+                fail_fast: *fail_fast,
+                ignore_untracked: *ignore_untracked,
+            },
+            delete_branch.as_deref(),
+            *merged,
+            *by_index,
+        ),
+        Some(Command::Restore { branch, repo, yes }) => {
+            commands::restore::run(branch, repo.as_deref(), *yes)
+        }
+        Some(Command::Clean { name, repo, force }) => {
+            commands::clean::run(name, repo.as_deref(), *force)
+        }
         Some(Command::Prune {
             dry_run,
+            interactive,
             gone,
             stale,
+            unused,
+            older_than,
+            ignore_untracked,
+            squashed,
+            keep_branches,
+            prune_refs,
             repo,
             base,
-        }) => commands::prune::run(*dry_run, *gone, *stale, repo.as_deref(), base.as_deref()),
-        Some(Command::Path { name, repo }) => commands::path::run(name, repo.as_deref()),
-        Some(Command::Switch { name, create, repo }) => {
-            commands::switch::run(name, *create, repo.as_deref())
+            report_file,
+        }) => commands::prune::run(
+            commands::prune::PruneOptions {
+                dry_run: *dry_run,
+                // Without a terminal to answer, prune behaves as if not asked.
+                interactive: *interactive && terminal::is_stdin_tty(),
+                gone: *gone,
+                stale: *stale,
+                unused: *unused,
+                older_than: *older_than,
+                ignore_untracked: *ignore_untracked,
+                squashed: *squashed,
+                keep_branches: *keep_branches,
+                prune_refs: *prune_refs,
+                base: base.as_deref(),
+                report_file: report_file.as_deref(),
+            },
+            repo,
+        ),
+        Some(Command::Diff { name, base, repo }) => {
+            commands::diff::run(name, base.as_deref(), repo.as_deref())
+        }
+        Some(Command::Status { repo, all, json }) => {
+            commands::status::run(repo.as_deref(), *all, *json)
+        }
+        Some(Command::Move { old, new, repo }) => commands::mv::run(old, new, repo.as_deref()),
+        Some(Command::Tag {
+            name,
+            message,
+            repo,
+        }) => commands::tag::run(name, message.as_deref(), repo.as_deref()),
+        Some(Command::Path {
+            name,
+            repo,
+            by_index,
+        }) => match name {
+            Some(name) => commands::path::run(name, repo.as_deref(), *by_index),
+            None => commands::path::current(),
+        },
+        Some(Command::Open {
+            name,
+            with,
+            repo,
+            by_index,
+        }) => commands::open::run(name, *with, repo.as_deref(), *by_index),
+        Some(Command::Info { name, repo, json }) => {
+            commands::info::run(name, repo.as_deref(), *json)
         }
+        Some(Command::BranchOf { path, repo }) => commands::branch_of::run(path, repo.as_deref()),
+        Some(Command::Switch {
+            name,
+            create,
+            repo,
+            shell,
+            eval,
+            path_only,
+            open,
+            no_open,
+            by_index,
+        }) => match commands::switch::run(
+            name.as_deref(),
+            *create,
+            repo.as_deref(),
+            if *shell {
+                commands::switch::Handoff::Shell
+            } else if *eval {
+                commands::switch::Handoff::Eval
+            } else {
+                commands::switch::Handoff::Path
+            },
+            *path_only,
+            open_flag(*open, *no_open),
+            *by_index,
+        ) {
+            Ok(commands::switch::Outcome::Dismissed) => process::exit(130),
+            result => result.map(|_| ()),
+        },
         Some(Command::Link {
             files,
             repo,
             force,
+            copy,
+            all,
+            repair,
             list,
-        }) => commands::link::run(files, repo.as_deref(), *force, *list),
+            check,
+        }) => {
+            let set = if *all {
+                commands::link::Files::Manifest
+            } else if *repair {
+                commands::link::Files::Recorded
+            } else {
+                commands::link::Files::Named(files)
+            };
+            commands::link::run(set, repo.as_deref(), *force, *copy, *list, *check)
+        }
         Some(Command::Unlink {
             files,
             repo,
@@ -61,9 +266,204 @@ fn main() {
     };
 
     if let Err(e) = result {
-        if !e.is_empty() {
-            eprintln!("{e}");
+        fail(&e);
+    }
+}
+
+fn fail(e: &str) -> ! {
+    if json_errors() {
+        eprintln!("{}", error_json(e));
+    } else if !e.is_empty() {
+        eprintln!("{e}");
+    }
+    process::exit(1);
+}
+
+// The `--repo` of commands that work in one repo. `--all` spans every repo,
+// prune goes global without `--repo`, and `branch-of` resolves from its
+// directory argument, so those are left as given.
+fn command_repo(command: &mut Command) -> Option<&mut Option<PathBuf>> {
+    match command {
+        Command::List { all: true, .. } | Command::Status { all: true, .. } => None,
+        Command::New { repo, .. }
+        | Command::List { repo, .. }
+        | Command::Status { repo, .. }
+        | Command::Remove { repo, .. }
+        | Command::Move { repo, .. }
+        | Command::Tag { repo, .. }
+        | Command::Restore { repo, .. }
+        | Command::Clean { repo, .. }
+        | Command::Doctor { repo, .. }
+        | Command::Complete { repo, .. }
+        | Command::Path { repo, .. }
+        | Command::Open { repo, .. }
+        | Command::Info { repo, .. }
+        | Command::Diff { repo, .. }
+        | Command::Switch { repo, .. }
+        | Command::Link { repo, .. }
+        | Command::Unlink { repo, .. } => Some(repo),
+        _ => None,
+    }
+}
+
+// Where `--repo` (or the current directory) is not a repo, `--discover` uses
+// the one repo it holds, and wt.defaultToLastRepo the repo used last, as if it
+// had been passed with `--repo`.
+fn fill_repo(repo: &mut Option<PathBuf>, discover: bool) -> Result<(), String> {
+    if git::Git::find_repo(repo.as_deref()).is_ok() {
+        return Ok(());
+    }
+    if discover {
+        *repo = Some(git::Git::discover_child_repo(repo.as_deref())?);
+    } else if repo.is_none() {
+        *repo = last_used_repo();
+    }
+    Ok(())
+}
+
+// `wt.defaultToLastRepo`: outside any repo, fall back to the repo whose
+// worktree `wt` handed out most recently. Only global config (or WT_CONFIG)
+// can turn this on, since there is no repo config to read yet.
+fn last_used_repo() -> Option<PathBuf> {
+    if !git::Git::new(".").wt_flag("defaultToLastRepo") {
+        return None;
+    }
+    let root = recency::repos_by_recency()
+        .iter()
+        .find_map(|repo| git::Git::toplevel(Some(Path::new(repo))))?;
+    terminal::eprintln_dim(&format!(
+        "using last-used repo {} (wt.defaultToLastRepo)",
+        terminal::tilde_path(&root)
+    ));
+    Some(root)
+}
+
+// `--open`/`--no-open` override wt.openOnCreate; `None` defers to it.
+fn open_flag(open: bool, no_open: bool) -> Option<bool> {
+    if open {
+        Some(true)
+    } else if no_open {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+fn json_errors() -> bool {
+    std::env::var("WT_ERROR_FORMAT").is_ok_and(|v| v == "json")
+}
+
+fn error_json(message: &str) -> String {
+    serde_json::json!({
+        "error": error_kind(message),
+        "message": message,
+        "code": 1,
+    })
+    .to_string()
+}
+
+// Stable ids for `--json` errors, so scripts don't have to match on prose.
+fn error_kind(message: &str) -> &'static str {
+    const KINDS: &[(&str, &str)] = &[
+        ("worktree has local changes", "dirty_worktree"),
+        ("is not fully merged", "branch_not_merged"),
+        (
+            "current directory is inside the worktree",
+            "cwd_inside_worktree",
+        ),
+        ("the primary worktree", "primary_worktree"),
+        ("the bare repository", "primary_worktree"),
+        ("multiple worktrees match", "ambiguous_worktree"),
+        ("no worktree found for", "worktree_not_found"),
+        ("not a git repository", "not_a_repository"),
+        (lock::LOCKED_BY, "repo_locked"),
+    ];
+    if message.is_empty() {
+        return "git_failed";
+    }
+    KINDS
+        .iter()
+        .find(|(needle, _)| message.contains(needle))
+        .map_or("error", |(_, kind)| kind)
+}
+
+fn parse_cli() -> Cli {
+    let args: Vec<OsString> = std::env::args_os().collect();
+    let mut cmd = Cli::command();
+    if wants_help(&args) {
+        cmd = with_user_config(cmd, repo_arg(&args).as_deref());
+    }
+    let matches = cmd.get_matches_from(args);
+    Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit())
+}
+
+// `help` only counts in the subcommand position: `wt new help` names a
+// branch. Global options are flags, so the first non-option is that position.
+fn wants_help(args: &[OsString]) -> bool {
+    let mut rest = args.iter().skip(1);
+    rest.clone().any(|a| a == "--help" || a == "-h")
+        || rest
+            .find(|a| !a.to_string_lossy().starts_with('-'))
+            .is_some_and(|a| a == "help")
+}
+
+fn repo_arg(args: &[OsString]) -> Option<PathBuf> {
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == "--repo" {
+            return iter.next().map(PathBuf::from);
         }
-        process::exit(1);
+        if let Some(value) = arg.to_str().and_then(|a| a.strip_prefix("--repo=")) {
+            return Some(PathBuf::from(value));
+        }
+    }
+    None
+}
+
+fn with_user_config(mut cmd: clap::Command, repo: Option<&Path>) -> clap::Command {
+    let Ok(repo_root) = git::Git::find_repo(repo) else {
+        return cmd;
+    };
+    let links = config::get_links(&repo_root);
+    if links.is_empty() {
+        return cmd;
+    }
+    let section = format!("Your config:\n  auto-link: {}", links.join(", "));
+    for name in ["new", "switch", "link"] {
+        cmd = cmd.mut_subcommand(name, |sub| {
+            let examples = sub
+                .get_after_help()
+                .map(|h| format!("{h}\n\n"))
+                .unwrap_or_default();
+            sub.after_help(format!("{examples}{section}"))
+        });
+    }
+    cmd
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_kind_classifies_known_messages() {
+        assert_eq!(
+            error_kind("worktree has local changes, use --force to remove"),
+            "dirty_worktree"
+        );
+        assert_eq!(
+            error_kind("branch 'x' is not fully merged, use --force to remove"),
+            "branch_not_merged"
+        );
+        assert_eq!(
+            error_kind("cannot clean the primary worktree: /tmp/repo"),
+            "primary_worktree"
+        );
+        assert_eq!(
+            error_kind("cannot remove the bare repository: /tmp/repo.git"),
+            "primary_worktree"
+        );
+        assert_eq!(error_kind(""), "git_failed");
+        assert_eq!(error_kind("something else"), "error");
     }
 }
@@ -0,0 +1,124 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::repo_key;
+use crate::git::Git;
+use crate::state;
+use crate::worktree;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Recency {
+    #[serde(default)]
+    pub worktrees: BTreeMap<String, Entry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entry {
+    pub repo: String,
+    pub accessed: u64,
+}
+
+const FILE: &str = "recency.toml";
+
+pub fn load() -> Result<Recency, String> {
+    state::load(FILE)
+}
+
+// Best-effort: losing an access timestamp must never fail the command that
+// handed out the worktree.
+pub fn touch(repo: &Path, worktree_path: &Path) {
+    let _ = state::update(FILE, |recency: &mut Recency| {
+        recency.worktrees.insert(
+            repo_key(worktree_path),
+            Entry {
+                repo: repo_key(repo),
+                accessed: now(),
+            },
+        );
+    });
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+// Repos in order of their most recently handed-out worktree, newest first.
+pub fn repos_by_recency() -> Vec<String> {
+    let Ok(recency) = load() else { return vec![] };
+    let mut latest: BTreeMap<String, u64> = BTreeMap::new();
+    for entry in recency.worktrees.values() {
+        let accessed = latest.entry(entry.repo.clone()).or_default();
+        *accessed = (*accessed).max(entry.accessed);
+    }
+    let mut repos: Vec<(String, u64)> = latest.into_iter().collect();
+    repos.sort_by_key(|(_, accessed)| std::cmp::Reverse(*accessed));
+    repos.into_iter().map(|(repo, _)| repo).collect()
+}
+
+impl Recency {
+    pub fn accessed(&self, worktree_path: &Path) -> Option<u64> {
+        self.worktrees
+            .get(&repo_key(worktree_path))
+            .map(|e| e.accessed)
+    }
+}
+
+// Drops entries whose worktree is no longer live in its repo, going by each
+// repo's own worktree list. A repo that can't be listed has no live worktrees;
+// one first seen while listing is left for the next prune.
+pub fn prune() -> Result<Vec<String>, String> {
+    let repos: BTreeSet<String> = load()?.worktrees.values().map(|e| e.repo.clone()).collect();
+    let mut live = BTreeSet::new();
+    for repo in &repos {
+        let Ok(output) = Git::new(repo).list_worktrees() else {
+            continue;
+        };
+        live.extend(
+            worktree::parse_porcelain(&output)
+                .iter()
+                .filter(|wt| wt.live())
+                .map(|wt| repo_key(&wt.path)),
+        );
+    }
+
+    let dead = |path: &String, entry: &Entry| repos.contains(&entry.repo) && !live.contains(path);
+    state::update(FILE, |recency: &mut Recency| {
+        let removed: Vec<String> = recency
+            .worktrees
+            .iter()
+            .filter(|(path, entry)| dead(path, entry))
+            .map(|(path, _)| path.clone())
+            .collect();
+        recency.worktrees.retain(|path, entry| !dead(path, entry));
+        removed
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_recency_entries() {
+        let toml = r#"
+[worktrees."/tmp/wt/abc123/repo"]
+repo = "/tmp/repo"
+accessed = 1700000000
+"#;
+        let recency: Recency = toml::from_str(toml).unwrap();
+        let entry = recency.worktrees.get("/tmp/wt/abc123/repo").unwrap();
+        assert_eq!(entry.repo, "/tmp/repo");
+        assert_eq!(entry.accessed, 1_700_000_000);
+    }
+
+    #[test]
+    fn parse_empty_recency() {
+        let recency: Recency = toml::from_str("").unwrap();
+        assert!(recency.worktrees.is_empty());
+    }
+}
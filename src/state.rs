@@ -0,0 +1,55 @@
+use std::fs::File;
+use std::path::PathBuf;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::config;
+use crate::worktree;
+
+// The TOML files wt keeps under its state dir. Every change goes through
+// `update`, which holds an exclusive lock on `<file>.lock` across the read,
+// the change and the write, so two `wt` runs can't drop each other's entries.
+// The OS releases the lock with the file, so a crash never leaves it held.
+
+fn state_path(file: &str) -> Result<PathBuf, String> {
+    worktree::state_dir().map(|p| p.join(file))
+}
+
+pub fn load<T: DeserializeOwned + Default>(file: &str) -> Result<T, String> {
+    let path = state_path(file)?;
+    match std::fs::read_to_string(&path) {
+        Ok(content) => {
+            toml::from_str(&content).map_err(|e| format!("cannot parse {}: {e}", path.display()))
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(T::default()),
+        Err(e) => Err(format!("cannot read {}: {e}", path.display())),
+    }
+}
+
+pub fn update<T, R>(file: &str, change: impl FnOnce(&mut T) -> R) -> Result<R, String>
+where
+    T: Serialize + DeserializeOwned + Default,
+{
+    let path = state_path(file)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("cannot create {}: {e}", parent.display()))?;
+    }
+    let lock_path = path.with_file_name(format!("{file}.lock"));
+    let lock = File::options()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_path)
+        .and_then(|f| f.lock().map(|()| f))
+        .map_err(|e| format!("cannot lock {}: {e}", lock_path.display()))?;
+
+    let mut state = load(file)?;
+    let result = change(&mut state);
+    let content = toml::to_string_pretty(&state)
+        .map_err(|e| format!("cannot serialize {}: {e}", path.display()))?;
+    config::write_atomic(&path, &content)?;
+    drop(lock);
+    Ok(result)
+}
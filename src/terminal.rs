@@ -1,4 +1,5 @@
-use std::io::IsTerminal;
+use std::io::{IsTerminal, Write};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 
 pub fn is_stdout_tty() -> bool {
     std::io::stdout().is_terminal()
@@ -8,6 +9,10 @@ pub fn is_stderr_tty() -> bool {
     std::io::stderr().is_terminal()
 }
 
+pub fn is_stdin_tty() -> bool {
+    std::io::stdin().is_terminal()
+}
+
 pub struct Colors {
     pub bold: &'static str,
     pub green: &'static str,
@@ -110,13 +115,138 @@ pub fn eprintln_dim(msg: &str) {
     eprintln!("{}{msg}{}", clr.dim, clr.reset);
 }
 
+// Only asks when someone can answer; without a terminal on stdin it declines.
+pub fn confirm(prompt: &str) -> bool {
+    if !is_stdin_tty() {
+        return false;
+    }
+    eprint!("{prompt} [y/N] ");
+    let mut answer = String::new();
+    if std::io::stdin().read_line(&mut answer).is_err() {
+        return false;
+    }
+    matches!(answer.trim(), "y" | "Y" | "yes")
+}
+
+// Binary units, one decimal below 10 so small sizes don't all round to "1 M".
+pub fn human_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "K", "M", "G", "T"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else if size < 10.0 {
+        format!("{size:.1} {}", UNITS[unit])
+    } else {
+        format!("{size:.0} {}", UNITS[unit])
+    }
+}
+
+// How long ago `secs` seconds was, in the largest whole unit, as git's
+// relative dates read ("3 days ago").
+pub fn human_age(secs: u64) -> String {
+    const UNITS: [(&str, u64); 6] = [
+        ("year", 365 * 24 * 60 * 60),
+        ("month", 30 * 24 * 60 * 60),
+        ("week", 7 * 24 * 60 * 60),
+        ("day", 24 * 60 * 60),
+        ("hour", 60 * 60),
+        ("minute", 60),
+    ];
+    match UNITS.iter().find(|(_, size)| secs >= *size) {
+        Some((unit, size)) => {
+            let n = secs / size;
+            format!("{n} {unit}{} ago", if n == 1 { "" } else { "s" })
+        }
+        None => "just now".into(),
+    }
+}
+
+// Durations on the command line: a whole number with one unit, s/m/h/d/w.
+pub fn parse_duration(s: &str) -> Result<u64, String> {
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (count, unit) = s.split_at(split);
+    let seconds = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => return Err(format!("invalid duration '{s}', use e.g. 12h, 7d, or 2w")),
+    };
+    count
+        .parse::<u64>()
+        .ok()
+        .and_then(|n| n.checked_mul(seconds))
+        .ok_or_else(|| format!("invalid duration '{s}', use e.g. 12h, 7d, or 2w"))
+}
+
 pub fn print_cd_hint(name: &str) {
     if is_stdout_tty() {
-        let escaped = name.replace("'", r"'\''");
-        eprintln!("cd \"$(wt path '{escaped}')\"");
+        eprintln!("cd \"$(wt path {})\"", shell_quote(name));
     }
 }
 
+// POSIX single-quoting: everything is literal except `'`, which closes the
+// quote, adds an escaped quote, and reopens it.
+pub fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+pub struct Progress {
+    total: usize,
+    done: AtomicUsize,
+    shown: AtomicBool,
+    enabled: bool,
+}
+
+impl Progress {
+    pub fn new(total: usize) -> Self {
+        Self {
+            total,
+            done: AtomicUsize::new(0),
+            shown: AtomicBool::new(false),
+            enabled: total > 1 && is_stderr_tty(),
+        }
+    }
+
+    pub fn tick(&self, label: &str) {
+        let done = self.done.fetch_add(1, Ordering::Relaxed) + 1;
+        if !self.enabled {
+            return;
+        }
+        let line = progress_line(done, self.total, label, width());
+        let mut stderr = std::io::stderr().lock();
+        let _ = write!(stderr, "\r\x1b[2K{line}");
+        let _ = stderr.flush();
+        self.shown.store(true, Ordering::Relaxed);
+    }
+
+    pub fn clear(&self) {
+        if self.shown.swap(false, Ordering::Relaxed) {
+            let mut stderr = std::io::stderr().lock();
+            let _ = write!(stderr, "\r\x1b[2K");
+            let _ = stderr.flush();
+        }
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+fn progress_line(done: usize, total: usize, label: &str, cols: usize) -> String {
+    let prefix = format!("({done}/{total}) ");
+    let budget = cols.saturating_sub(prefix.chars().count() + 4);
+    format!("{prefix}{}...", trunc(label, budget))
+}
+
 pub fn width() -> usize {
     let columns_env = std::env::var("COLUMNS").ok();
     width_inner(columns_env.as_deref())
@@ -161,6 +291,41 @@ fn tiocgwinsz() -> Option<usize> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn parse_duration_units() {
+        assert_eq!(parse_duration("90s"), Ok(90));
+        assert_eq!(parse_duration("12h"), Ok(12 * 3600));
+        assert_eq!(parse_duration("7d"), Ok(7 * 86400));
+        assert_eq!(parse_duration("2w"), Ok(14 * 86400));
+        for bad in ["", "7", "d", "7x", "-7d", "1.5d", "99999999999999999999d"] {
+            assert!(parse_duration(bad).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn shell_quote_escapes_single_quotes() {
+        assert_eq!(shell_quote("/tmp/my repo"), "'/tmp/my repo'");
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+    }
+
+    #[test]
+    fn human_age_uses_largest_unit() {
+        assert_eq!(human_age(30), "just now");
+        assert_eq!(human_age(60), "1 minute ago");
+        assert_eq!(human_age(3 * 60 * 60 + 59), "3 hours ago");
+        assert_eq!(human_age(15 * 24 * 60 * 60), "2 weeks ago");
+        assert_eq!(human_age(400 * 24 * 60 * 60), "1 year ago");
+    }
+
+    #[test]
+    fn human_size_scales_units() {
+        assert_eq!(human_size(0), "0 B");
+        assert_eq!(human_size(1023), "1023 B");
+        assert_eq!(human_size(1536), "1.5 K");
+        assert_eq!(human_size(20 * 1024 * 1024), "20 M");
+        assert_eq!(human_size(3 * 1024 * 1024 * 1024), "3.0 G");
+    }
+
     #[test]
     fn trunc_short_string_unchanged() {
         assert_eq!(trunc("main", 10), "main");
@@ -301,6 +466,18 @@ mod tests {
         assert!(!result);
     }
 
+    #[test]
+    fn progress_line_shows_count_and_label() {
+        assert_eq!(progress_line(3, 12, "feat/x", 80), "(3/12) feat/x...");
+    }
+
+    #[test]
+    fn progress_line_truncates_long_label() {
+        let line = progress_line(1, 2, &"x".repeat(200), 80);
+        assert!(line.chars().count() <= 80, "line too long: {line}");
+        assert!(line.starts_with("(1/2) xxx"));
+    }
+
     #[test]
     fn width_from_columns_env() {
         assert_eq!(width_inner(Some("200")), 200);
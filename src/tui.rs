@@ -1,19 +1,36 @@
-use std::io;
+use std::io::{self, Write};
 
 use ratatui::backend::CrosstermBackend;
 use ratatui::{Terminal, TerminalOptions, Viewport};
 
-pub type StdoutTerminal = Terminal<CrosstermBackend<io::Stdout>>;
+// Where the picker draws. `switch` draws on stderr so the shell wrapper can
+// still capture the chosen path from stdout.
+#[derive(Clone, Copy)]
+pub enum Target {
+    Stdout,
+    Stderr,
+}
+
+impl Target {
+    fn writer(self) -> Box<dyn Write> {
+        match self {
+            Target::Stdout => Box::new(io::stdout()),
+            Target::Stderr => Box::new(io::stderr()),
+        }
+    }
+}
+
+pub type PickerTerminal = Terminal<CrosstermBackend<Box<dyn Write>>>;
 
-fn init(height: u16) -> io::Result<StdoutTerminal> {
+fn init(target: Target, height: u16) -> io::Result<PickerTerminal> {
     let original = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |info| {
-        let _ = restore();
+        let _ = restore(target);
         original(info);
     }));
     ratatui::crossterm::terminal::enable_raw_mode()?;
 
-    let backend = CrosstermBackend::new(io::stdout());
+    let backend = CrosstermBackend::new(target.writer());
     Terminal::with_options(
         backend,
         TerminalOptions {
@@ -21,27 +38,27 @@ fn init(height: u16) -> io::Result<StdoutTerminal> {
         },
     )
     .inspect_err(|_| {
-        let _ = restore();
+        let _ = restore(target);
     })
 }
 
-fn restore() -> io::Result<()> {
-    let cursor = ratatui::crossterm::execute!(io::stdout(), ratatui::crossterm::cursor::Show);
+fn restore(target: Target) -> io::Result<()> {
+    let cursor = ratatui::crossterm::execute!(target.writer(), ratatui::crossterm::cursor::Show);
     let raw = ratatui::crossterm::terminal::disable_raw_mode();
     cursor.and(raw)
 }
 
-pub fn run<F, R>(height: u16, f: F) -> io::Result<R>
+pub fn run<F, R>(target: Target, height: u16, f: F) -> io::Result<R>
 where
-    F: FnOnce(&mut StdoutTerminal) -> io::Result<R>,
+    F: FnOnce(&mut PickerTerminal) -> io::Result<R>,
 {
-    let mut terminal = init(height)?;
+    let mut terminal = init(target, height)?;
     let result = f(&mut terminal);
     let _ = terminal.clear();
     if result.is_ok() {
-        restore()?;
+        restore(target)?;
     } else {
-        let _ = restore();
+        let _ = restore(target);
     }
     result
 }
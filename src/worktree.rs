@@ -1,6 +1,9 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 
+use crate::config;
+use crate::fuzzy;
 use crate::git::Git;
 use crate::terminal;
 
@@ -23,6 +26,8 @@ impl Worktree {
 
 pub struct RepoInfo {
     pub name: String,
+    // The primary checkout, or the bare clone for a repo from `wt clone`.
+    pub path: PathBuf,
     pub worktrees: Vec<WorktreeInfo>,
 }
 
@@ -173,6 +178,35 @@ pub fn resolve_worktree<'a>(worktrees: &'a [Worktree], name: &str, git: &Git) ->
     Resolved::NotFound
 }
 
+pub fn resolve_named<'a>(
+    worktrees: &'a [Worktree],
+    name: &str,
+    git: &Git,
+) -> Result<&'a Worktree, String> {
+    match resolve_worktree(worktrees, name, git) {
+        Resolved::Found(wt) => Ok(wt),
+        Resolved::Ambiguous { matches, kind } => {
+            eprintln!("ambiguous {kind} '{name}'; matches:");
+            for m in &matches {
+                eprintln!("  - {}", m.path.display());
+            }
+            Err("multiple worktrees match, specify a path instead".into())
+        }
+        Resolved::NotFound => {
+            let branches: Vec<&str> = worktrees
+                .iter()
+                .filter_map(|wt| wt.branch.as_deref())
+                .collect();
+            Err(match fuzzy::close_match(name, &branches) {
+                Some(suggestion) => {
+                    format!("no worktree found for: {name}, did you mean '{suggestion}'?")
+                }
+                None => format!("no worktree found for: {name}"),
+            })
+        }
+    }
+}
+
 pub fn find_by_path<'a>(worktrees: &'a [Worktree], path: &Path) -> Option<&'a Worktree> {
     let canonical = canonicalize_or_self(path);
     worktrees
@@ -230,7 +264,10 @@ pub fn create_dest(repo_root: &Path, git: &Git) -> Result<PathBuf, String> {
     let repo_name = origin_url
         .as_deref()
         .and_then(parse_repo_name)
-        .or_else(|| repo_root.file_name().and_then(|n| n.to_str()))
+        .or_else(|| {
+            let name = repo_root.file_name()?.to_str()?;
+            Some(name.strip_suffix(".git").unwrap_or(name))
+        })
         .ok_or_else(|| format!("cannot determine repo name from {}", repo_root.display()))?;
     create_worktree_dest(repo_name)
 }
@@ -248,34 +285,154 @@ pub(crate) fn wt_home() -> Result<PathBuf, String> {
     Ok(Path::new(&home).join(".wt"))
 }
 
+// Sidecar files (recency, locks, logs) belong here rather than under `~/.wt`,
+// which holds only user-facing config, repos, and worktrees.
+pub(crate) fn state_dir() -> Result<PathBuf, String> {
+    resolve_state_dir(
+        std::env::var("XDG_STATE_HOME").ok().as_deref(),
+        std::env::var("HOME").ok().as_deref(),
+    )
+}
+
+// Per the XDG spec, a relative XDG_STATE_HOME is invalid and must be ignored.
+fn resolve_state_dir(xdg_state_home: Option<&str>, home: Option<&str>) -> Result<PathBuf, String> {
+    if let Some(dir) = xdg_state_home.map(Path::new)
+        && dir.is_absolute()
+    {
+        return Ok(dir.join("wt"));
+    }
+    let home = home
+        .filter(|h| !h.is_empty())
+        .ok_or("cannot determine home directory: HOME is not set")?;
+    Ok(Path::new(home).join(".local").join("state").join("wt"))
+}
+
+// WT_ROOT moves only the worktrees (say, onto a faster disk); config and
+// bare repos stay under ~/.wt. Unlike XDG_STATE_HOME, a relative value is an
+// error: silently ignoring it would scatter worktrees the user didn't expect.
 pub(crate) fn worktrees_root() -> Result<PathBuf, String> {
-    wt_home().map(|p| p.join("worktrees"))
+    match std::env::var_os("WT_ROOT").filter(|v| !v.is_empty()) {
+        Some(root) => resolve_worktrees_root(Path::new(&root)),
+        None => wt_home().map(|p| p.join("worktrees")),
+    }
+}
+
+fn resolve_worktrees_root(root: &Path) -> Result<PathBuf, String> {
+    if !root.is_absolute() {
+        return Err("WT_ROOT must be an absolute path".into());
+    }
+    Ok(root.to_path_buf())
 }
 
 pub(crate) fn repos_root() -> Result<PathBuf, String> {
     wt_home().map(|p| p.join("repos"))
 }
 
+// `wt clone` also makes its first worktree, so both roots are checked before
+// anything is fetched.
 pub fn create_bare_dest(repo_name: &str) -> Result<PathBuf, String> {
     let base = repos_root()?;
-    let dest = unique_dest(&base, repo_name)?;
-    std::fs::create_dir_all(&dest)
-        .map_err(|e| format!("cannot create directory {}: {e}", dest.display()))?;
-    Ok(dest)
+    check_writable(&base, "repository root")?;
+    check_root_writable(&worktrees_root()?)?;
+    create_unique_dir(&base, repo_name)
 }
 
 pub fn create_worktree_dest(repo_name: &str) -> Result<PathBuf, String> {
     let base = worktrees_root()?;
-    let dest = unique_dest(&base, repo_name)?;
+    check_root_writable(&base)?;
+    create_unique_dir(&base, repo_name)
+}
+
+pub(crate) fn check_root_writable(root: &Path) -> Result<(), String> {
+    check_writable(root, "worktree root")
+}
+
+// Checked before anything is created, so a read-only mount or a root owned by
+// someone else fails naming the root, not deep inside mkdir or git. A root
+// that doesn't exist yet is judged by the nearest path that does, which has
+// to be a directory to create anything under.
+fn check_writable(root: &Path, what: &str) -> Result<(), String> {
+    let existing = root.ancestors().find(|p| p.exists()).unwrap_or(root);
+    let reason = if !existing.is_dir() {
+        "not a directory"
+    } else if !is_writable(existing) {
+        "permission denied"
+    } else {
+        return Ok(());
+    };
+    Err(format!(
+        "cannot write to {what} {}: {reason}",
+        canonicalize_or_self(root).display()
+    ))
+}
+
+#[cfg(unix)]
+fn is_writable(path: &Path) -> bool {
+    use std::os::unix::ffi::OsStrExt;
+
+    let Ok(path) = std::ffi::CString::new(path.as_os_str().as_bytes()) else {
+        return false;
+    };
+    unsafe { libc::access(path.as_ptr(), libc::W_OK) == 0 }
+}
+
+#[cfg(not(unix))]
+fn is_writable(_path: &Path) -> bool {
+    true
+}
+
+// Git records worktree paths with symlinks resolved, so when ~/.wt or WT_ROOT
+// is a symlink, hand back the resolved path too; otherwise `new` would print
+// a path that `list` and `path` never show.
+fn create_unique_dir(base: &Path, repo_name: &str) -> Result<PathBuf, String> {
+    let dest = unique_dest(base, repo_name)?;
     std::fs::create_dir_all(&dest)
         .map_err(|e| format!("cannot create directory {}: {e}", dest.display()))?;
-    Ok(dest)
+    Ok(canonicalize_or_self(&dest))
 }
 
 pub(crate) fn canonicalize_or_self(path: &Path) -> PathBuf {
     path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
 }
 
+// Bytes in a worktree's files. The top-level `.git` is skipped: in a linked
+// worktree it's a pointer file, in the primary it's the object store every
+// worktree shares. Symlinks count as themselves, not their targets.
+pub(crate) fn dir_size(path: &Path) -> u64 {
+    fn walk(dir: &Path, top: bool) -> u64 {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return 0;
+        };
+        entries
+            .filter_map(Result::ok)
+            .filter(|e| !(top && e.file_name() == ".git"))
+            .map(|e| match e.metadata() {
+                Ok(meta) if meta.is_dir() => walk(&e.path(), false),
+                Ok(meta) => meta.len(),
+                Err(_) => 0,
+            })
+            .sum()
+    }
+    walk(path, true)
+}
+
+// Entries git tracks separately that land in the same directory, through a
+// symlink or a case-insensitive filesystem. Removing one would remove the
+// others' files too, so callers that delete worktrees must leave them alone.
+pub fn canonical_collisions(worktrees: &[Worktree]) -> Vec<Vec<&Worktree>> {
+    let mut by_dir: BTreeMap<PathBuf, Vec<&Worktree>> = BTreeMap::new();
+    for wt in worktrees.iter().filter(|wt| wt.live()) {
+        by_dir
+            .entry(canonicalize_or_self(&wt.path))
+            .or_default()
+            .push(wt);
+    }
+    by_dir
+        .into_values()
+        .filter(|group| group.len() > 1)
+        .collect()
+}
+
 pub fn find_primary<'a>(worktrees: &'a [Worktree], repo_root: &Path) -> Option<&'a Worktree> {
     let canonical_root = canonicalize_or_self(repo_root);
     worktrees
@@ -367,11 +524,21 @@ fn collect_repos(dir: &Path, repos: &mut BTreeSet<PathBuf>) {
     }
 }
 
+// A linked worktree's gitdir is always `<admin>/worktrees/<name>`. Anything
+// else is corrupt or hand-written and must not drive removal decisions.
+pub(crate) fn is_worktree_gitdir(gitdir: &Path) -> bool {
+    gitdir
+        .parent()
+        .and_then(Path::file_name)
+        .is_some_and(|name| name == "worktrees")
+        && gitdir.file_name().is_some()
+}
+
 fn admin_repo_from_gitdir(gitdir: &Path) -> Option<PathBuf> {
-    let worktrees_dir = gitdir.parent()?;
-    if worktrees_dir.file_name()?.to_str()? != "worktrees" {
+    if !is_worktree_gitdir(gitdir) {
         return None;
     }
+    let worktrees_dir = gitdir.parent()?;
     let parent = worktrees_dir.parent()?;
     if parent.file_name()?.to_str()? == ".git" {
         let repo = parent.parent()?;
@@ -392,7 +559,9 @@ pub(crate) fn repo_basename(path: &Path) -> String {
 pub(crate) fn parse_gitdir(dot_git_file: &Path) -> Option<PathBuf> {
     let content = std::fs::read_to_string(dot_git_file).ok()?;
     let line = content.lines().next()?;
-    let gitdir = line.strip_prefix("gitdir: ")?.trim();
+    // Only the line ending is noise; leading or internal spaces belong to
+    // the path.
+    let gitdir = line.strip_prefix("gitdir: ")?.trim_end();
     if gitdir.is_empty() {
         return None;
     }
@@ -406,6 +575,29 @@ pub(crate) fn parse_gitdir(dot_git_file: &Path) -> Option<PathBuf> {
     }
 }
 
+// Git leaves these markers in the worktree's git dir while an operation is
+// paused, so checking for them is enough and avoids another subprocess per row.
+pub(crate) fn pending_operation(worktree_path: &Path) -> Option<&'static str> {
+    const MARKERS: &[(&str, &str)] = &[
+        ("rebase-merge", "rebasing"),
+        ("rebase-apply", "rebasing"),
+        ("MERGE_HEAD", "merging"),
+        ("CHERRY_PICK_HEAD", "cherry-picking"),
+        ("REVERT_HEAD", "reverting"),
+        ("BISECT_LOG", "bisecting"),
+    ];
+    let dot_git = worktree_path.join(".git");
+    let git_dir = if dot_git.is_dir() {
+        dot_git
+    } else {
+        parse_gitdir(&dot_git)?
+    };
+    MARKERS
+        .iter()
+        .find(|(marker, _)| git_dir.join(marker).exists())
+        .map(|(_, op)| *op)
+}
+
 pub fn find_current_worktree<'a>(
     worktrees: impl IntoIterator<Item = &'a Worktree>,
     cwd: Option<&Path>,
@@ -426,6 +618,7 @@ pub fn find_current_worktree<'a>(
 pub(crate) fn enrich_worktrees(
     worktrees: &[Worktree],
     current_path: Option<&Path>,
+    progress: Option<&terminal::Progress>,
 ) -> Vec<WorktreeInfo> {
     std::thread::scope(|s| {
         let handles: Vec<_> = worktrees
@@ -438,6 +631,9 @@ pub(crate) fn enrich_worktrees(
                         Git::worktree_status(&wt.path)
                     };
                     let current = current_path == Some(wt.path.as_path());
+                    if let Some(progress) = progress {
+                        progress.tick(wt.branch.as_deref().unwrap_or(&repo_basename(&wt.path)));
+                    }
                     WorktreeInfo::from_worktree(wt, dirty, ahead, behind, current)
                 })
             })
@@ -450,9 +646,78 @@ pub(crate) fn enrich_worktrees(
     })
 }
 
-pub(crate) fn load_all(wt_root: &Path) -> Result<Vec<RepoInfo>, String> {
+// Commands that span every repo work on them in parallel, a bounded number at
+// a time, so a long repo list doesn't start all its git processes at once.
+const REPO_WORKERS: usize = 8;
+
+// `f` over `repos` on the worker pool; results come back in `repos` order.
+pub(crate) fn map_repos<T: Sync, R: Send>(repos: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let next = AtomicUsize::new(0);
+    std::thread::scope(|s| {
+        let handles: Vec<_> = (0..REPO_WORKERS.min(repos.len()))
+            .map(|_| {
+                let (next, f) = (&next, &f);
+                s.spawn(move || {
+                    let mut done = Vec::new();
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(repo) = repos.get(i) else {
+                            return done;
+                        };
+                        done.push((i, f(repo)));
+                    }
+                })
+            })
+            .collect();
+        let mut results: Vec<Option<R>> = repos.iter().map(|_| None).collect();
+        for handle in handles {
+            let done = handle
+                .join()
+                .unwrap_or_else(|e| std::panic::resume_unwind(e));
+            for (i, result) in done {
+                results[i] = Some(result);
+            }
+        }
+        results.into_iter().flatten().collect()
+    })
+}
+
+// The `wt new --independent` clones recorded for a repo that still exist,
+// as rows alongside git's own worktrees.
+pub(crate) fn load_independent(repo_root: &Path) -> Vec<Worktree> {
+    config::get_independent(repo_root)
+        .into_iter()
+        .filter(|path| path.join(".git").is_dir())
+        .map(|path| {
+            let git = Git::new(&path);
+            let branch = git.current_branch();
+            Worktree {
+                head: git.rev_parse("HEAD").unwrap_or_default(),
+                detached: branch.is_none(),
+                branch,
+                path,
+                bare: false,
+                locked: false,
+                prunable: false,
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn is_independent(independent: &[PathBuf], path: &Path) -> bool {
+    let path = canonicalize_or_self(path);
+    independent.iter().any(|p| canonicalize_or_self(p) == path)
+}
+
+pub(crate) fn load_all(wt_root: &Path, show_progress: bool) -> Result<Vec<RepoInfo>, String> {
     let wt_root = canonicalize_or_self(wt_root);
-    let admin_repos = discover_repos(&wt_root);
+    let mut admin_repos: BTreeSet<PathBuf> = discover_repos(&wt_root)
+        .iter()
+        .map(|p| canonicalize_or_self(p))
+        .collect();
+    admin_repos.extend(config::independent_repos());
+    let progress = show_progress.then(|| terminal::Progress::new(admin_repos.len()));
+    let progress = progress.as_ref();
     let cwd = std::env::current_dir()
         .ok()
         .and_then(|p| p.canonicalize().ok());
@@ -462,6 +727,14 @@ pub(crate) fn load_all(wt_root: &Path) -> Result<Vec<RepoInfo>, String> {
             .iter()
             .map(|repo_path| {
                 s.spawn(move || {
+                    // A worktree can outlive the repo it was made from; with
+                    // nothing left to ask, it isn't listed.
+                    if !repo_path.is_dir() {
+                        if let Some(progress) = progress {
+                            progress.tick(&repo_basename(repo_path));
+                        }
+                        return None;
+                    }
                     let git = Git::new(repo_path);
                     let output = match git.list_worktrees() {
                         Ok(o) => o,
@@ -476,14 +749,19 @@ pub(crate) fn load_all(wt_root: &Path) -> Result<Vec<RepoInfo>, String> {
                             return None;
                         }
                     };
-                    let worktrees = parse_porcelain(&output);
+                    let mut worktrees = parse_porcelain(&output);
+                    worktrees.extend(load_independent(repo_path));
                     let name = repo_basename(repo_path);
-                    let infos = enrich_worktrees(&worktrees, None);
+                    let infos = enrich_worktrees(&worktrees, None, None);
+                    if let Some(progress) = progress {
+                        progress.tick(&name);
+                    }
                     if infos.is_empty() {
                         return None;
                     }
                     Some(RepoInfo {
                         name,
+                        path: repo_path.clone(),
                         worktrees: infos,
                     })
                 })
@@ -692,6 +970,15 @@ prunable gitdir file points to non-existent location
         assert_eq!(admin_repo_from_gitdir(&wt_gitdir), Some(bare));
     }
 
+    #[test]
+    fn is_worktree_gitdir_requires_worktrees_parent() {
+        assert!(is_worktree_gitdir(Path::new("/repo/.git/worktrees/feat")));
+        assert!(is_worktree_gitdir(Path::new("/repos/bare/worktrees/feat")));
+        assert!(!is_worktree_gitdir(Path::new("/etc/passwd")));
+        assert!(!is_worktree_gitdir(Path::new("/repo/.git")));
+        assert!(!is_worktree_gitdir(Path::new("worktrees")));
+    }
+
     #[test]
     fn admin_repo_from_gitdir_unknown_layout() {
         let gitdir = PathBuf::from("/some/random/worktrees/thing");
@@ -795,6 +1082,83 @@ prunable gitdir file points to non-existent location
         );
     }
 
+    #[test]
+    fn pending_operation_from_linked_gitdir() {
+        let tmp = tempfile::tempdir().unwrap();
+        let gitdir = tmp.path().join("admin").join("worktrees").join("feat");
+        let wt = tmp.path().join("feat");
+        std::fs::create_dir_all(&gitdir).unwrap();
+        std::fs::create_dir(&wt).unwrap();
+        std::fs::write(wt.join(".git"), format!("gitdir: {}", gitdir.display())).unwrap();
+        assert_eq!(pending_operation(&wt), None);
+
+        std::fs::write(gitdir.join("MERGE_HEAD"), "abc").unwrap();
+        assert_eq!(pending_operation(&wt), Some("merging"));
+
+        std::fs::create_dir(gitdir.join("rebase-merge")).unwrap();
+        assert_eq!(pending_operation(&wt), Some("rebasing"));
+    }
+
+    #[test]
+    fn pending_operation_from_git_directory() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir(tmp.path().join(".git")).unwrap();
+        std::fs::write(tmp.path().join(".git").join("BISECT_LOG"), "").unwrap();
+        assert_eq!(pending_operation(tmp.path()), Some("bisecting"));
+    }
+
+    #[test]
+    fn pending_operation_without_git_dir() {
+        let tmp = tempfile::tempdir().unwrap();
+        assert_eq!(pending_operation(tmp.path()), None);
+    }
+
+    #[test]
+    fn worktrees_root_requires_absolute_path() {
+        assert_eq!(
+            resolve_worktrees_root(Path::new("/scratch/wt")),
+            Ok(PathBuf::from("/scratch/wt"))
+        );
+        assert_eq!(
+            resolve_worktrees_root(Path::new("scratch/wt")),
+            Err("WT_ROOT must be an absolute path".to_string())
+        );
+    }
+
+    #[test]
+    fn state_dir_uses_xdg_state_home() {
+        assert_eq!(
+            resolve_state_dir(Some("/xdg/state"), Some("/home/u")),
+            Ok(PathBuf::from("/xdg/state/wt"))
+        );
+    }
+
+    #[test]
+    fn state_dir_falls_back_to_home() {
+        assert_eq!(
+            resolve_state_dir(None, Some("/home/u")),
+            Ok(PathBuf::from("/home/u/.local/state/wt"))
+        );
+    }
+
+    #[test]
+    fn state_dir_rejects_relative_xdg_state_home() {
+        assert_eq!(
+            resolve_state_dir(Some("relative/state"), Some("/home/u")),
+            Ok(PathBuf::from("/home/u/.local/state/wt"))
+        );
+        assert_eq!(
+            resolve_state_dir(Some(""), Some("/home/u")),
+            Ok(PathBuf::from("/home/u/.local/state/wt"))
+        );
+    }
+
+    #[test]
+    fn state_dir_errors_without_home() {
+        assert!(resolve_state_dir(None, None).is_err());
+        assert!(resolve_state_dir(Some("relative"), Some("")).is_err());
+    }
+
     #[test]
     fn parse_gitdir_absolute() {
         let tmp = tempfile::tempdir().unwrap();
@@ -818,6 +1182,23 @@ prunable gitdir file points to non-existent location
         assert_eq!(result, sub.join("../../admin/.git/worktrees/feat"));
     }
 
+    #[test]
+    fn parse_gitdir_keeps_spaces_in_path() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dot_git = tmp.path().join(".git");
+        std::fs::write(
+            &dot_git,
+            "gitdir: /Users/me/Library/Mobile Documents/ repo/.git/worktrees/feat \r\n",
+        )
+        .unwrap();
+        assert_eq!(
+            parse_gitdir(&dot_git),
+            Some(PathBuf::from(
+                "/Users/me/Library/Mobile Documents/ repo/.git/worktrees/feat"
+            ))
+        );
+    }
+
     #[test]
     fn parse_gitdir_empty_value() {
         let tmp = tempfile::tempdir().unwrap();
@@ -933,7 +1314,7 @@ prunable gitdir file points to non-existent location
 
         std::fs::write(wt_dest.join("dirty.txt"), "change").unwrap();
 
-        let repos = load_all(&wt_root).expect("should load repos");
+        let repos = load_all(&wt_root, false).expect("should load repos");
         assert_eq!(repos.len(), 1);
         assert_eq!(repos[0].name, "myrepo");
         assert!(!repos[0].worktrees.is_empty());
@@ -983,7 +1364,7 @@ prunable gitdir file points to non-existent location
             );
         }
 
-        let repos = load_all(&wt_root).expect("should load repos");
+        let repos = load_all(&wt_root, false).expect("should load repos");
         assert_eq!(repos.len(), 2);
         assert_eq!(repos[0].name, "alpha");
         assert_eq!(repos[1].name, "beta");
@@ -992,7 +1373,7 @@ prunable gitdir file points to non-existent location
     #[test]
     fn load_all_empty_root() {
         let tmp = tempfile::tempdir().unwrap();
-        let repos = load_all(tmp.path()).unwrap();
+        let repos = load_all(tmp.path(), false).unwrap();
         assert!(repos.is_empty());
     }
 
@@ -1075,6 +1456,7 @@ prunable gitdir file points to non-existent location
 
         let mut repos = vec![RepoInfo {
             name: "repo".into(),
+            path: PathBuf::from("/repo"),
             worktrees: vec![
                 WorktreeInfo::from_worktree(
                     &make_worktree(outer, Some("main")),
@@ -1109,6 +1491,7 @@ prunable gitdir file points to non-existent location
         let mut repos = vec![
             RepoInfo {
                 name: "alpha".into(),
+                path: PathBuf::from("/alpha"),
                 worktrees: vec![WorktreeInfo::from_worktree(
                     &make_worktree(shallow, Some("main")),
                     false,
@@ -1119,6 +1502,7 @@ prunable gitdir file points to non-existent location
             },
             RepoInfo {
                 name: "beta".into(),
+                path: PathBuf::from("/beta"),
                 worktrees: vec![WorktreeInfo::from_worktree(
                     &make_worktree(deep.clone(), Some("feat")),
                     false,
@@ -0,0 +1,88 @@
+use serde_json::Value;
+use tempfile::TempDir;
+
+pub mod common;
+
+use common::*;
+
+// A bare repo with `main` pushed to it and no worktrees yet, the starting
+// point of the bare-repo workflow.
+fn setup_bare() -> (TempDir, std::path::PathBuf) {
+    let (home, repo) = setup();
+    let bare = home.path().join("project.git");
+    init_bare_repo(&bare);
+    assert_git_success_with(&repo, |cmd| {
+        cmd.arg("push").arg(&bare).arg("main");
+    });
+    assert_git_success(&bare, &["symbolic-ref", "HEAD", "refs/heads/main"]);
+    (home, bare)
+}
+
+#[test]
+fn new_list_rm_from_inside_bare_repo() {
+    let (home, bare) = setup_bare();
+
+    let run_in_bare = |args: &[&str]| {
+        run_wt(home.path(), |cmd| {
+            cmd.args(args).current_dir(&bare);
+        })
+    };
+
+    let output = run_in_bare(&["new", "main"]);
+    assert_exit_code(&output, 0);
+    let main_wt = parse_wt_new_path(&output);
+    let output = run_in_bare(&["new", "-c", "feat/a"]);
+    assert_exit_code(&output, 0);
+    let feat_wt = parse_wt_new_path(&output);
+    assert_eq!(feat_wt.file_name().unwrap(), "project");
+
+    let output = run_in_bare(&["list", "--json"]);
+    assert_exit_code(&output, 0);
+    let entries: Vec<Value> = serde_json::from_slice(&output.stdout).unwrap();
+    // Git orders linked worktrees by their random admin names, so only the
+    // bare entry's place is fixed.
+    let mut paths: Vec<&str> = entries.iter().filter_map(|e| e["path"].as_str()).collect();
+    paths[1..].sort_unstable();
+    let mut linked = [main_wt.to_str().unwrap(), feat_wt.to_str().unwrap()];
+    linked.sort_unstable();
+    assert_eq!(paths[0], canonical(&bare).to_str().unwrap());
+    assert_eq!(paths[1..], linked);
+    assert_eq!(entries[0]["bare"], true);
+
+    let output = run_in_bare(&["rm", "feat/a"]);
+    assert_exit_code(&output, 0);
+    assert!(!feat_wt.exists());
+    assert_branch_absent(&bare, "feat/a");
+    assert!(main_wt.exists());
+}
+
+#[test]
+fn rm_refuses_the_bare_repository() {
+    let (home, bare) = setup_bare();
+    wt_new(home.path(), &bare, "feat/a");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.arg("rm").arg(&bare).arg("--repo").arg(&bare);
+    });
+    assert_error(
+        &output,
+        1,
+        &format!(
+            "cannot remove the bare repository: {}\n",
+            canonical(&bare).display()
+        ),
+    );
+    assert!(bare.join("HEAD").exists());
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.arg("rm")
+            .arg(&bare)
+            .arg("--repo")
+            .arg(&bare)
+            .env("WT_ERROR_FORMAT", "json");
+    });
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let error: serde_json::Value = serde_json::from_str(stderr.trim())
+        .unwrap_or_else(|e| panic!("stderr should be a JSON object ({e}), got: {stderr}"));
+    assert_eq!(error["error"], "primary_worktree");
+}
@@ -0,0 +1,71 @@
+pub mod common;
+
+use common::*;
+
+#[test]
+fn prints_branch_of_current_worktree() {
+    let (home, repo) = setup();
+    let wt_path = wt_new(home.path(), &repo, "feat/x");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["branch-of", "."]).current_dir(&wt_path);
+    });
+
+    assert!(
+        output.status.success(),
+        "wt branch-of failed: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "feat/x\n");
+    assert_stderr_empty(&output);
+}
+
+#[test]
+fn resolves_nested_path_to_innermost_worktree() {
+    let (home, repo) = setup();
+    let wt_path = wt_new(home.path(), &repo, "feat-nested");
+    let sub = wt_path.join("src");
+    std::fs::create_dir(&sub).unwrap();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.arg("branch-of").arg(&sub).current_dir(&repo);
+    });
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "feat-nested\n");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.arg("branch-of").current_dir(&repo);
+    });
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "main\n");
+}
+
+#[test]
+fn prints_short_sha_for_detached_worktree() {
+    let (home, repo) = setup();
+    let wt_path = wt_new(home.path(), &repo, "feat-detach");
+    assert_git_success(&wt_path, &["checkout", "--detach"]);
+    let head = assert_git_stdout_success(&wt_path, &["rev-parse", "--short=7", "HEAD"]);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["branch-of", "."]).current_dir(&wt_path);
+    });
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), head.trim());
+}
+
+#[test]
+fn errors_when_path_is_outside_every_worktree() {
+    let (home, repo) = setup();
+    let outside = home.path().join("elsewhere");
+    std::fs::create_dir(&outside).unwrap();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.arg("branch-of").arg(&outside).arg("--repo").arg(&repo);
+    });
+
+    assert_error(
+        &output,
+        1,
+        &format!("not inside a worktree: {}\n", outside.display()),
+    );
+}
@@ -0,0 +1,131 @@
+pub mod common;
+
+use common::*;
+
+fn setup_artifacts(wt_path: &std::path::Path) {
+    std::fs::write(wt_path.join(".gitignore"), "target/\n").unwrap();
+    assert_git_success(wt_path, &["add", ".gitignore"]);
+    assert_git_success(wt_path, &["commit", "-m", "ignore target"]);
+    std::fs::create_dir(wt_path.join("target")).unwrap();
+    std::fs::write(wt_path.join("target").join("out.o"), "bin").unwrap();
+    std::fs::write(wt_path.join("scratch.txt"), "notes").unwrap();
+}
+
+#[test]
+fn dry_run_lists_untracked_and_ignored_files() {
+    let (home, repo) = setup();
+    let wt_path = wt_new(home.path(), &repo, "feat-clean");
+    setup_artifacts(&wt_path);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["clean", "feat-clean", "--repo"]).arg(&repo);
+    });
+
+    assert_exit_code(&output, 0);
+    assert_stdout_empty(&output);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Would remove scratch.txt"),
+        "should list untracked file, got: {stderr}",
+    );
+    assert!(
+        stderr.contains("Would remove target/"),
+        "should list ignored directory, got: {stderr}",
+    );
+    assert!(
+        stderr.contains("use --force"),
+        "should hint at --force, got: {stderr}",
+    );
+    assert!(wt_path.join("scratch.txt").exists());
+    assert!(wt_path.join("target").join("out.o").exists());
+}
+
+#[test]
+fn force_removes_untracked_and_ignored_files() {
+    let (home, repo) = setup();
+    let wt_path = wt_new(home.path(), &repo, "feat-clean");
+    setup_artifacts(&wt_path);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["clean", "feat-clean", "--force", "--repo"])
+            .arg(&repo);
+    });
+
+    assert_exit_code(&output, 0);
+    assert_stdout_empty(&output);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Removing scratch.txt"),
+        "should report removed file, got: {stderr}",
+    );
+    assert!(!wt_path.join("scratch.txt").exists());
+    assert!(!wt_path.join("target").exists());
+    assert!(wt_path.join(".gitignore").exists());
+}
+
+#[test]
+fn reports_nothing_to_clean() {
+    let (home, repo) = setup();
+    wt_new(home.path(), &repo, "feat-clean");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["clean", "feat-clean", "--repo"]).arg(&repo);
+    });
+
+    assert_exit_code(&output, 0);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.starts_with("nothing to clean"),
+        "should report nothing to clean, got: {stderr}",
+    );
+}
+
+#[test]
+fn refuses_primary_worktree() {
+    let (home, repo) = setup();
+    std::fs::write(repo.join("scratch.txt"), "notes").unwrap();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["clean", "main", "--force", "--repo"]).arg(&repo);
+    });
+
+    assert_exit_code(&output, 1);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("cannot clean the primary worktree"),
+        "expected primary-worktree error, got: {stderr}",
+    );
+    assert!(repo.join("scratch.txt").exists());
+}
+
+#[test]
+fn force_refuses_when_current_directory_is_inside_worktree() {
+    let (home, repo) = setup();
+    let wt_path = wt_new(home.path(), &repo, "feat-clean");
+    setup_artifacts(&wt_path);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["clean", "feat-clean", "--force", "--repo"])
+            .arg(&repo)
+            .current_dir(wt_path.join("target"));
+    });
+
+    assert_exit_code(&output, 1);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("current directory is inside the worktree"),
+        "expected cwd guard error, got: {stderr}",
+    );
+    assert!(wt_path.join("target").join("out.o").exists());
+}
+
+#[test]
+fn errors_when_branch_has_no_worktree() {
+    let (home, repo) = setup();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["clean", "missing", "--repo"]).arg(&repo);
+    });
+
+    assert_error(&output, 1, "no worktree found for: missing\n");
+}
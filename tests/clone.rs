@@ -51,6 +51,25 @@ fn find_repo_under(base: &Path) -> std::path::PathBuf {
     children[0].clone()
 }
 
+#[test]
+fn clone_checks_worktree_root_before_cloning() {
+    let (home, origin) = setup_origin();
+    let root = file_as_wt_root(home.path());
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["clone"]).arg(&origin);
+    });
+    assert_error(
+        &output,
+        1,
+        &format!(
+            "cannot write to worktree root {}: not a directory\n",
+            canonical(&root).display()
+        ),
+    );
+    assert!(find_subdirs(&repos_dir(home.path())).is_empty());
+}
+
 #[test]
 fn clone_creates_worktree_and_bare_repo() {
     let (home, origin) = setup_origin();
@@ -403,3 +422,145 @@ fn clone_empty_repo_fails_and_cleans_up() {
         "no worktree dirs should exist"
     );
 }
+
+#[test]
+fn clone_with_reference_borrows_objects() {
+    let (home, origin) = setup_origin();
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["clone"])
+            .arg(&origin)
+            .arg("--reference")
+            .arg(&origin);
+    });
+    assert!(
+        output.status.success(),
+        "wt clone --reference failed: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    let wt_path = parse_wt_new_path(&output);
+
+    let bare_repo = find_repo_under(&repos_dir(home.path()));
+    let alternates = bare_repo.join("objects").join("info").join("alternates");
+    let content = std::fs::read_to_string(&alternates).unwrap();
+    assert!(
+        content.contains(origin.to_string_lossy().as_ref()),
+        "alternates should point at the reference repo, got: {content}",
+    );
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["list", "--repo"]).arg(&wt_path);
+    });
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("main"), "list should show main branch");
+}
+
+#[test]
+fn clone_with_dissociate_drops_alternates() {
+    let (home, origin) = setup_origin();
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["clone"])
+            .arg(&origin)
+            .arg("--reference")
+            .arg(&origin)
+            .arg("--dissociate");
+    });
+    assert!(
+        output.status.success(),
+        "wt clone --dissociate failed: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    let wt_path = parse_wt_new_path(&output);
+
+    let bare_repo = find_repo_under(&repos_dir(home.path()));
+    let alternates = bare_repo.join("objects").join("info").join("alternates");
+    assert!(
+        !alternates.exists(),
+        "dissociated clone should not keep alternates"
+    );
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["list", "--repo"]).arg(&wt_path);
+    });
+    assert!(output.status.success());
+}
+
+#[test]
+fn clone_dissociate_requires_reference() {
+    let (home, origin) = setup_origin();
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["clone"]).arg(&origin).arg("--dissociate");
+    });
+    assert_exit_code(&output, 2);
+    assert!(!repos_dir(home.path()).exists());
+}
+
+#[test]
+fn clone_uses_given_name_for_directory() {
+    let (home, origin) = setup_origin();
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["clone"]).arg(&origin).arg("my-fork");
+    });
+    assert_exit_code(&output, 0);
+    let wt_path = parse_wt_new_path(&output);
+    assert_eq!(wt_path.file_name().unwrap(), "my-fork");
+    let bare_repo = find_repo_under(&repos_dir(home.path()));
+    assert_eq!(bare_repo.file_name().unwrap(), "my-fork");
+}
+
+#[test]
+fn clone_rejects_name_with_leading_dash() {
+    let (home, origin) = setup_origin();
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["clone"]).arg(&origin).args(["--", "-fork"]);
+    });
+    assert_error(
+        &output,
+        1,
+        "invalid clone name '-fork': cannot start with '-'\n",
+    );
+    assert!(find_subdirs(&repos_dir(home.path())).is_empty());
+}
+
+#[test]
+fn clone_refuses_existing_destination() {
+    let (home, origin) = setup_origin();
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["clone"]).arg(&origin);
+    });
+    assert_exit_code(&output, 0);
+    let bare_repo = find_repo_under(&repos_dir(home.path()));
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["clone"]).arg(&origin);
+    });
+    assert_error(
+        &output,
+        1,
+        &format!(
+            "'origin' is already cloned at ~/.wt/repos/{}/origin; pass another name to clone it again\n",
+            bare_repo
+                .parent()
+                .unwrap()
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+        ),
+    );
+    assert_eq!(find_subdirs(&repos_dir(home.path())).len(), 1);
+    assert_eq!(find_subdirs(&worktrees_dir(home.path())).len(), 1);
+}
+
+#[test]
+fn clone_rejects_name_with_slash() {
+    let (home, origin) = setup_origin();
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["clone"]).arg(&origin).arg("a/b");
+    });
+    assert_error(
+        &output,
+        1,
+        "invalid clone name 'a/b': must be a single directory name\n",
+    );
+    assert!(!repos_dir(home.path()).exists());
+}
@@ -11,7 +11,9 @@ pub fn wt_bin() -> Command {
 
 pub fn wt(home: &Path) -> Command {
     let mut cmd = wt_bin();
-    cmd.env("HOME", home);
+    cmd.env("HOME", home)
+        .env_remove("XDG_STATE_HOME")
+        .env_remove("WT_ROOT");
     cmd
 }
 
@@ -241,3 +243,81 @@ pub fn assert_error(output: &Output, code: i32, expected_stderr: &str) {
     assert_stdout_empty(output);
     assert_stderr_exact(output, expected_stderr);
 }
+
+// Mirrors wt's lockfile naming: the percent-encoded git common dir.
+pub fn lock_path(home: &Path, repo: &Path) -> PathBuf {
+    let common_dir = repo.join(".git").canonicalize().unwrap();
+    let mut name = String::new();
+    for byte in common_dir.to_string_lossy().bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.') {
+            name.push(byte as char);
+        } else {
+            name.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    home.join(".local/state/wt/locks")
+        .join(format!("{name}.lock"))
+}
+
+pub fn write_lock(home: &Path, repo: &Path, pid: u32, command: &str) -> PathBuf {
+    let path = lock_path(home, repo);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(&path, format!("{pid}\n{command}\n{}\n", repo.display())).unwrap();
+    path
+}
+
+// Makes ~/.wt a symlink to a directory elsewhere (say, another volume) and
+// returns that directory, resolved.
+#[cfg(unix)]
+pub fn symlink_wt_home(home: &Path) -> PathBuf {
+    let target = home.join("volume").join("wt");
+    std::fs::create_dir_all(&target).unwrap();
+    std::os::unix::fs::symlink(&target, home.join(".wt")).unwrap();
+    canonical(&target)
+}
+
+// Makes the worktree root read-only and returns it. Permission bits don't
+// bind root, so this says so and returns `None` for the caller to skip;
+// `file_as_wt_root` covers the same checks for every user.
+#[cfg(unix)]
+pub fn read_only_wt_root(home: &Path) -> Option<PathBuf> {
+    use std::os::unix::fs::PermissionsExt;
+
+    if unsafe { libc::geteuid() } == 0 {
+        eprintln!("skipped: permission bits don't bind root");
+        return None;
+    }
+    let root = home.join(".wt").join("worktrees");
+    std::fs::create_dir_all(&root).unwrap();
+    std::fs::set_permissions(&root, std::fs::Permissions::from_mode(0o555)).unwrap();
+    Some(root)
+}
+
+// Puts a file where the worktree root should be, which no user can create
+// directories under, and returns its path.
+pub fn file_as_wt_root(home: &Path) -> PathBuf {
+    let root = home.join(".wt").join("worktrees");
+    std::fs::create_dir_all(root.parent().unwrap()).unwrap();
+    std::fs::write(&root, "").unwrap();
+    root
+}
+
+// A pseudo-terminal pair; the leader must outlive the child writing to the
+// follower.
+#[cfg(unix)]
+pub fn pty() -> (std::os::fd::OwnedFd, std::os::fd::OwnedFd) {
+    use std::os::fd::{FromRawFd, OwnedFd};
+
+    let (mut leader, mut follower) = (0, 0);
+    let rc = unsafe {
+        libc::openpty(
+            &mut leader,
+            &mut follower,
+            std::ptr::null_mut(),
+            std::ptr::null(),
+            std::ptr::null(),
+        )
+    };
+    assert_eq!(rc, 0, "openpty failed");
+    unsafe { (OwnedFd::from_raw_fd(leader), OwnedFd::from_raw_fd(follower)) }
+}
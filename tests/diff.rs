@@ -0,0 +1,144 @@
+pub mod common;
+
+use common::*;
+
+fn commit_file(wt_path: &std::path::Path, file: &str) {
+    std::fs::write(wt_path.join(file), "work").unwrap();
+    assert_git_success(wt_path, &["add", file]);
+    assert_git_success(wt_path, &["commit", "-m", "add file"]);
+}
+
+#[test]
+fn shows_changed_files_against_default_branch() {
+    let (home, repo, _origin) = setup_with_origin();
+    let wt_path = wt_new(home.path(), &repo, "feat/x");
+    commit_file(&wt_path, "feature.txt");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["diff", "feat/x", "--repo"]).arg(&repo);
+    });
+
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("feature.txt") && stdout.contains("1 file changed"),
+        "should show diff stat, got: {stdout}",
+    );
+}
+
+#[test]
+fn base_flag_overrides_default_branch() {
+    let (home, repo) = setup();
+    assert_git_success(&repo, &["branch", "develop"]);
+    let wt_path = wt_new(home.path(), &repo, "feat/y");
+    commit_file(&wt_path, "other.txt");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["diff", "feat/y", "--base", "develop", "--repo"])
+            .arg(&repo);
+    });
+
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("other.txt"),
+        "should diff against --base, got: {stdout}",
+    );
+}
+
+#[test]
+fn reports_no_changes() {
+    let (home, repo) = setup();
+    wt_new(home.path(), &repo, "feat/empty");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["diff", "feat/empty", "--base", "main", "--repo"])
+            .arg(&repo);
+    });
+
+    assert_exit_code(&output, 0);
+    assert_stdout_empty(&output);
+    assert_stderr_exact(&output, "no changes against main\n");
+}
+
+#[test]
+fn errors_without_base_when_default_branch_is_unknown() {
+    let (home, repo) = setup();
+    wt_new(home.path(), &repo, "feat/nobase");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["diff", "feat/nobase", "--repo"]).arg(&repo);
+    });
+
+    assert_exit_code(&output, 1);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("cannot determine default branch") && stderr.contains("use --base"),
+        "expected base hint, got: {stderr}",
+    );
+}
+
+#[test]
+fn errors_when_base_is_missing() {
+    let (home, repo) = setup();
+    wt_new(home.path(), &repo, "feat/z");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["diff", "feat/z", "--base", "nope", "--repo"])
+            .arg(&repo);
+    });
+
+    assert_error(&output, 1, "base branch 'nope' not found\n");
+}
+
+#[test]
+fn wt_config_file_sets_base_branch() {
+    let (home, repo) = setup();
+    assert_git_success(&repo, &["branch", "develop"]);
+    let wt_path = wt_new(home.path(), &repo, "feat/cfg");
+    commit_file(&wt_path, "configured.txt");
+    let config = home.path().join("wt-gitconfig");
+    std::fs::write(&config, "[wt]\n\tbaseBranch = develop\n").unwrap();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["diff", "feat/cfg", "--repo"])
+            .arg(&repo)
+            .env("WT_CONFIG", &config);
+    });
+
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("configured.txt"),
+        "should diff against wt.baseBranch, got: {stdout}",
+    );
+}
+
+#[test]
+fn wt_config_file_replaces_repo_git_config() {
+    let (home, repo) = setup();
+    assert_git_success(&repo, &["branch", "develop"]);
+    assert_git_success(&repo, &["config", "wt.baseBranch", "missing"]);
+    let wt_path = wt_new(home.path(), &repo, "feat/iso");
+    commit_file(&wt_path, "isolated.txt");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["diff", "feat/iso", "--repo"]).arg(&repo);
+    });
+    assert_exit_code(&output, 1);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("configured base branch 'missing' not found"),
+        "repo git config should be read without WT_CONFIG, got: {stderr}",
+    );
+
+    let config = home.path().join("wt-gitconfig");
+    std::fs::write(&config, "[wt]\n\tbaseBranch = develop\n").unwrap();
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["diff", "feat/iso", "--repo"])
+            .arg(&repo)
+            .env("WT_CONFIG", &config);
+    });
+    assert_exit_code(&output, 0);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("isolated.txt"));
+}
@@ -0,0 +1,88 @@
+use std::path::{Path, PathBuf};
+
+pub mod common;
+
+use common::*;
+
+// Points `dup`'s directory at `target`'s, so git tracks two worktrees that
+// are one directory on disk, as a case-insensitive filesystem could.
+#[cfg(unix)]
+fn alias_worktree(target: &Path, dup: &Path) {
+    std::fs::remove_dir_all(dup).unwrap();
+    std::os::unix::fs::symlink(target, dup).unwrap();
+}
+
+#[cfg(unix)]
+fn setup_collision() -> (tempfile::TempDir, PathBuf, PathBuf, PathBuf) {
+    let (home, repo) = setup();
+    let a = wt_new(home.path(), &repo, "feat/a");
+    let b = wt_new(home.path(), &repo, "feat/b");
+    alias_worktree(&a, &b);
+    (home, repo, a, b)
+}
+
+#[test]
+fn reports_no_problems_for_healthy_repo() {
+    let (home, repo) = setup();
+    wt_new(home.path(), &repo, "feat/a");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["doctor", "--repo"]).arg(&repo);
+    });
+    assert_exit_code(&output, 0);
+    assert_stdout_empty(&output);
+    assert_stderr_exact(&output, "no problems found\n");
+}
+
+#[cfg(unix)]
+#[test]
+fn reports_worktrees_sharing_a_directory() {
+    let (home, repo, a, b) = setup_collision();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["doctor", "--repo"]).arg(&repo);
+    });
+    assert_exit_code(&output, 1);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let line = stdout.lines().next().unwrap_or_default();
+    assert!(line.starts_with("same-directory\t"), "got: {stdout}");
+    let tilde = |p: &Path| format!("~/{}", p.strip_prefix(home.path()).unwrap().display());
+    assert!(line.contains(&tilde(&a)), "got: {stdout}");
+    assert!(line.contains(&tilde(&b)), "got: {stdout}");
+    assert_stderr_exact(&output, "found 1 problem\n");
+}
+
+#[cfg(unix)]
+#[test]
+fn reports_unwritable_worktree_root() {
+    let (home, repo) = setup();
+    if read_only_wt_root(home.path()).is_none() {
+        return;
+    }
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["doctor", "--repo"]).arg(&repo);
+    });
+    assert_exit_code(&output, 1);
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "unwritable-root\t~/.wt/worktrees\n"
+    );
+    assert_stderr_exact(&output, "found 1 problem\n");
+}
+
+#[test]
+fn reports_worktree_root_that_is_a_file() {
+    let (home, repo) = setup();
+    file_as_wt_root(home.path());
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["doctor", "--repo"]).arg(&repo);
+    });
+    assert_exit_code(&output, 1);
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "unwritable-root\t~/.wt/worktrees\n"
+    );
+    assert_stderr_exact(&output, "found 1 problem\n");
+}
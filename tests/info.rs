@@ -0,0 +1,61 @@
+use serde_json::Value;
+
+pub mod common;
+
+use common::*;
+
+#[test]
+fn reports_branch_head_and_upstream() {
+    let (home, repo, _origin) = setup_with_origin();
+    let wt_path = wt_new(home.path(), &repo, "feat/x");
+    std::fs::write(wt_path.join("x.txt"), "x").unwrap();
+    assert_git_success(&wt_path, &["add", "x.txt"]);
+    assert_git_success(&wt_path, &["commit", "-m", "add x"]);
+    assert_git_success(&wt_path, &["push", "-u", "origin", "feat/x"]);
+    std::fs::write(wt_path.join("y.txt"), "y").unwrap();
+    assert_git_success(&wt_path, &["add", "y.txt"]);
+    assert_git_success(&wt_path, &["commit", "-m", "add y"]);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["info", "feat/x", "--json", "--repo"]).arg(&repo);
+    });
+    assert_exit_code(&output, 0);
+    let info: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(info["branch"], "feat/x");
+    assert_eq!(info["path"].as_str(), wt_path.to_str());
+    let head = info["head"].as_str().unwrap();
+    assert_eq!(head.len(), 40);
+    assert!(head.chars().any(|c| c != '0'), "got: {head}");
+    assert!(info["summary"].as_str().unwrap().ends_with(" add y"));
+    assert_eq!(info["upstream"], "origin/feat/x");
+    assert_eq!(info["ahead"], 1);
+    assert_eq!(info["behind"], 0);
+    assert_eq!(info["dirty"], false);
+    assert_eq!(info["base"], "origin/main");
+    assert!(info["created"].as_u64().is_some());
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["info", "feat/x", "--repo"]).arg(&repo);
+    });
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("branch     feat/x\n"), "got: {stdout}");
+    assert!(
+        stdout.contains("upstream   origin/feat/x (ahead 1, behind 0)\n"),
+        "got: {stdout}"
+    );
+}
+
+#[test]
+fn missing_worktree_fails_like_path() {
+    let (home, repo) = setup();
+
+    let path = run_wt(home.path(), |cmd| {
+        cmd.args(["path", "nope", "--repo"]).arg(&repo);
+    });
+    let info = run_wt(home.path(), |cmd| {
+        cmd.args(["info", "nope", "--repo"]).arg(&repo);
+    });
+    assert_exit_code(&path, 1);
+    assert_error(&info, 1, &String::from_utf8_lossy(&path.stderr));
+}
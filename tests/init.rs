@@ -110,8 +110,122 @@ fn fish_init_includes_wrapper() {
     assert!(stdout.contains("and cd -- $out"));
 }
 
+#[test]
+fn write_rc_appends_block_once() {
+    let home = tempfile::TempDir::new().unwrap();
+    let rc = home.path().join(".bashrc");
+    std::fs::write(&rc, "export EDITOR=vi").unwrap();
+
+    let output = wt(home.path())
+        .args(["init", "bash", "--write-rc"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_stdout_empty(&output);
+    assert_stderr_exact(
+        &output,
+        "added wt shell integration to ~/.bashrc, restart your shell to use it\n",
+    );
+    let first = std::fs::read_to_string(&rc).unwrap();
+    assert_eq!(
+        first,
+        "export EDITOR=vi\n\n# >>> wt shell integration >>>\neval \"$(wt init bash)\"\n# <<< wt shell integration <<<\n"
+    );
+
+    let output = wt(home.path())
+        .args(["init", "bash", "--write-rc"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_stderr_exact(&output, "already set up in ~/.bashrc\n");
+    assert_eq!(std::fs::read_to_string(&rc).unwrap(), first);
+}
+
+#[test]
+fn write_rc_creates_zshrc_in_zdotdir() {
+    let home = tempfile::TempDir::new().unwrap();
+    let zdotdir = home.path().join("zsh");
+    std::fs::create_dir(&zdotdir).unwrap();
+
+    let output = wt(home.path())
+        .env("ZDOTDIR", &zdotdir)
+        .args(["init", "zsh", "--write-rc"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let rc = std::fs::read_to_string(zdotdir.join(".zshrc")).unwrap();
+    assert_eq!(rc.matches("eval \"$(wt init zsh)\"").count(), 1);
+    assert!(!home.path().join(".zshrc").exists());
+}
+
+#[test]
+fn write_rc_leaves_fish_config_alone() {
+    let home = tempfile::TempDir::new().unwrap();
+    let output = wt(home.path())
+        .args(["init", "fish", "--write-rc"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_stdout_empty(&output);
+    assert!(!home.path().join(".config").exists());
+}
+
 #[test]
 fn completions_subcommand_is_removed() {
     let output = wt_bin().args(["completions", "zsh"]).output().unwrap();
     assert_exit_code(&output, 2);
 }
+
+#[test]
+fn complete_prints_branch_path_and_flags() {
+    let (home, repo) = setup();
+    let wt_path = wt_new(home.path(), &repo, "feat/x");
+    let lock = ["worktree", "lock", wt_path.to_str().unwrap()];
+    assert_git_success(&repo, &lock);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["complete", "--repo"]).arg(&repo);
+    });
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(
+        lines,
+        [
+            "main\t~/repo\tmain",
+            &format!(
+                "feat/x\t~/{}\tlocked",
+                wt_path.strip_prefix(home.path()).unwrap().display()
+            )
+        ]
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn bash_completion_offers_worktree_branches() {
+    let (home, repo) = setup();
+    wt_new(home.path(), &repo, "feat/x");
+    assert_git_success(&repo, &["branch", "feat/no-worktree"]);
+
+    let bin_dir = std::path::Path::new(env!("CARGO_BIN_EXE_wt"))
+        .parent()
+        .unwrap();
+    let path = format!("{}:{}", bin_dir.display(), std::env::var("PATH").unwrap());
+    let output = std::process::Command::new("bash")
+        .args([
+            "-c",
+            r#"eval "$(wt init bash)"; COMP_WORDS=(wt switch feat/); COMP_CWORD=2; _wt_dynamic; printf '%s\n' "${COMPREPLY[@]}""#,
+        ])
+        .current_dir(&repo)
+        .env("HOME", home.path())
+        .env("PATH", path)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "bash failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "feat/x\n");
+}
@@ -27,6 +27,17 @@ fn link_force(home: &Path, repo: &Path, files: &[&str]) -> std::process::Output
     })
 }
 
+fn link_copy(home: &Path, repo: &Path, files: &[&str], force: bool) -> std::process::Output {
+    run_wt(home, |cmd| {
+        cmd.args(["link", "--copy"]);
+        if force {
+            cmd.arg("--force");
+        }
+        cmd.args(files);
+        cmd.args(["--repo"]).arg(repo);
+    })
+}
+
 fn create_symlink(source: &Path, dest: &Path) {
     #[cfg(unix)]
     {
@@ -404,6 +415,157 @@ fn force_skips_correct_symlink() {
     );
 }
 
+#[test]
+fn copy_writes_regular_file() {
+    let (home, repo) = setup();
+    std::fs::write(repo.join(".env"), "SECRET=abc").unwrap();
+    let wt_path = wt_new(home.path(), &repo, "feat-copy");
+
+    let output = link_copy(home.path(), &repo, &[".env"], false);
+    assert!(
+        output.status.success(),
+        "wt link --copy failed: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("copied .env"), "got: {stderr}");
+
+    let copy = wt_path.join(".env");
+    assert!(copy.symlink_metadata().unwrap().file_type().is_file());
+    assert_eq!(std::fs::read_to_string(&copy).unwrap(), "SECRET=abc");
+}
+
+#[test]
+fn copy_idempotent_skip() {
+    let (home, repo) = setup();
+    std::fs::write(repo.join(".env"), "SECRET=abc").unwrap();
+    wt_new(home.path(), &repo, "feat-copy-idem");
+
+    let out1 = link_copy(home.path(), &repo, &[".env"], false);
+    assert!(out1.status.success());
+
+    let out2 = link_copy(home.path(), &repo, &[".env"], false);
+    assert!(out2.status.success());
+    let err2 = String::from_utf8_lossy(&out2.stderr);
+    assert!(
+        !err2.contains("copied .env"),
+        "second run should not re-copy, got: {err2}",
+    );
+    assert!(
+        !err2.contains("skipped"),
+        "identical copy should not warn, got: {err2}",
+    );
+}
+
+#[test]
+fn copy_warns_when_contents_differ() {
+    let (home, repo) = setup();
+    std::fs::write(repo.join(".env"), "SECRET=abc").unwrap();
+    let wt_path = wt_new(home.path(), &repo, "feat-copy-conflict");
+    std::fs::write(wt_path.join(".env"), "LOCAL=xyz").unwrap();
+
+    let output = link_copy(home.path(), &repo, &[".env"], false);
+    assert!(output.status.success(), "should still exit 0");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("skipped .env") && stderr.contains("already exists"),
+        "expected skip warning, got: {stderr}",
+    );
+    assert_eq!(
+        std::fs::read_to_string(wt_path.join(".env")).unwrap(),
+        "LOCAL=xyz",
+    );
+}
+
+#[test]
+fn copy_does_not_accept_correct_symlink() {
+    let (home, repo) = setup();
+    std::fs::write(repo.join(".env"), "SECRET=abc").unwrap();
+    let wt_path = wt_new(home.path(), &repo, "feat-copy-link");
+    assert!(wt_link(home.path(), &repo, &[".env"]).status.success());
+
+    let output = link_copy(home.path(), &repo, &[".env"], false);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("skipped .env"), "got: {stderr}");
+
+    let output = link_copy(home.path(), &repo, &[".env"], true);
+    assert!(output.status.success());
+    let copy = wt_path.join(".env");
+    assert!(copy.symlink_metadata().unwrap().file_type().is_file());
+    assert_eq!(std::fs::read_to_string(&copy).unwrap(), "SECRET=abc");
+}
+
+#[test]
+fn copy_force_refreshes_stale_copy() {
+    let (home, repo) = setup();
+    std::fs::write(repo.join(".env"), "SECRET=abc").unwrap();
+    let wt_path = wt_new(home.path(), &repo, "feat-copy-force");
+    assert!(
+        link_copy(home.path(), &repo, &[".env"], false)
+            .status
+            .success()
+    );
+
+    std::fs::write(repo.join(".env"), "SECRET=def").unwrap();
+    assert_eq!(
+        std::fs::read_to_string(wt_path.join(".env")).unwrap(),
+        "SECRET=abc",
+        "copy should not track the primary",
+    );
+
+    let output = link_copy(home.path(), &repo, &[".env"], true);
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("copied .env"), "got: {stderr}");
+    assert_eq!(
+        std::fs::read_to_string(wt_path.join(".env")).unwrap(),
+        "SECRET=def",
+    );
+}
+
+#[test]
+fn copy_directory_recursively() {
+    let (home, repo) = setup();
+    std::fs::create_dir_all(repo.join("config/nested")).unwrap();
+    std::fs::write(repo.join("config/a.json"), "{}").unwrap();
+    std::fs::write(repo.join("config/nested/b.json"), "[]").unwrap();
+    let wt_path = wt_new(home.path(), &repo, "feat-copy-dir");
+
+    assert!(
+        link_copy(home.path(), &repo, &["config"], false)
+            .status
+            .success()
+    );
+    assert_eq!(
+        std::fs::read_to_string(wt_path.join("config/nested/b.json")).unwrap(),
+        "[]",
+    );
+
+    let output = link_copy(home.path(), &repo, &["config"], false);
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        !stderr.contains("copied") && !stderr.contains("skipped"),
+        "identical directory copy should be left alone, got: {stderr}",
+    );
+}
+
+#[test]
+fn copy_is_not_recorded_as_link() {
+    let (home, repo) = setup();
+    std::fs::write(repo.join(".env"), "SECRET=abc").unwrap();
+    wt_new(home.path(), &repo, "feat-copy-config");
+
+    assert!(
+        link_copy(home.path(), &repo, &[".env"], false)
+            .status
+            .success()
+    );
+
+    let output = wt_link_list(home.path(), &repo);
+    assert_stdout_empty(&output);
+}
+
 #[test]
 fn link_persists_to_config() {
     let (home, repo) = setup();
@@ -446,6 +608,20 @@ fn auto_links_on_new_worktree_creation() {
     assert_eq!(std::fs::read_to_string(&link).unwrap(), "SECRET=abc");
 }
 
+#[test]
+fn auto_links_files_from_repo_config() {
+    let (home, repo) = setup();
+    std::fs::write(repo.join(".env"), "SECRET=abc").unwrap();
+    std::fs::write(repo.join(".wt.toml"), "link = [\".env\"]\n").unwrap();
+
+    let wt_path = wt_new(home.path(), &repo, "feat-repo-config");
+
+    assert_eq!(
+        std::fs::read_link(wt_path.join(".env")).unwrap(),
+        repo.join(".env")
+    );
+}
+
 #[test]
 fn auto_links_on_switch_creation() {
     let (home, repo) = setup();
@@ -542,3 +718,257 @@ fn auto_link_skips_dotdot_path() {
 fn auto_link_skips_absolute_path() {
     assert_auto_link_skips_invalid_path("/etc/passwd", "feat-abs-auto", "path must be relative");
 }
+
+fn wt_link_check(home: &Path, repo: &Path) -> std::process::Output {
+    run_wt(home, |cmd| {
+        cmd.args(["link", "--check", "--repo"]).arg(repo);
+    })
+}
+
+#[test]
+fn check_passes_when_links_are_healthy() {
+    let (home, repo) = setup();
+    std::fs::write(repo.join(".env"), "SECRET=1").unwrap();
+    wt_new(home.path(), &repo, "feat/healthy");
+    assert!(wt_link(home.path(), &repo, &[".env"]).status.success());
+
+    let output = wt_link_check(home.path(), &repo);
+    assert!(output.status.success());
+    assert_stdout_empty(&output);
+}
+
+#[test]
+fn check_reports_dangling_link() {
+    let (home, repo) = setup();
+    std::fs::write(repo.join(".env"), "SECRET=1").unwrap();
+    let wt_path = wt_new(home.path(), &repo, "feat/dangling");
+    assert!(wt_link(home.path(), &repo, &[".env"]).status.success());
+    std::fs::remove_file(repo.join(".env")).unwrap();
+
+    let output = wt_link_check(home.path(), &repo);
+    assert!(
+        !output.status.success(),
+        "dangling link should fail the check"
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.starts_with("dangling\t.env\t"),
+        "expected a dangling report, got: {stdout}"
+    );
+    assert_eq!(stdout.lines().count(), 1, "got: {stdout}");
+    assert!(
+        wt_path.join(".env").symlink_metadata().is_ok(),
+        "check must not touch the link"
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("found 1 link problem"), "got: {stderr}");
+}
+
+#[test]
+fn check_distinguishes_wrong_target() {
+    let (home, repo) = setup();
+    std::fs::write(repo.join(".env"), "SECRET=1").unwrap();
+    let wt_path = wt_new(home.path(), &repo, "feat/wrong");
+    assert!(wt_link(home.path(), &repo, &[".env"]).status.success());
+    std::fs::remove_file(wt_path.join(".env")).unwrap();
+    let elsewhere = home.path().join("elsewhere.env");
+    std::fs::write(&elsewhere, "OTHER=1").unwrap();
+    create_symlink(&elsewhere, &wt_path.join(".env"));
+
+    let output = wt_link_check(home.path(), &repo);
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("wrong-target\t.env\t"), "got: {stdout}");
+}
+
+#[cfg(unix)]
+#[test]
+fn link_target_is_absolute_when_wt_home_is_symlink() {
+    let (home, repo) = setup();
+    symlink_wt_home(home.path());
+    std::fs::write(repo.join(".env"), "SECRET=abc").unwrap();
+    let wt_path = wt_new(home.path(), &repo, "via-symlink");
+
+    let output = wt_link(home.path(), &repo, &[".env"]);
+    assert!(
+        output.status.success(),
+        "wt link failed: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+
+    let target = std::fs::read_link(wt_path.join(".env")).unwrap();
+    assert_eq!(target, canonical(&repo).join(".env"));
+    assert_eq!(
+        std::fs::read_to_string(wt_path.join(".env")).unwrap(),
+        "SECRET=abc"
+    );
+
+    let check = wt_link_check(home.path(), &repo);
+    assert!(
+        check.status.success(),
+        "link --check should find nothing wrong: {}",
+        String::from_utf8_lossy(&check.stdout),
+    );
+}
+
+fn link_all(home: &Path, repo: &Path) -> std::process::Output {
+    run_wt(home, |cmd| {
+        cmd.args(["link", "--all", "--repo"]).arg(repo);
+    })
+}
+
+#[test]
+fn all_links_manifest_entries_and_skips_bad_ones() {
+    let (home, repo) = setup();
+    std::fs::write(repo.join(".env"), "SECRET=abc").unwrap();
+    std::fs::create_dir(repo.join("config")).unwrap();
+    std::fs::write(repo.join("config/local.json"), "{}").unwrap();
+    std::fs::write(
+        repo.join(".wtlink"),
+        "# shared files\n.env\n\n  config/local.json  \nmissing.txt\n../outside\n",
+    )
+    .unwrap();
+    let wt_a = wt_new(home.path(), &repo, "feat-all-a");
+    let wt_b = wt_new(home.path(), &repo, "feat-all-b");
+
+    let output = link_all(home.path(), &repo);
+    assert!(
+        output.status.success(),
+        "wt link --all failed: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("skipped missing.txt: not found in primary worktree"),
+        "got: {stderr}"
+    );
+    assert!(
+        stderr.contains("skipped ../outside: path must not contain '..'"),
+        "got: {stderr}"
+    );
+    assert!(stderr.ends_with("linked 4, skipped 2\n"), "got: {stderr}");
+
+    for wt in [&wt_a, &wt_b] {
+        for file in [".env", "config/local.json"] {
+            let dest = wt.join(file);
+            assert!(
+                dest.symlink_metadata().unwrap().file_type().is_symlink(),
+                "{} should be a symlink",
+                dest.display()
+            );
+        }
+    }
+
+    let output = link_all(home.path(), &repo);
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.ends_with("linked 0, skipped 2\n"), "got: {stderr}");
+
+    let output = wt_link_list(home.path(), &repo);
+    assert_stdout_empty(&output);
+}
+
+#[test]
+fn all_counts_conflicts_as_skipped() {
+    let (home, repo) = setup();
+    std::fs::write(repo.join(".env"), "SECRET=abc").unwrap();
+    std::fs::write(repo.join(".wtlink"), ".env\n").unwrap();
+    let wt_path = wt_new(home.path(), &repo, "feat-all-conflict");
+    std::fs::write(wt_path.join(".env"), "LOCAL=xyz").unwrap();
+
+    let output = link_all(home.path(), &repo);
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("skipped .env"), "got: {stderr}");
+    assert!(stderr.ends_with("linked 0, skipped 1\n"), "got: {stderr}");
+}
+
+#[test]
+fn all_without_manifest_errors() {
+    let (home, repo) = setup();
+    wt_new(home.path(), &repo, "feat-all-none");
+
+    let output = link_all(home.path(), &repo);
+    assert_exit_code(&output, 1);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.starts_with("no .wtlink in "), "got: {stderr}");
+}
+
+#[test]
+fn repair_fixes_drifted_links_and_leaves_correct_ones() {
+    let (home, repo) = setup();
+    std::fs::write(repo.join(".env"), "SECRET=abc").unwrap();
+    std::fs::write(repo.join(".tool"), "TOOL").unwrap();
+    std::fs::write(repo.join(".other"), "OTHER").unwrap();
+    let wt_a = wt_new(home.path(), &repo, "feat-repair-a");
+    let wt_b = wt_new(home.path(), &repo, "feat-repair-b");
+    assert!(
+        wt_link(home.path(), &repo, &[".env", ".tool"])
+            .status
+            .success()
+    );
+
+    // a: .env correct, .tool pointing elsewhere; b: .env missing, .tool a copy.
+    std::fs::remove_file(wt_a.join(".tool")).unwrap();
+    create_symlink(&repo.join(".other"), &wt_a.join(".tool"));
+    std::fs::remove_file(wt_b.join(".env")).unwrap();
+    std::fs::remove_file(wt_b.join(".tool")).unwrap();
+    std::fs::write(wt_b.join(".tool"), "LOCAL").unwrap();
+    let correct_before = wt_a
+        .join(".env")
+        .symlink_metadata()
+        .unwrap()
+        .modified()
+        .unwrap();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["link", "--repair", "--repo"]).arg(&repo);
+    });
+    assert!(
+        output.status.success(),
+        "wt link --repair failed: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        !stderr.contains(&format!("repaired .env ({})", wt_a.display())),
+        "correct link should be left alone, got: {stderr}"
+    );
+    assert!(stderr.ends_with("repaired 3, skipped 0\n"), "got: {stderr}");
+    assert_eq!(
+        wt_a.join(".env")
+            .symlink_metadata()
+            .unwrap()
+            .modified()
+            .unwrap(),
+        correct_before
+    );
+
+    for wt in [&wt_a, &wt_b] {
+        for file in [".env", ".tool"] {
+            let dest = wt.join(file);
+            assert_eq!(
+                std::fs::read_link(&dest).unwrap(),
+                canonical(&repo).join(file)
+            );
+        }
+    }
+
+    let check = wt_link_check(home.path(), &repo);
+    assert!(
+        check.status.success(),
+        "links should be healthy after repair"
+    );
+}
+
+#[test]
+fn repair_without_configured_links() {
+    let (home, repo) = setup();
+    wt_new(home.path(), &repo, "feat-repair-none");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["link", "--repair", "--repo"]).arg(&repo);
+    });
+    assert!(output.status.success());
+    assert_stderr_exact(&output, "no links configured\n");
+}
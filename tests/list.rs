@@ -71,6 +71,16 @@ fn json_output_includes_all_fields() {
     assert_eq!(entry["current"].as_bool(), Some(false));
     assert!(entry.get("ahead").is_some());
     assert!(entry.get("behind").is_some());
+    assert!(entry["operation"].is_null());
+}
+
+#[test]
+fn json_without_linked_worktrees_lists_only_primary() {
+    let (home, repo) = setup();
+
+    let entries = run_list_json(home.path(), &repo, None);
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["branch"].as_str(), Some("main"));
 }
 
 #[test]
@@ -191,6 +201,39 @@ fn list_human_output_matches_golden() {
     );
 }
 
+#[test]
+fn piped_output_has_no_escape_sequences() {
+    let (home, repo) = setup();
+    let wt_path = wt_new(home.path(), &repo, "feat-piped");
+    assert_git_success(&wt_path, &["checkout", "--detach"]);
+    assert_git_success_with(&repo, |cmd| {
+        cmd.args(["worktree", "lock"]).arg(&wt_path);
+    });
+
+    for args in [&["list"][..], &["list", "--all"][..]] {
+        let output = run_wt(home.path(), |cmd| {
+            cmd.args(args)
+                .current_dir(&repo)
+                .env("COLUMNS", "200")
+                .env("TERM", "xterm-256color")
+                .env_remove("NO_COLOR");
+        });
+        assert_exit_code(&output, 0);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(
+            !stdout.contains('\x1b') && !stderr.contains('\x1b'),
+            "piped `wt {}` must not emit control sequences, got:\n{stdout}{stderr}",
+            args.join(" "),
+        );
+        let row = find_row(&stdout, "(detached)");
+        assert!(
+            row.ends_with("[detached] [locked]"),
+            "badges should be plain text when piped, got: {row}",
+        );
+    }
+}
+
 #[test]
 fn marks_current_worktree_when_cwd_is_inside_linked_worktree() {
     let (home, repo) = setup();
@@ -234,6 +277,177 @@ fn shows_dirty_status_for_dirty_worktree() {
     );
 }
 
+#[test]
+fn shows_merging_state_for_worktree_mid_merge() {
+    let (home, repo) = setup();
+    let wt_path = wt_new(home.path(), &repo, "mid-merge");
+    let head = assert_git_stdout_success(&wt_path, &["rev-parse", "HEAD"]);
+    let git_dir = assert_git_stdout_success(&wt_path, &["rev-parse", "--absolute-git-dir"]);
+    std::fs::write(PathBuf::from(git_dir.trim()).join("MERGE_HEAD"), head).unwrap();
+
+    let output = run_list(home.path(), &repo, "200", Some(home.path()));
+    assert!(
+        output.status.success(),
+        "wt list failed: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let row = find_row(&stdout, "mid-merge");
+    assert!(
+        row.contains("   merging"),
+        "mid-merge worktree should show 'merging' in STATUS column, got: {row}"
+    );
+
+    let entries = run_list_json(home.path(), &repo, None);
+    assert_eq!(
+        find_json_entry(&entries, "mid-merge")["operation"].as_str(),
+        Some("merging")
+    );
+    assert!(find_json_entry(&entries, "main")["operation"].is_null());
+}
+
+#[test]
+fn links_column_shows_link_health_per_worktree() {
+    let (home, repo) = setup();
+    std::fs::write(repo.join(".env"), "SECRET=abc").unwrap();
+    wt_new(home.path(), &repo, "linked-wt");
+    let shadowed = wt_new(home.path(), &repo, "shadowed-wt");
+    std::fs::write(shadowed.join(".env"), "LOCAL=1").unwrap();
+    let link = run_wt(home.path(), |cmd| {
+        cmd.args(["link", ".env", "--repo"]).arg(&repo);
+    });
+    assert!(
+        link.status.success(),
+        "wt link failed: {}",
+        String::from_utf8_lossy(&link.stderr),
+    );
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["list", "--links", "--repo"]).arg(&repo);
+        cmd.env("COLUMNS", "200");
+    });
+    assert!(
+        output.status.success(),
+        "wt list --links failed: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.lines().next().unwrap().contains("LINKS"),
+        "header should include LINKS column, got:\n{stdout}"
+    );
+    assert!(find_row(&stdout, "linked-wt").contains("   ✓"));
+    assert!(find_row(&stdout, "shadowed-wt").contains("   ⚠"));
+    assert!(find_row(&stdout, "main").contains("   -"));
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["list", "--links", "--json", "--repo"]).arg(&repo);
+    });
+    let entries: Vec<Value> = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        find_json_entry(&entries, "linked-wt")["links"].as_str(),
+        Some("linked")
+    );
+    assert_eq!(
+        find_json_entry(&entries, "shadowed-wt")["links"].as_str(),
+        Some("partial")
+    );
+    assert!(find_json_entry(&entries, "main")["links"].is_null());
+}
+
+#[test]
+fn size_column_shows_worktree_size() {
+    let (home, repo) = setup();
+    let big = wt_new(home.path(), &repo, "big-wt");
+    wt_new(home.path(), &repo, "empty-wt");
+    std::fs::write(big.join("data.bin"), vec![0u8; 256 * 1024]).unwrap();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["list", "--size", "--repo"]).arg(&repo);
+        cmd.env("COLUMNS", "200");
+    });
+    assert!(
+        output.status.success(),
+        "wt list --size failed: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.lines().next().unwrap().contains("SIZE"),
+        "header should include SIZE column, got:\n{stdout}"
+    );
+    assert!(
+        find_row(&stdout, "big-wt").contains("256 K"),
+        "got:\n{stdout}"
+    );
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["list", "--size", "--json", "--repo"]).arg(&repo);
+    });
+    let entries: Vec<Value> = serde_json::from_slice(&output.stdout).unwrap();
+    let big_size = find_json_entry(&entries, "big-wt")["size"]
+        .as_u64()
+        .unwrap();
+    let empty_size = find_json_entry(&entries, "empty-wt")["size"]
+        .as_u64()
+        .unwrap();
+    assert!(big_size >= 256 * 1024, "got {big_size}");
+    assert!(big_size > empty_size, "{big_size} vs {empty_size}");
+}
+
+#[test]
+fn age_column_shows_relative_head_commit_date() {
+    let (home, repo) = setup();
+    let old = wt_new(home.path(), &repo, "old-wt");
+    let two_weeks_ago = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+        - 14 * 24 * 60 * 60;
+    assert_git_success_with(&old, |cmd| {
+        cmd.args(["commit", "--allow-empty", "-m", "old work"])
+            .env("GIT_COMMITTER_DATE", format!("@{two_weeks_ago} +0000"));
+    });
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["list", "--age", "--repo"]).arg(&repo);
+        cmd.env("COLUMNS", "200");
+    });
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.lines().next().unwrap().contains("AGE"),
+        "header should include AGE column, got:\n{stdout}"
+    );
+    assert!(
+        find_row(&stdout, "old-wt").contains("2 weeks ago"),
+        "got:\n{stdout}"
+    );
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["list", "--age", "--json", "--repo"]).arg(&repo);
+    });
+    let entries: Vec<Value> = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(find_json_entry(&entries, "old-wt")["age"], "2 weeks ago");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["list", "--repo"]).arg(&repo);
+    });
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!stdout.contains("AGE"), "got:\n{stdout}");
+}
+
+#[test]
+fn list_without_links_flag_has_no_links_column() {
+    let (home, repo) = setup();
+    let output = run_list(home.path(), &repo, "200", None);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        !stdout.contains("LINKS"),
+        "unexpected LINKS column:\n{stdout}"
+    );
+}
+
 #[test]
 fn shows_ahead_behind_arrows_for_diverged_branch() {
     let (home, repo, origin) = setup_with_origin();
@@ -470,6 +684,29 @@ fn list_all_json_includes_repo_field() {
     );
 }
 
+#[test]
+fn list_all_json_tags_repo_path_and_skips_deleted_repos() {
+    let (home, repo_a, repo_b) = setup_two_repos();
+    wt_new(home.path(), &repo_a, "feat-a");
+    let orphan = wt_new(home.path(), &repo_b, "feat-b");
+    std::fs::remove_dir_all(&repo_b).unwrap();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["list", "--all", "--json"])
+            .current_dir(home.path());
+    });
+    assert_exit_code(&output, 0);
+    assert_stderr_empty(&output);
+    let entries: Vec<Value> = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(entries.len(), 2, "only repo-a's worktrees: {entries:?}");
+    let repo_a_path = canonical(&repo_a).to_string_lossy().into_owned();
+    for entry in &entries {
+        assert_eq!(entry["repo"], "repo-a");
+        assert_eq!(entry["repo_path"], repo_a_path.as_str());
+    }
+    assert!(orphan.exists(), "list never removes anything");
+}
+
 #[test]
 fn list_all_marks_current_worktree() {
     let (home, repo_a, repo_b) = setup_two_repos();
@@ -504,6 +741,41 @@ fn list_all_marks_current_worktree() {
     );
 }
 
+#[test]
+fn list_all_repo_filter_shows_only_matching_repos() {
+    let home = TempDir::new().unwrap();
+    for name in ["work-api", "work-web", "personal"] {
+        let repo = home.path().join(name);
+        std::fs::create_dir(&repo).unwrap();
+        init_repo(&repo);
+        wt_new(home.path(), &repo, &format!("feat-{name}"));
+    }
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["list", "--all", "--repo-filter", "work-*"]);
+        cmd.env("COLUMNS", "200");
+    });
+    assert_exit_code(&output, 0);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("work-api:"), "got:\n{stdout}");
+    assert!(stdout.contains("work-web:"), "got:\n{stdout}");
+    assert!(
+        !stdout.contains("personal"),
+        "non-matching repo should be filtered out, got:\n{stdout}",
+    );
+}
+
+#[test]
+fn repo_filter_requires_all() {
+    let (home, repo) = setup();
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["list", "--repo-filter", "work-*", "--repo"])
+            .arg(&repo);
+    });
+    assert_exit_code(&output, 2);
+}
+
 #[test]
 fn list_all_and_repo_are_mutually_exclusive() {
     let (home, repo) = setup();
@@ -575,3 +847,236 @@ fn truncates_branch_and_path_in_narrow_terminal() {
         "full long branch name should not appear in narrow mode, got: {stdout}",
     );
 }
+
+#[test]
+fn no_progress_output_when_stderr_is_captured() {
+    let (home, repo) = setup();
+    wt_new(home.path(), &repo, "feat-a");
+    wt_new(home.path(), &repo, "feat-b");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["list", "--repo"]).arg(&repo);
+    });
+    assert!(output.status.success());
+    assert_stderr_empty(&output);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["list", "--all"]);
+    });
+    assert!(output.status.success());
+    assert_stderr_empty(&output);
+}
+
+#[test]
+fn discover_uses_single_repo_one_level_down() {
+    let (home, repo) = setup();
+    wt_new(home.path(), &repo, "feat/discovered");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["list", "--discover", "--json"])
+            .current_dir(home.path());
+    });
+    assert!(
+        output.status.success(),
+        "wt list --discover failed: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    let entries: Vec<Value> = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(
+        entries
+            .iter()
+            .any(|e| e["branch"].as_str() == Some("feat/discovered")),
+        "should list the child repo's worktrees, got: {entries:?}"
+    );
+}
+
+#[test]
+fn discover_refuses_multiple_repos() {
+    let (home, _repo) = setup();
+    let other = home.path().join("other");
+    std::fs::create_dir(&other).unwrap();
+    init_repo(&other);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["list", "--discover"]).current_dir(home.path());
+    });
+    assert!(!output.status.success(), "ambiguous discovery should fail");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("multiple repositories found") && stderr.contains("other, repo"),
+        "got: {stderr}"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn lists_resolved_path_when_wt_home_is_symlink() {
+    let (home, repo) = setup();
+    symlink_wt_home(home.path());
+    let wt_path = wt_new(home.path(), &repo, "via-symlink");
+
+    let entries = run_list_json(home.path(), &repo, None);
+    let entry = find_json_entry(&entries, "via-symlink");
+    assert_eq!(entry["path"].as_str(), wt_path.to_str());
+}
+
+#[test]
+fn remote_lists_branches_without_worktrees_as_candidates() {
+    let (home, repo, _origin) = setup_with_origin();
+    for branch in ["feat-a", "feat-b", "feat-c"] {
+        assert_git_success(&repo, &["branch", branch]);
+        assert_git_success(&repo, &["push", "-q", "origin", branch]);
+        assert_git_success(&repo, &["branch", "-D", branch]);
+    }
+    assert_git_success(&repo, &["fetch", "-q", "origin"]);
+    let wt_path = wt_new(home.path(), &repo, "feat-a");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["list", "--remote", "--json", "--repo"])
+            .arg(&repo);
+    });
+    assert!(
+        output.status.success(),
+        "wt list --remote failed: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    let entries: Vec<Value> = serde_json::from_slice(&output.stdout).unwrap();
+    let worktree_of = |branch: &str| {
+        entries
+            .iter()
+            .find(|e| e["branch"] == branch && e["remote"] == "origin")
+            .unwrap_or_else(|| panic!("no entry for {branch}: {entries:?}"))["worktree"]
+            .as_str()
+            .map(str::to_string)
+    };
+    assert_eq!(worktree_of("feat-a").as_deref(), wt_path.to_str());
+    assert_eq!(
+        worktree_of("main"),
+        Some(canonical(&repo).to_string_lossy().into_owned())
+    );
+    assert_eq!(worktree_of("feat-b"), None);
+    assert_eq!(worktree_of("feat-c"), None);
+    assert!(entries.iter().all(|e| e["branch"] != "HEAD"));
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["list", "--remote", "--repo"]).arg(&repo);
+    });
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let row = find_row(&stdout, "origin/feat-b");
+    assert!(row.trim_end().ends_with(" -"), "got: {row}");
+    assert!(
+        !find_row(&stdout, "origin/feat-a")
+            .trim_end()
+            .ends_with(" -")
+    );
+    assert_stderr_exact(&output, "2 remote branches without a worktree\n");
+}
+
+#[test]
+fn outside_a_repo_default_to_last_repo_uses_recorded_repo() {
+    let (home, repo) = setup();
+    let wt_path = wt_new(home.path(), &repo, "feat/recent");
+    let elsewhere = TempDir::new().unwrap();
+    let config = home.path().join("wt-gitconfig");
+    std::fs::write(&config, "[wt]\n\tdefaultToLastRepo = true\n").unwrap();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["list", "--json"]).current_dir(elsewhere.path());
+    });
+    assert_error(
+        &output,
+        1,
+        "not a git repository, use --repo or run inside one\n",
+    );
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["list", "--json"])
+            .current_dir(elsewhere.path())
+            .env("WT_CONFIG", &config);
+    });
+    assert_exit_code(&output, 0);
+    let entries: Vec<Value> = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(
+        entries
+            .iter()
+            .any(|e| e["path"].as_str() == wt_path.to_str()),
+        "should list the recorded repo's worktrees, got: {entries:?}",
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("using last-used repo") && stderr.contains("wt.defaultToLastRepo"),
+        "should say which repo was chosen, got: {stderr}",
+    );
+}
+
+#[test]
+fn help_outside_a_repo_ignores_default_to_last_repo() {
+    let (home, repo) = setup();
+    wt_new(home.path(), &repo, "feat/recent");
+    let elsewhere = TempDir::new().unwrap();
+    let config = home.path().join("wt-gitconfig");
+    std::fs::write(&config, "[wt]\n\tdefaultToLastRepo = true\n").unwrap();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.arg("--help")
+            .current_dir(elsewhere.path())
+            .env("WT_CONFIG", &config);
+    });
+    assert_exit_code(&output, 0);
+    assert_stderr_exact(&output, "");
+}
+
+fn json_branches(output: &Output) -> Vec<String> {
+    let entries: Vec<Value> = serde_json::from_slice(&output.stdout).unwrap();
+    entries
+        .iter()
+        .map(|e| e["branch"].as_str().unwrap_or("(detached)").to_string())
+        .collect()
+}
+
+#[test]
+fn sort_orders_rows_but_keeps_primary_first() {
+    let (home, repo) = setup();
+    for (branch, date) in [
+        ("feat/b", "2024-01-03T00:00:00Z"),
+        ("feat/c", "2024-01-01T00:00:00Z"),
+        ("feat/a", "2024-01-02T00:00:00Z"),
+    ] {
+        let wt_path = wt_new(home.path(), &repo, branch);
+        assert_git_success_with(&wt_path, |cmd| {
+            cmd.args(["commit", "--allow-empty", "-m", branch])
+                .env("GIT_COMMITTER_DATE", date);
+        });
+    }
+    let list = |sort: &str| {
+        run_wt(home.path(), |cmd| {
+            cmd.args(["list", "--json", "--sort", sort, "--repo"])
+                .arg(&repo);
+        })
+    };
+
+    let output = list("branch");
+    assert_exit_code(&output, 0);
+    assert_eq!(
+        json_branches(&output),
+        ["main", "feat/a", "feat/b", "feat/c"]
+    );
+
+    let output = list("committed");
+    assert_exit_code(&output, 0);
+    assert_eq!(
+        json_branches(&output),
+        ["main", "feat/b", "feat/a", "feat/c"]
+    );
+
+    // Row numbers follow the order that was shown.
+    let path_of = |args: &[&str]| {
+        let output = run_wt(home.path(), |cmd| {
+            cmd.arg("path").args(args).arg("--repo").arg(&repo);
+        });
+        assert_exit_code(&output, 0);
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+    assert_eq!(path_of(&["--by-index", "2"]), path_of(&["feat/b"]));
+}
@@ -0,0 +1,84 @@
+pub mod common;
+
+use std::process::Command;
+
+use common::*;
+
+fn dead_pid() -> u32 {
+    let mut child = Command::new("true").spawn().unwrap();
+    let pid = child.id();
+    child.wait().unwrap();
+    pid
+}
+
+#[test]
+fn clean_removes_stale_lock_and_unblocks_new() {
+    let (home, repo) = setup();
+    let lock = write_lock(home.path(), &repo, dead_pid(), "prune");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "-c", "feat/blocked", "--repo"]).arg(&repo);
+    });
+    assert!(
+        !output.status.success(),
+        "wt new should refuse a locked repo"
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("wt locks --clean"),
+        "should point at wt locks --clean, got: {stderr}"
+    );
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.arg("locks");
+    });
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("stale\t"), "got: {stdout}");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["locks", "--clean"]);
+    });
+    assert!(
+        output.status.success(),
+        "wt locks --clean failed: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    assert!(!lock.exists(), "stale lock should be removed");
+
+    let wt_path = wt_new(home.path(), &repo, "feat/unblocked");
+    assert!(wt_path.exists());
+    assert!(!lock.exists(), "wt new should release its lock");
+}
+
+#[test]
+fn clean_keeps_lock_held_by_live_process() {
+    let (home, repo) = setup();
+    let lock = write_lock(home.path(), &repo, std::process::id(), "prune");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["locks", "--clean"]);
+    });
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("held\t"), "got: {stdout}");
+    assert!(lock.exists(), "live lock should be kept");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "-c", "feat/busy", "--repo"]).arg(&repo);
+    });
+    assert!(
+        !output.status.success(),
+        "wt new should refuse while the holder runs"
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("repository is locked by `wt prune`"),
+        "got: {stderr}"
+    );
+    // The refused attempt leaves nothing beside the lock it lost to.
+    let files: Vec<_> = std::fs::read_dir(lock.parent().unwrap())
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .collect();
+    assert_eq!(files, [lock]);
+}
@@ -0,0 +1,64 @@
+use std::path::PathBuf;
+
+pub mod common;
+
+use common::*;
+
+#[test]
+fn renames_branch_and_keeps_worktree_in_place() {
+    let (home, repo) = setup();
+    let wt_path = wt_new(home.path(), &repo, "feat/logni");
+    std::fs::write(wt_path.join("wip.txt"), "uncommitted").unwrap();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["move", "feat/logni", "feat/login", "--repo"])
+            .arg(&repo);
+    });
+
+    assert_exit_code(&output, 0);
+    assert_stderr_exact(&output, "renamed 'feat/logni' to 'feat/login'\n");
+    let reported = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    assert_eq!(canonical(&reported), canonical(&wt_path));
+    assert_branch_absent(&repo, "feat/logni");
+    assert_branch_present(&repo, "feat/login");
+    assert_eq!(
+        assert_git_stdout_success(&wt_path, &["branch", "--show-current"]).trim(),
+        "feat/login"
+    );
+    assert!(wt_path.join("wip.txt").exists());
+}
+
+#[test]
+fn refuses_existing_branch() {
+    let (home, repo) = setup();
+    wt_new(home.path(), &repo, "feat/a");
+    assert_git_success(&repo, &["branch", "feat/b"]);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["mv", "feat/a", "feat/b", "--repo"]).arg(&repo);
+    });
+
+    assert_error(
+        &output,
+        1,
+        "cannot rename to 'feat/b': branch already exists\n",
+    );
+    assert_branch_present(&repo, "feat/a");
+}
+
+#[test]
+fn refuses_detached_worktree() {
+    let (home, repo) = setup();
+    assert_git_success(&repo, &["tag", "v1.0"]);
+    wt_checkout(home.path(), &repo, "v1.0");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["mv", "v1.0", "feat/x", "--repo"]).arg(&repo);
+    });
+
+    assert_error(
+        &output,
+        1,
+        "'v1.0' is a detached worktree, it has no branch to rename\n",
+    );
+}
@@ -105,6 +105,76 @@ fn base_succeeds_when_only_remote_branch_exists() {
     );
 }
 
+#[test]
+fn from_base_branches_from_configured_remote_only_base() {
+    let (home, repo) = setup();
+    let origin = home.path().join("origin.git");
+    init_bare_repo(&origin);
+    assert_git_success_with(&repo, |cmd| {
+        cmd.args(["remote", "add", "origin"]).arg(&origin);
+    });
+    assert_git_success(&repo, &["push", "-u", "origin", "main"]);
+    assert_git_success(&repo, &["checkout", "-q", "-b", "develop"]);
+    assert_git_success(&repo, &["commit", "-q", "--allow-empty", "-m", "develop"]);
+    assert_git_success(&repo, &["push", "-u", "origin", "develop"]);
+    assert_git_success(&repo, &["checkout", "-q", "main"]);
+    assert_git_success(&repo, &["branch", "-D", "develop"]);
+    assert_git_success(&repo, &["config", "wt.baseBranch", "develop"]);
+
+    let head_of = |path: &std::path::Path, rev: &str| {
+        assert_git_stdout_success(path, &["rev-parse", rev])
+            .trim()
+            .to_string()
+    };
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "-c", "feat/from-base", "--from-base", "--repo"])
+            .arg(&repo);
+    });
+    assert_exit_code(&output, 0);
+    let wt_path = parse_wt_new_path(&output);
+    assert_eq!(head_of(&wt_path, "HEAD"), head_of(&repo, "origin/develop"));
+    assert_eq!(upstream_of(&repo, "feat/from-base"), None);
+
+    // An explicit base wins over --from-base.
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args([
+            "new",
+            "-c",
+            "feat/explicit",
+            "main",
+            "--from-base",
+            "--repo",
+        ])
+        .arg(&repo);
+    });
+    assert_exit_code(&output, 0);
+    let wt_path = parse_wt_new_path(&output);
+    assert_eq!(head_of(&wt_path, "HEAD"), head_of(&repo, "main"));
+}
+
+#[test]
+fn from_base_falls_back_to_head_without_a_base() {
+    let (home, repo) = setup();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "-c", "feat/no-base", "--from-base", "--repo"])
+            .arg(&repo);
+    });
+
+    assert_exit_code(&output, 0);
+    assert_stderr_exact(
+        &output,
+        "cannot determine default branch (tried origin/HEAD, origin/main, origin/master), \
+         branching from HEAD\ncreating branch 'feat/no-base'\n",
+    );
+    let wt_path = parse_wt_new_path(&output);
+    assert_eq!(
+        assert_git_stdout_success(&wt_path, &["branch", "--show-current"]).trim(),
+        "feat/no-base"
+    );
+}
+
 #[test]
 fn checks_out_remote_branch_when_local_missing() {
     let (home, repo) = setup();
@@ -487,8 +557,8 @@ fn checkout_error_does_not_fallback_to_creation() {
     );
     let stderr = String::from_utf8_lossy(&output.stderr);
     assert!(
-        stderr.contains("already used by worktree"),
-        "expected checkout error from git, got: {stderr}",
+        stderr.contains("branch 'existing' is checked out in another worktree"),
+        "expected checked-out error, got: {stderr}",
     );
     assert!(
         !wt_root.exists() || std::fs::read_dir(&wt_root).unwrap().next().is_none(),
@@ -552,3 +622,1379 @@ fn no_cd_hint_when_stdout_not_tty() {
         "cd hint should not appear when stdout is not a TTY, got: {stderr}",
     );
 }
+
+#[test]
+fn independent_creates_separate_clone() {
+    let (home, repo) = setup();
+    assert_git_success(&repo, &["branch", "feat-indep"]);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "--independent", "feat-indep", "--repo"])
+            .arg(&repo);
+    });
+    assert!(
+        output.status.success(),
+        "wt new --independent should succeed: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    let clone_path = parse_wt_new_path(&output);
+
+    assert!(
+        clone_path.join(".git").is_dir(),
+        "independent checkout should have its own .git directory"
+    );
+    assert_eq!(
+        assert_git_stdout_success(&clone_path, &["branch", "--show-current"]).trim(),
+        "feat-indep"
+    );
+
+    let worktrees = assert_git_stdout_success(&repo, &["worktree", "list", "--porcelain"]);
+    assert!(
+        !worktrees.contains(canonical(&clone_path).to_string_lossy().as_ref()),
+        "independent checkout should not be a worktree of the primary, got: {worktrees}",
+    );
+}
+
+#[test]
+fn independent_is_marked_in_list() {
+    let (home, repo) = setup();
+    assert_git_success(&repo, &["branch", "feat-indep"]);
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "--independent", "feat-indep", "--repo"])
+            .arg(&repo);
+    });
+    assert!(output.status.success());
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["list", "--repo"]).arg(&repo);
+    });
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let row = stdout
+        .lines()
+        .find(|l| l.contains("feat-indep"))
+        .unwrap_or_else(|| panic!("list should show independent checkout, got: {stdout}"));
+    assert!(
+        row.contains("[independent]"),
+        "row should be badged, got: {row}"
+    );
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["list", "--json", "--repo"]).arg(&repo);
+    });
+    let entries: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let entry = entries
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|e| e["branch"] == "feat-indep")
+        .unwrap();
+    assert_eq!(entry["independent"], true);
+}
+
+#[test]
+fn independent_is_not_pruned() {
+    let (home, repo) = setup();
+    assert_git_success(&repo, &["branch", "feat-indep"]);
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "--independent", "feat-indep", "--repo"])
+            .arg(&repo);
+    });
+    let clone_path = parse_wt_new_path(&output);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["prune", "--stale", "--base", "main"]);
+    });
+    assert!(output.status.success());
+    assert!(clone_path.join(".git").is_dir());
+    assert_branch_present(&repo, "feat-indep");
+}
+
+#[test]
+fn independent_is_listed_with_all_and_removed_by_rm() {
+    let (home, repo) = setup();
+    assert_git_success(&repo, &["branch", "feat-indep"]);
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "--independent", "feat-indep", "--repo"])
+            .arg(&repo);
+    });
+    let clone_path = parse_wt_new_path(&output);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["list", "--all", "--json"]);
+    });
+    assert_exit_code(&output, 0);
+    let entries: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout).unwrap();
+    let entry = entries
+        .iter()
+        .find(|e| e["branch"] == "feat-indep")
+        .expect("list --all should show the independent checkout");
+    assert_eq!(entry["independent"], true);
+    assert_eq!(entry["path"], clone_path.to_str().unwrap());
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["rm", "feat-indep", "--repo"]).arg(&repo);
+    });
+    assert_exit_code(&output, 0);
+    let display = clone_path
+        .strip_prefix(canonical(home.path()))
+        .unwrap()
+        .display()
+        .to_string();
+    assert_stderr_exact(
+        &output,
+        &format!("removed independent checkout (~/{display})\n"),
+    );
+    assert!(!clone_path.exists());
+    assert_branch_present(&repo, "feat-indep");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["list", "--json", "--repo"]).arg(&repo);
+    });
+    let entries: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(entries.iter().all(|e| e.get("independent").is_none()));
+}
+
+#[test]
+fn rm_refuses_independent_checkout_with_unpushed_commits() {
+    let (home, repo) = setup();
+    assert_git_success(&repo, &["branch", "feat-indep"]);
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "--independent", "feat-indep", "--repo"])
+            .arg(&repo);
+    });
+    let clone_path = parse_wt_new_path(&output);
+    assert_git_success(&clone_path, &["config", "user.name", "Test"]);
+    assert_git_success(&clone_path, &["config", "user.email", "t@t"]);
+    assert_git_success(&clone_path, &["commit", "--allow-empty", "-m", "local"]);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["rm", "feat-indep", "--repo"]).arg(&repo);
+    });
+    assert_error(
+        &output,
+        1,
+        "independent checkout has 1 unpushed commit, use --force to remove\n",
+    );
+    assert!(clone_path.join(".git").is_dir());
+}
+
+#[test]
+fn independent_conflicts_with_create() {
+    let (home, repo) = setup();
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "--independent", "-c", "feat", "--repo"])
+            .arg(&repo);
+    });
+    assert_exit_code(&output, 2);
+}
+
+#[test]
+fn help_shows_configured_auto_links() {
+    let (home, repo) = setup();
+    std::fs::write(repo.join(".env"), "SECRET=1").unwrap();
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["link", ".env", "--repo"]).arg(&repo);
+    });
+    assert!(output.status.success());
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "--help"]).current_dir(&repo);
+    });
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Examples:\n  wt new feat/login"),
+        "built-in examples should remain, got: {stdout}",
+    );
+    assert!(
+        stdout.contains("Your config:\n  auto-link: .env"),
+        "help should surface configured links, got: {stdout}",
+    );
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "--help", "--repo"]).arg(&repo);
+    });
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("auto-link: .env"));
+}
+
+#[test]
+fn help_as_branch_name_is_not_a_help_request() {
+    let (home, repo) = setup();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "-c", "help", "--repo"]).arg(&repo);
+    });
+
+    assert_exit_code(&output, 0);
+    assert_stderr_exact(&output, "creating branch 'help'\n");
+    assert_branch_present(&repo, "help");
+}
+
+#[test]
+fn help_omits_config_section_when_nothing_configured() {
+    let (home, repo) = setup();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "--help"]).current_dir(&repo);
+    });
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!stdout.contains("Your config:"));
+}
+
+#[test]
+fn refuses_branch_checked_out_in_primary_worktree() {
+    let (home, repo) = setup();
+    assert_git_success(&repo, &["checkout", "-b", "feat/x"]);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "feat/x", "--repo"]).arg(&repo);
+    });
+
+    assert_exit_code(&output, 1);
+    assert_stdout_empty(&output);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("branch 'feat/x' is checked out in the primary worktree"),
+        "expected primary-worktree message, got: {stderr}",
+    );
+    assert!(
+        stderr.contains("use `wt switch feat/x` to go there"),
+        "expected switch hint, got: {stderr}",
+    );
+    let wt_root = home.path().join(".wt").join("worktrees");
+    assert!(
+        !wt_root.exists() || std::fs::read_dir(&wt_root).unwrap().next().is_none(),
+        "wt new should not leave directories behind"
+    );
+}
+
+#[test]
+fn path_places_worktree_at_custom_location() {
+    let (home, repo) = setup();
+    let parent = home.path().join("siblings");
+    std::fs::create_dir(&parent).unwrap();
+    let custom = parent.join("feat-x");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "-c", "feat/x", "--path"])
+            .arg(&custom)
+            .arg("--repo")
+            .arg(&repo);
+    });
+    assert_exit_code(&output, 0);
+    let created = parse_wt_new_path(&output);
+    assert_eq!(canonical(&created), canonical(&custom));
+    assert!(custom.join(".git").is_file(), "should be a linked worktree");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["path", "feat/x", "--repo"]).arg(&repo);
+    });
+    assert_exit_code(&output, 0);
+    let reported = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    assert_eq!(canonical(&reported), canonical(&custom));
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["rm", "feat/x", "--repo"]).arg(&repo);
+    });
+    assert_exit_code(&output, 0);
+    assert!(!custom.exists(), "custom worktree should be removed");
+    assert!(parent.exists(), "user-owned parent must be preserved");
+}
+
+#[test]
+fn path_refuses_non_empty_directory() {
+    let (home, repo) = setup();
+    let custom = home.path().join("occupied");
+    std::fs::create_dir(&custom).unwrap();
+    std::fs::write(custom.join("keep.txt"), "mine").unwrap();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "-c", "feat/y", "--path"])
+            .arg(&custom)
+            .arg("--repo")
+            .arg(&repo);
+    });
+    assert_exit_code(&output, 1);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("path already exists"),
+        "expected occupied-path error, got: {stderr}",
+    );
+    assert!(custom.join("keep.txt").exists());
+    assert_branch_absent(&repo, "feat/y");
+}
+
+#[test]
+fn base_upstream_resolves_in_invoking_worktree() {
+    let (home, repo, _origin) = setup_with_origin();
+    assert_git_success(&repo, &["checkout", "-b", "release"]);
+    assert_git_success(&repo, &["commit", "--allow-empty", "-m", "release work"]);
+    assert_git_success(&repo, &["push", "origin", "release"]);
+    assert_git_success(&repo, &["checkout", "main"]);
+    let release_tip = assert_git_stdout_success(&repo, &["rev-parse", "origin/release"]);
+    let main_tip = assert_git_stdout_success(&repo, &["rev-parse", "origin/main"]);
+    assert_ne!(release_tip, main_tip);
+
+    let feat_x = wt_new(home.path(), &repo, "feat/x");
+    assert_git_success(&feat_x, &["branch", "--set-upstream-to", "origin/release"]);
+    assert_git_success(&feat_x, &["commit", "--allow-empty", "-m", "local only"]);
+
+    for (branch, upstream) in [("feat/y", "@{upstream}"), ("feat/z", "@{u}")] {
+        let output = run_wt(home.path(), |cmd| {
+            cmd.args(["new", "-c", branch, upstream])
+                .current_dir(&feat_x);
+        });
+        assert_exit_code(&output, 0);
+        let tip = assert_git_stdout_success(&repo, &["rev-parse", branch]);
+        assert_eq!(
+            tip, release_tip,
+            "{branch} should start at the invoking worktree's upstream"
+        );
+    }
+}
+
+#[test]
+fn notes_when_start_point_is_behind_upstream() {
+    let (home, repo, _origin) = setup_with_origin();
+    assert_git_success(&repo, &["commit", "--allow-empty", "-m", "upstream only"]);
+    assert_git_success(&repo, &["push", "origin", "main"]);
+    assert_git_success(&repo, &["reset", "--hard", "HEAD~1"]);
+    let local_tip = assert_git_stdout_success(&repo, &["rev-parse", "main"]);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "-c", "feat/behind", "--repo"]).arg(&repo);
+    });
+
+    assert_exit_code(&output, 0);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("note: 'main' is 1 commit behind 'origin/main', consider pulling first"),
+        "expected behind-upstream note, got: {stderr}",
+    );
+    let tip = assert_git_stdout_success(&repo, &["rev-parse", "feat/behind"]);
+    assert_eq!(tip, local_tip, "start point must stay at HEAD");
+}
+
+#[test]
+fn no_behind_note_when_up_to_date() {
+    let (home, repo, _origin) = setup_with_origin();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "-c", "feat/current", "--repo"]).arg(&repo);
+    });
+
+    assert_exit_code(&output, 0);
+    assert_stderr_exact(&output, "creating branch 'feat/current'\n");
+}
+
+// `feat/conflict` tracks a file where the primary has the linked directory, so
+// creating the link's parent directory in the new worktree fails.
+fn setup_failing_link() -> (tempfile::TempDir, std::path::PathBuf) {
+    let (home, repo) = setup();
+    assert_git_success(&repo, &["checkout", "-b", "feat/conflict"]);
+    std::fs::write(repo.join("conf"), "file").unwrap();
+    assert_git_success(&repo, &["add", "conf"]);
+    assert_git_success(&repo, &["commit", "-m", "conf as a file"]);
+    assert_git_success(&repo, &["checkout", "main"]);
+
+    std::fs::create_dir(repo.join("conf")).unwrap();
+    std::fs::write(repo.join("conf/.env"), "SECRET=1").unwrap();
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["link", "conf/.env", "--repo"]).arg(&repo);
+    });
+    assert!(
+        output.status.success(),
+        "wt link failed: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    (home, repo)
+}
+
+#[test]
+fn keeps_worktree_when_auto_link_fails() {
+    let (home, repo) = setup_failing_link();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "feat/conflict", "--repo"]).arg(&repo);
+    });
+    assert!(
+        output.status.success(),
+        "wt new should keep going: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("cannot auto-link conf/.env"),
+        "got: {stderr}"
+    );
+    assert!(parse_wt_new_path(&output).exists());
+}
+
+#[test]
+fn strict_setup_rolls_back_when_auto_link_fails() {
+    let (home, repo) = setup_failing_link();
+    let worktrees = home.path().join(".wt/worktrees");
+    let entries = || std::fs::read_dir(&worktrees).map_or(0, |d| d.count());
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "feat/conflict", "--strict-setup", "--repo"])
+            .arg(&repo);
+    });
+    assert!(!output.status.success(), "wt new should fail");
+    assert_stdout_empty(&output);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--strict-setup"), "got: {stderr}");
+    assert_eq!(entries(), 0, "no worktree directory should remain");
+    let list = assert_git_stdout_success(&repo, &["worktree", "list", "--porcelain"]);
+    assert!(!list.contains("feat/conflict"), "got: {list}");
+    assert_branch_present(&repo, "feat/conflict");
+}
+
+#[test]
+fn strict_setup_deletes_created_branch() {
+    let (home, repo) = setup_failing_link();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args([
+            "new",
+            "-c",
+            "feat/strict",
+            "feat/conflict",
+            "--strict-setup",
+            "--repo",
+        ])
+        .arg(&repo);
+    });
+    assert!(!output.status.success(), "wt new should fail");
+    assert_branch_absent(&repo, "feat/strict");
+}
+
+#[test]
+fn create_in_repo_without_commits_explains() {
+    let home = tempfile::TempDir::new().unwrap();
+    let repo = home.path().join("empty");
+    std::fs::create_dir(&repo).unwrap();
+    assert_git_success(&repo, &["init", "-b", "main"]);
+
+    for args in [&["new", "-c", "feat/x"][..], &["switch", "feat/x"][..]] {
+        let output = run_wt(home.path(), |cmd| {
+            cmd.args(args).arg("--repo").arg(&repo);
+        });
+        assert_eq!(output.status.code(), Some(1), "{args:?} should fail");
+        assert_stdout_empty(&output);
+        assert_stderr_exact(
+            &output,
+            "repository has no commits yet, make an initial commit first\n",
+        );
+    }
+    assert!(!home.path().join(".wt/worktrees").exists());
+}
+
+fn setup_ignored_files() -> (TempDir, PathBuf) {
+    let (home, repo) = setup();
+    std::fs::write(repo.join(".gitignore"), ".env\nconfig/\n").unwrap();
+    assert_git_success(&repo, &["add", ".gitignore"]);
+    assert_git_success(&repo, &["commit", "-m", "ignore local files"]);
+    std::fs::write(repo.join(".env"), "SECRET=1").unwrap();
+    std::fs::create_dir(repo.join("config")).unwrap();
+    std::fs::write(repo.join("config/local.toml"), "debug = true").unwrap();
+    std::fs::write(repo.join("scratch.txt"), "untracked, not ignored").unwrap();
+    (home, repo)
+}
+
+#[test]
+fn copy_untracked_copies_ignored_files() {
+    let (home, repo) = setup_ignored_files();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(repo.join(".env"), std::fs::Permissions::from_mode(0o600))
+            .unwrap();
+    }
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "-c", "feat/copy", "--copy-untracked", "--repo"])
+            .arg(&repo);
+    });
+    assert!(
+        output.status.success(),
+        "wt new failed: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 1, "stdout should be the path only");
+    let wt_path = parse_wt_new_path(&output);
+    assert_eq!(
+        std::fs::read_to_string(wt_path.join(".env")).unwrap(),
+        "SECRET=1"
+    );
+    assert_eq!(
+        std::fs::read_to_string(wt_path.join("config/local.toml")).unwrap(),
+        "debug = true"
+    );
+    assert!(!wt_path.join("scratch.txt").exists());
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(wt_path.join(".env"))
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("copied .env\n"), "got: {stderr}");
+    assert!(
+        stderr.contains("copied config/local.toml\n"),
+        "got: {stderr}"
+    );
+}
+
+#[test]
+fn copy_untracked_with_glob_copies_only_matches() {
+    let (home, repo) = setup_ignored_files();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args([
+            "new",
+            "-c",
+            "feat/copy",
+            "--copy-untracked=config/*",
+            "--repo",
+        ])
+        .arg(&repo);
+    });
+    assert!(
+        output.status.success(),
+        "wt new failed: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    let wt_path = parse_wt_new_path(&output);
+    assert!(wt_path.join("config/local.toml").exists());
+    assert!(!wt_path.join(".env").exists());
+}
+
+#[test]
+fn copy_untracked_keeps_linked_files() {
+    let (home, repo) = setup_ignored_files();
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["link", ".env", "--repo"]).arg(&repo);
+    });
+    assert!(output.status.success());
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "-c", "feat/copy", "--copy-untracked", "--repo"])
+            .arg(&repo);
+    });
+    assert!(
+        output.status.success(),
+        "wt new failed: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    let wt_path = parse_wt_new_path(&output);
+    assert!(
+        wt_path
+            .join(".env")
+            .symlink_metadata()
+            .unwrap()
+            .file_type()
+            .is_symlink()
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!stderr.contains("copied .env"), "got: {stderr}");
+    assert!(stderr.contains("copied config/local.toml"), "got: {stderr}");
+}
+
+#[test]
+fn cd_prints_quoted_cd_command() {
+    let (home, repo) = setup();
+    let custom = home.path().join("it's my feat");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "-c", "feat/x", "--cd", "--path"])
+            .arg(&custom)
+            .arg("--repo")
+            .arg(&repo);
+    });
+    assert_exit_code(&output, 0);
+    let quoted = custom.display().to_string().replace('\'', r"'\''");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        format!("cd '{quoted}'\n")
+    );
+    assert!(custom.join(".git").is_file());
+}
+
+#[cfg(unix)]
+fn fake_editor(home: &std::path::Path) -> (PathBuf, PathBuf) {
+    use std::os::unix::fs::PermissionsExt;
+
+    let record = home.join("editor-record");
+    let editor = home.join("fake-editor");
+    std::fs::write(
+        &editor,
+        format!(
+            "#!/bin/sh\nprintf '%s\\n' \"$1\" > '{}.tmp' && mv '{0}.tmp' '{0}'\n",
+            record.display()
+        ),
+    )
+    .unwrap();
+    std::fs::set_permissions(&editor, std::fs::Permissions::from_mode(0o755)).unwrap();
+    (editor, record)
+}
+
+// The editor is started without waiting for it, so its record shows up a
+// little after `wt` exits.
+#[cfg(unix)]
+fn wait_for(path: &std::path::Path) -> Option<String> {
+    for _ in 0..100 {
+        if let Ok(content) = std::fs::read_to_string(path) {
+            return Some(content);
+        }
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+    None
+}
+
+#[cfg(unix)]
+#[test]
+fn open_on_create_launches_editor() {
+    let (home, repo) = setup();
+    let (editor, record) = fake_editor(home.path());
+    assert_git_success(&repo, &["config", "wt.openOnCreate", "true"]);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "-c", "feat/open", "--repo"])
+            .arg(&repo)
+            .env_remove("VISUAL")
+            .env("EDITOR", &editor);
+    });
+    assert_exit_code(&output, 0);
+    let wt_path = parse_wt_new_path(&output);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("opening in"), "got: {stderr}");
+    let opened = wait_for(&record).expect("editor should run");
+    assert_eq!(opened.trim(), wt_path.display().to_string());
+}
+
+#[cfg(unix)]
+#[test]
+fn no_open_overrides_open_on_create() {
+    let (home, repo) = setup();
+    let (editor, _record) = fake_editor(home.path());
+    assert_git_success(&repo, &["config", "wt.openOnCreate", "true"]);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "-c", "feat/open", "--no-open", "--repo"])
+            .arg(&repo)
+            .env_remove("VISUAL")
+            .env("EDITOR", &editor);
+    });
+    assert_exit_code(&output, 0);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!stderr.contains("opening in"), "got: {stderr}");
+}
+
+#[test]
+fn open_without_editor_still_prints_path() {
+    let (home, repo) = setup();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "-c", "feat/open", "--open", "--repo"])
+            .arg(&repo)
+            .env_remove("VISUAL")
+            .env_remove("EDITOR");
+    });
+    assert_exit_code(&output, 0);
+    assert!(parse_wt_new_path(&output).exists());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("cannot open editor: neither $VISUAL nor $EDITOR is set"),
+        "got: {stderr}"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn open_with_command_runs_it_instead_of_editor() {
+    let (home, repo) = setup();
+    let (editor, record) = fake_editor(home.path());
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "-c", "feat/open", "--repo"])
+            .arg(&repo)
+            .arg(format!("--open={}", editor.display()))
+            .env_remove("VISUAL")
+            .env("EDITOR", "false");
+    });
+    assert_exit_code(&output, 0);
+    let wt_path = parse_wt_new_path(&output);
+    let opened = wait_for(&record).expect("command should run");
+    assert_eq!(opened.trim(), wt_path.display().to_string());
+}
+
+#[test]
+fn double_dash_separates_branch_name() {
+    let (home, repo) = setup();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "-c", "--repo"])
+            .arg(&repo)
+            .args(["--", "feat/x"]);
+    });
+    assert_exit_code(&output, 0);
+    let wt_path = parse_wt_new_path(&output);
+    assert_branch_present(&repo, "feat/x");
+
+    for cmd_name in ["path", "rm"] {
+        let output = run_wt(home.path(), |cmd| {
+            cmd.args([cmd_name, "--repo"])
+                .arg(&repo)
+                .args(["--", "feat/x"]);
+        });
+        assert_exit_code(&output, 0);
+    }
+    assert!(!wt_path.exists());
+}
+
+#[test]
+fn leading_dash_name_is_rejected_before_git_sees_it() {
+    let (home, repo) = setup();
+
+    for (args, expected) in [
+        (
+            &["new", "--", "-bad"][..],
+            "invalid ref '-bad': cannot start with '-'\n",
+        ),
+        (
+            &["new", "-c", "--", "-bad"][..],
+            "invalid branch name '-bad': cannot start with '-'\n",
+        ),
+        (
+            &["new", "-c", "feat/x", "--", "-bad"][..],
+            "invalid ref '-bad': cannot start with '-'\n",
+        ),
+    ] {
+        let output = run_wt(home.path(), |cmd| {
+            cmd.current_dir(&repo).args(args);
+        });
+        assert_error(&output, 1, expected);
+    }
+    // `-bad` handed to `git worktree add` would have read as `-b ad`.
+    assert_branch_absent(&repo, "ad");
+    assert_branch_absent(&repo, "feat/x");
+}
+
+#[test]
+fn create_rejects_invalid_branch_name() {
+    let (home, repo) = setup();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "-c", "feat..x", "--repo"]).arg(&repo);
+    });
+    assert_error(&output, 1, "invalid branch name 'feat..x'\n");
+}
+
+#[test]
+fn invalid_repo_config_aborts_before_creating() {
+    let (home, repo) = setup();
+    std::fs::write(repo.join(".wt.toml"), "link = \".env\"\n").unwrap();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "-c", "feat/x", "--repo"]).arg(&repo);
+    });
+    assert_exit_code(&output, 1);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.starts_with("invalid .wt.toml: "), "got: {stderr}");
+    assert_branch_absent(&repo, "feat/x");
+}
+
+#[test]
+fn from_stash_ref_applies_that_stash_at_its_base() {
+    let (home, repo) = setup();
+    std::fs::write(repo.join("notes.txt"), "base\n").unwrap();
+    assert_git_success(&repo, &["add", "notes.txt"]);
+    assert_git_success(&repo, &["commit", "-m", "add notes"]);
+
+    std::fs::write(repo.join("notes.txt"), "first patch\n").unwrap();
+    assert_git_success(&repo, &["stash"]);
+    std::fs::write(repo.join("notes.txt"), "second patch\n").unwrap();
+    assert_git_success(&repo, &["stash"]);
+    // The stash's base, not HEAD, is where the review branch starts.
+    assert_git_success(&repo, &["commit", "--allow-empty", "-m", "later"]);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args([
+            "new",
+            "--from-stash-ref",
+            "stash@{1}",
+            "--as",
+            "review/patch",
+            "--repo",
+        ])
+        .arg(&repo);
+    });
+    assert_exit_code(&output, 0);
+    let wt_path = parse_wt_new_path(&output);
+    assert_eq!(
+        std::fs::read_to_string(wt_path.join("notes.txt")).unwrap(),
+        "first patch\n"
+    );
+    assert_eq!(
+        assert_git_stdout_success(&wt_path, &["log", "-1", "--format=%s"]).trim(),
+        "add notes"
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("applied stash@{1}"), "got: {stderr}");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args([
+            "new",
+            "--from-stash-ref",
+            "HEAD",
+            "--as",
+            "review/head",
+            "--repo",
+        ])
+        .arg(&repo);
+    });
+    assert_error(&output, 1, "not a stash entry: HEAD\n");
+}
+
+#[test]
+fn verbose_shows_git_advice_below_the_error() {
+    let (home, repo) = setup();
+    let dest = home.path().join("custom");
+    let new = |branch: &str, verbose: bool| {
+        run_wt(home.path(), |cmd| {
+            cmd.args(["new", "-c", branch, "--path"])
+                .arg(&dest)
+                .arg("--repo")
+                .arg(&repo);
+            if verbose {
+                cmd.arg("-v");
+            }
+        })
+    };
+    assert_exit_code(&new("feat/a", false), 0);
+    std::fs::remove_dir_all(&dest).unwrap();
+
+    let error = format!(
+        "cannot create worktree: '{}' is a missing but already registered worktree;\n",
+        dest.display()
+    );
+    assert_error(&new("feat/b", false), 1, &error);
+    assert_error(
+        &new("feat/c", true),
+        1,
+        &format!("{error}  use 'add -f' to override, or 'prune' or 'remove' to clear\n"),
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn prints_resolved_path_when_wt_home_is_symlink() {
+    let (home, repo) = setup();
+    let target = symlink_wt_home(home.path());
+
+    let wt_path = wt_new(home.path(), &repo, "via-symlink");
+    assert!(
+        wt_path.starts_with(&target),
+        "expected a path under {}, got {}",
+        target.display(),
+        wt_path.display(),
+    );
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["path", "via-symlink", "--repo"]).arg(&repo);
+    });
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout).trim(),
+        wt_path.to_str().unwrap(),
+        "new and path should agree on the worktree path",
+    );
+}
+
+fn sparse_fixture(repo: &std::path::Path) {
+    std::fs::write(repo.join("top.txt"), "top").unwrap();
+    for dir in ["app", "docs", "vendor"] {
+        std::fs::create_dir(repo.join(dir)).unwrap();
+        std::fs::write(repo.join(dir).join("file.txt"), dir).unwrap();
+    }
+    assert_git_success(repo, &["add", "."]);
+    assert_git_success(repo, &["commit", "-m", "add dirs"]);
+}
+
+#[test]
+fn new_worktree_copies_cone_sparse_checkout_of_primary() {
+    let (home, repo) = setup();
+    sparse_fixture(&repo);
+    assert_git_success(&repo, &["sparse-checkout", "set", "--cone", "app"]);
+
+    let wt_path = wt_new(home.path(), &repo, "sparse");
+    assert!(wt_path.join("app/file.txt").exists());
+    assert!(wt_path.join("top.txt").exists(), "top-level files stay");
+    assert!(!wt_path.join("docs").exists());
+    assert!(!wt_path.join("vendor").exists());
+    assert_eq!(
+        assert_git_stdout_success(&wt_path, &["sparse-checkout", "list"]).trim(),
+        "app"
+    );
+    assert!(
+        assert_git_stdout_success(&wt_path, &["status", "--porcelain"]).is_empty(),
+        "sparse worktree should be clean"
+    );
+}
+
+#[test]
+fn sparse_paths_config_narrows_new_worktree() {
+    let (home, repo) = setup();
+    sparse_fixture(&repo);
+    assert_git_success(&repo, &["config", "wt.sparsePaths", "docs, vendor"]);
+
+    let wt_path = wt_new(home.path(), &repo, "sparse");
+    assert!(wt_path.join("docs/file.txt").exists());
+    assert!(wt_path.join("vendor/file.txt").exists());
+    assert!(!wt_path.join("app").exists());
+    assert!(repo.join("app/file.txt").exists(), "primary is untouched");
+}
+
+#[test]
+fn sparse_flag_overrides_sparse_paths_config() {
+    let (home, repo) = setup();
+    sparse_fixture(&repo);
+    assert_git_success(&repo, &["config", "wt.sparsePaths", "docs"]);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "-c", "feat/sparse", "--repo"])
+            .arg(&repo)
+            .args(["--sparse", "app,vendor"]);
+    });
+    assert_exit_code(&output, 0);
+    let wt_path = parse_wt_new_path(&output);
+    assert!(wt_path.join("app/file.txt").exists());
+    assert!(wt_path.join("vendor/file.txt").exists());
+    assert!(!wt_path.join("docs").exists());
+    assert_eq!(
+        assert_git_stdout_success(&wt_path, &["sparse-checkout", "list"]),
+        "app\nvendor\n"
+    );
+}
+
+#[test]
+fn sparse_flag_leaves_the_base_positional_alone() {
+    let (home, repo) = setup();
+    sparse_fixture(&repo);
+    assert_git_success(&repo, &["branch", "develop"]);
+    assert_git_success(&repo, &["commit", "--allow-empty", "-m", "ahead"]);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args([
+            "new",
+            "-c",
+            "feat/sparse",
+            "--sparse",
+            "app",
+            "develop",
+            "--repo",
+        ])
+        .arg(&repo);
+    });
+    assert_exit_code(&output, 0);
+    let wt_path = parse_wt_new_path(&output);
+    assert_eq!(
+        assert_git_stdout_success(&wt_path, &["rev-parse", "HEAD"]),
+        assert_git_stdout_success(&repo, &["rev-parse", "develop"])
+    );
+    assert_eq!(
+        assert_git_stdout_success(&wt_path, &["sparse-checkout", "list"]),
+        "app\n"
+    );
+}
+
+#[test]
+fn sparse_flag_rejects_paths_outside_the_worktree() {
+    let (home, repo) = setup();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "-c", "feat/sparse", "--repo"])
+            .arg(&repo)
+            .args(["--sparse", "app", "--sparse", "../other"]);
+    });
+    assert_error(&output, 1, "path must not contain '..': ../other\n");
+    assert_branch_absent(&repo, "feat/sparse");
+    assert!(!home.path().join(".wt/worktrees").exists());
+}
+
+#[cfg(unix)]
+fn write_hook(path: &std::path::Path, body: &str) {
+    use std::os::unix::fs::PermissionsExt;
+
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, format!("#!/bin/sh\n{body}")).unwrap();
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755)).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn post_create_hook_runs_in_new_worktree() {
+    let (home, repo) = setup();
+    let sentinel = home.path().join("sentinel");
+    write_hook(
+        &home.path().join(".wt/hooks/post-create"),
+        &format!(
+            "echo \"$1|$WT_BRANCH|$WT_REPO|$WT_PATH|$PWD\" > '{}'\necho hook output\n",
+            sentinel.display()
+        ),
+    );
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "-c", "feat/hook", "--repo"]).arg(&repo);
+    });
+    assert_exit_code(&output, 0);
+    let wt_path = parse_wt_new_path(&output);
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout).lines().count(),
+        1,
+        "hook output must stay off stdout"
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("hook output\n"), "got: {stderr}");
+    let path = wt_path.display();
+    assert_eq!(
+        std::fs::read_to_string(&sentinel).unwrap(),
+        format!(
+            "{path}|feat/hook|{}|{path}|{path}\n",
+            canonical(&repo).display()
+        )
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn failing_post_create_hook_keeps_worktree() {
+    let (home, repo) = setup();
+    write_hook(&home.path().join(".wt/hooks/post-create"), "exit 4\n");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "-c", "feat/hook", "--repo"]).arg(&repo);
+    });
+    assert_exit_code(&output, 0);
+    assert_stderr_exact(
+        &output,
+        "creating branch 'feat/hook'\npost-create hook failed (exit 4)\n",
+    );
+    assert!(parse_wt_new_path(&output).exists());
+    assert_branch_present(&repo, "feat/hook");
+}
+
+#[cfg(unix)]
+#[test]
+fn repo_config_post_create_hook_wins_over_global() {
+    let (home, repo) = setup();
+    let sentinel = home.path().join("sentinel");
+    write_hook(
+        &home.path().join(".wt/hooks/post-create"),
+        &format!("echo global > '{}'\n", sentinel.display()),
+    );
+    write_hook(
+        &repo.join("scripts/setup.sh"),
+        &format!("echo repo > '{}'\n", sentinel.display()),
+    );
+    std::fs::write(
+        repo.join(".wt.toml"),
+        "post_create = \"scripts/setup.sh\"\n",
+    )
+    .unwrap();
+    assert_git_success(&repo, &["config", "wt.allowRepoHooks", "true"]);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["switch", "feat/hook", "--repo"]).arg(&repo);
+    });
+    assert_exit_code(&output, 0);
+    assert_eq!(std::fs::read_to_string(&sentinel).unwrap(), "repo\n");
+}
+
+#[cfg(unix)]
+#[test]
+fn repo_config_post_create_hook_needs_opt_in() {
+    let (home, repo) = setup();
+    let sentinel = home.path().join("sentinel");
+    write_hook(
+        &home.path().join(".wt/hooks/post-create"),
+        &format!("echo global > '{}'\n", sentinel.display()),
+    );
+    write_hook(
+        &repo.join("scripts/setup.sh"),
+        &format!("echo repo > '{}'\n", sentinel.display()),
+    );
+    std::fs::write(
+        repo.join(".wt.toml"),
+        "post_create = \"scripts/setup.sh\"\n",
+    )
+    .unwrap();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "-c", "feat/hook", "--repo"]).arg(&repo);
+    });
+    assert_exit_code(&output, 0);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("not running post_create from .wt.toml, set wt.allowRepoHooks to allow it"),
+        "got: {stderr}"
+    );
+    assert_eq!(std::fs::read_to_string(&sentinel).unwrap(), "global\n");
+}
+
+#[cfg(unix)]
+#[test]
+fn repo_config_post_create_hook_must_stay_in_the_repo() {
+    let (home, repo) = setup();
+    let sentinel = home.path().join("sentinel");
+    write_hook(
+        &home.path().join("outside.sh"),
+        &format!("echo outside > '{}'\n", sentinel.display()),
+    );
+    std::fs::write(repo.join(".wt.toml"), "post_create = \"../outside.sh\"\n").unwrap();
+    assert_git_success(&repo, &["config", "wt.allowRepoHooks", "true"]);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "-c", "feat/hook", "--repo"]).arg(&repo);
+    });
+    assert_exit_code(&output, 0);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains(
+            "ignoring post_create in .wt.toml: path must not contain '..': ../outside.sh"
+        ),
+        "got: {stderr}"
+    );
+    assert!(!sentinel.exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn post_create_hook_leaves_branch_unset_for_detached_checkout() {
+    let (home, repo) = setup();
+    let sentinel = home.path().join("sentinel");
+    write_hook(
+        &home.path().join(".wt/hooks/post-create"),
+        &format!("echo \"${{WT_BRANCH-unset}}\" > '{}'\n", sentinel.display()),
+    );
+    assert_git_success(&repo, &["tag", "v1.0"]);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "v1.0", "--repo"])
+            .arg(&repo)
+            .env("WT_BRANCH", "stale");
+    });
+    assert_exit_code(&output, 0);
+    assert_eq!(std::fs::read_to_string(&sentinel).unwrap(), "unset\n");
+}
+
+#[cfg(unix)]
+#[test]
+fn open_at_terminal_waits_and_fails_with_editor() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let (home, repo) = setup();
+    let editor = home.path().join("failing-editor");
+    std::fs::write(&editor, "#!/bin/sh\nexit 3\n").unwrap();
+    std::fs::set_permissions(&editor, std::fs::Permissions::from_mode(0o755)).unwrap();
+    let (_leader, follower) = pty();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "-c", "feat/open", "--repo"])
+            .arg(&repo)
+            .arg(format!("--open={}", editor.display()))
+            .stdout(follower);
+    });
+    assert_exit_code(&output, 1);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.ends_with("failing-editor exited with status 3\n"),
+        "got: {stderr}"
+    );
+    assert_branch_present(&repo, "feat/open");
+}
+
+#[cfg(unix)]
+#[test]
+fn open_at_terminal_without_editor_fails_only_when_explicit() {
+    let (home, repo) = setup();
+    assert_git_success(&repo, &["config", "wt.openOnCreate", "true"]);
+
+    let (_leader, follower) = pty();
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "-c", "feat/implied", "--repo"])
+            .arg(&repo)
+            .env_remove("VISUAL")
+            .env_remove("EDITOR")
+            .stdout(follower);
+    });
+    assert_exit_code(&output, 0);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("cannot open editor: neither $VISUAL nor $EDITOR is set"),
+        "got: {stderr}"
+    );
+
+    let (_leader, follower) = pty();
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "-c", "feat/explicit", "--open", "--repo"])
+            .arg(&repo)
+            .env_remove("VISUAL")
+            .env_remove("EDITOR")
+            .stdout(follower);
+    });
+    assert_exit_code(&output, 1);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.ends_with("no editor found; set $EDITOR or pass a command\n"),
+        "got: {stderr}"
+    );
+    assert_branch_present(&repo, "feat/explicit");
+}
+
+fn upstream_of(repo: &std::path::Path, branch: &str) -> Option<String> {
+    let output = git(repo)
+        .args(["rev-parse", "--abbrev-ref"])
+        .arg(format!("{branch}@{{upstream}}"))
+        .output()
+        .unwrap();
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[test]
+fn track_sets_upstream_of_created_branch() {
+    let (home, repo, _origin) = setup_with_origin();
+    assert_git_success(&repo, &["push", "-q", "origin", "main:release-2.1"]);
+    assert_git_success(&repo, &["fetch", "-q", "origin"]);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args([
+            "new",
+            "-c",
+            "fix-2.1",
+            "--track",
+            "origin/release-2.1",
+            "--repo",
+        ])
+        .arg(&repo);
+    });
+    assert!(
+        output.status.success(),
+        "wt new --track failed: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    assert!(String::from_utf8_lossy(&output.stderr).contains("tracking 'origin/release-2.1'"));
+    assert_eq!(
+        upstream_of(&repo, "fix-2.1").as_deref(),
+        Some("origin/release-2.1")
+    );
+}
+
+#[test]
+fn track_rejects_unknown_ref_before_creating_anything() {
+    let (home, repo, _origin) = setup_with_origin();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args([
+            "new",
+            "-c",
+            "fix-typo",
+            "--track",
+            "origin/no-such",
+            "--repo",
+        ])
+        .arg(&repo);
+    });
+    assert_error(
+        &output,
+        1,
+        "cannot track 'origin/no-such': no such branch\n",
+    );
+    assert_branch_absent(&repo, "fix-typo");
+    assert!(!home.path().join(".wt").join("worktrees").exists());
+}
+
+#[test]
+fn no_track_leaves_branch_off_remote_without_upstream() {
+    let (home, repo, _origin) = setup_with_origin();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args([
+            "new",
+            "-c",
+            "untracked",
+            "origin/main",
+            "--no-track",
+            "--repo",
+        ])
+        .arg(&repo);
+    });
+    assert!(
+        output.status.success(),
+        "wt new --no-track failed: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    assert_eq!(upstream_of(&repo, "untracked"), None);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "-c", "tracked", "origin/main", "--repo"])
+            .arg(&repo);
+    });
+    assert!(output.status.success());
+    assert_eq!(
+        upstream_of(&repo, "tracked").as_deref(),
+        Some("origin/main")
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn read_only_worktree_root_fails_with_clear_error() {
+    let (home, repo) = setup();
+    let Some(root) = read_only_wt_root(home.path()) else {
+        return;
+    };
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "-c", "feat/ro", "--repo"]).arg(&repo);
+    });
+    assert_error(
+        &output,
+        1,
+        &format!(
+            "cannot write to worktree root {}: permission denied\n",
+            canonical(&root).display()
+        ),
+    );
+    assert_branch_absent(&repo, "feat/ro");
+}
+
+#[test]
+fn file_as_worktree_root_fails_with_clear_error() {
+    let (home, repo) = setup();
+    let root = file_as_wt_root(home.path());
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "-c", "feat/file-root", "--repo"])
+            .arg(&repo);
+    });
+    assert_error(
+        &output,
+        1,
+        &format!(
+            "cannot write to worktree root {}: not a directory\n",
+            canonical(&root).display()
+        ),
+    );
+    assert_branch_absent(&repo, "feat/file-root");
+}
@@ -0,0 +1,157 @@
+use std::path::{Path, PathBuf};
+
+pub mod common;
+
+use common::*;
+
+// A stand-in for an opener that appends each run's arguments, one per line,
+// to `<name>.args` next to it.
+#[cfg(unix)]
+fn fake_program(dir: &Path, name: &str) -> PathBuf {
+    use std::os::unix::fs::PermissionsExt;
+
+    let record = dir.join(format!("{name}.args"));
+    let program = dir.join(name);
+    std::fs::write(
+        &program,
+        format!(
+            "#!/bin/sh\nprintf '%s\\n' \"$@\" >> '{}'\n",
+            record.display()
+        ),
+    )
+    .unwrap();
+    std::fs::set_permissions(&program, std::fs::Permissions::from_mode(0o755)).unwrap();
+    record
+}
+
+#[cfg(unix)]
+fn with_bin(bin: &Path) -> String {
+    format!("{}:{}", bin.display(), std::env::var("PATH").unwrap())
+}
+
+// The editor is started without waiting for it when stdout is captured.
+// The record exists as soon as the shell opens it for appending, so only a
+// finished line counts.
+#[cfg(unix)]
+fn wait_for(path: &Path) -> Option<String> {
+    for _ in 0..100 {
+        if let Ok(content) = std::fs::read_to_string(path)
+            && content.ends_with('\n')
+        {
+            return Some(content);
+        }
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+    None
+}
+
+#[cfg(unix)]
+#[test]
+fn opens_worktree_in_editor_by_default() {
+    let (home, repo) = setup();
+    let wt_path = wt_new(home.path(), &repo, "feat/edit");
+    let bin = home.path().join("bin");
+    std::fs::create_dir(&bin).unwrap();
+    let record = fake_program(&bin, "fake-editor");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["open", "feat/edit", "--repo"])
+            .arg(&repo)
+            .env_remove("VISUAL")
+            .env("EDITOR", bin.join("fake-editor"));
+    });
+    assert_exit_code(&output, 0);
+    assert_stdout_empty(&output);
+    let opened = wait_for(&record).expect("editor should run");
+    assert_eq!(opened, format!("{}\n", wt_path.display()));
+}
+
+#[cfg(unix)]
+#[test]
+fn opens_worktree_in_file_manager() {
+    let (home, repo) = setup();
+    let wt_path = wt_new(home.path(), &repo, "feat/files");
+    let bin = home.path().join("bin");
+    std::fs::create_dir(&bin).unwrap();
+    let opener = if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+    let record = fake_program(&bin, opener);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["open", "feat/files", "--with", "files", "--repo"])
+            .arg(&repo)
+            .env("PATH", with_bin(&bin));
+    });
+    assert_exit_code(&output, 0);
+    assert_eq!(
+        std::fs::read_to_string(&record).unwrap(),
+        format!("{}\n", wt_path.display())
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn tmux_outside_tmux_attaches_to_session_named_after_branch() {
+    let (home, repo) = setup();
+    let wt_path = wt_new(home.path(), &repo, "release/1.2");
+    let bin = home.path().join("bin");
+    std::fs::create_dir(&bin).unwrap();
+    let record = fake_program(&bin, "tmux");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["open", "release/1.2", "--with", "tmux", "--repo"])
+            .arg(&repo)
+            .env_remove("TMUX")
+            .env("PATH", with_bin(&bin));
+    });
+    assert_exit_code(&output, 0);
+    assert_eq!(
+        std::fs::read_to_string(&record).unwrap(),
+        format!(
+            "new-session\n-A\n-s\nrelease/1-2\n-c\n{}\n",
+            wt_path.display()
+        )
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn tmux_inside_tmux_creates_window_rooted_at_worktree() {
+    let (home, repo) = setup();
+    let wt_path = wt_new(home.path(), &repo, "feat/win");
+    let bin = home.path().join("bin");
+    std::fs::create_dir(&bin).unwrap();
+    let record = fake_program(&bin, "tmux");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["open", "feat/win", "--with", "tmux", "--repo"])
+            .arg(&repo)
+            .env("TMUX", "/tmp/tmux-1000/default,1,0")
+            .env("PATH", with_bin(&bin));
+    });
+    assert_exit_code(&output, 0);
+    assert_eq!(
+        std::fs::read_to_string(&record).unwrap(),
+        format!(
+            "list-windows\n-F\n#{{window_name}}\nnew-window\n-n\nfeat/win\n-c\n{}\n",
+            wt_path.display()
+        )
+    );
+}
+
+#[test]
+fn missing_branch_fails_like_path() {
+    let (home, repo) = setup();
+
+    let path = run_wt(home.path(), |cmd| {
+        cmd.args(["path", "nope", "--repo"]).arg(&repo);
+    });
+    let open = run_wt(home.path(), |cmd| {
+        cmd.args(["open", "nope", "--repo"]).arg(&repo);
+    });
+    assert_exit_code(&path, 1);
+    assert_error(&open, 1, &String::from_utf8_lossy(&path.stderr));
+}
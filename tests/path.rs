@@ -183,3 +183,73 @@ fn errors_when_ref_matches_multiple_detached_worktrees() {
         "expected ambiguous ref error, got: {stderr}",
     );
 }
+
+#[test]
+fn by_index_uses_rows_of_last_list() {
+    let (home, repo) = setup();
+    wt_new(home.path(), &repo, "feat-one");
+    let by_index = |n: &str| {
+        run_wt(home.path(), |cmd| {
+            cmd.args(["path", "--by-index", n, "--repo"]).arg(&repo);
+        })
+    };
+
+    assert_error(
+        &by_index("2"),
+        1,
+        "no numbered worktrees, run `wt list` first\n",
+    );
+
+    let list = run_wt(home.path(), |cmd| {
+        cmd.args(["list", "--json", "--repo"]).arg(&repo);
+    });
+    let rows: Vec<serde_json::Value> = serde_json::from_slice(&list.stdout).unwrap();
+
+    let output = by_index("2");
+    assert_exit_code(&output, 0);
+    assert_eq!(
+        canonical(&PathBuf::from(
+            String::from_utf8_lossy(&output.stdout).trim()
+        )),
+        canonical(&PathBuf::from(rows[1]["path"].as_str().unwrap()))
+    );
+    assert_error(&by_index("3"), 1, "no row 3 in the last `wt list`\n");
+
+    wt_new(home.path(), &repo, "feat-two");
+    assert_error(
+        &by_index("2"),
+        1,
+        "worktrees changed since the last `wt list`, run it again\n",
+    );
+}
+
+#[test]
+fn without_name_prints_enclosing_worktree_root() {
+    let (home, repo) = setup();
+    let wt_path = wt_new(home.path(), &repo, "feat/here");
+    let nested = wt_path.join("src/deep");
+    std::fs::create_dir_all(&nested).unwrap();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.arg("path").current_dir(&nested);
+    });
+    assert_exit_code(&output, 0);
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        format!("{}\n", wt_path.display())
+    );
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.arg("path").current_dir(home.path());
+    });
+    assert_error(&output, 1, "not inside a worktree\n");
+}
+
+#[test]
+fn repo_requires_name() {
+    let (home, repo) = setup();
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["path", "--repo"]).arg(&repo);
+    });
+    assert_exit_code(&output, 2);
+}
@@ -1,3 +1,5 @@
+use std::path::{Path, PathBuf};
+
 use tempfile::TempDir;
 
 pub mod common;
@@ -42,6 +44,313 @@ fn prunes_stale_metadata_across_repos() {
     let _wt_b1_new = wt_checkout(home.path(), &repo_b, "branch-b1");
 }
 
+// More repos than prune runs at once, so some workers handle several; the
+// output must still come out in repo order.
+#[test]
+fn parallel_prune_prints_repos_in_order() {
+    let home = TempDir::new().unwrap();
+    let names: Vec<String> = (0..12).map(|i| format!("repo-{i:02}")).collect();
+    for name in &names {
+        let repo = home.path().join(name);
+        std::fs::create_dir(&repo).unwrap();
+        init_repo(&repo);
+        let stale = wt_new(home.path(), &repo, "stale");
+        let _kept = wt_new(home.path(), &repo, "kept");
+        std::fs::remove_dir_all(&stale).unwrap();
+    }
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.arg("prune");
+    });
+    assert_exit_code(&output, 0);
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let positions: Vec<usize> = names
+        .iter()
+        .map(|name| {
+            stderr
+                .find(&format!("{name}:\n"))
+                .unwrap_or_else(|| panic!("missing {name} header, got: {stderr}"))
+        })
+        .collect();
+    assert!(
+        positions.is_sorted(),
+        "repos should print in path order, got: {stderr}",
+    );
+}
+
+#[test]
+fn parallel_gone_prune_labels_fetch_output_by_repo() {
+    let home = TempDir::new().unwrap();
+    let names = ["repo-a", "repo-b"];
+    for name in names {
+        let repo = home.path().join(name);
+        let origin = home.path().join(format!("{name}-origin.git"));
+        std::fs::create_dir(&repo).unwrap();
+        init_repo(&repo);
+        init_bare_repo(&origin);
+        assert_git_success_with(&repo, |cmd| {
+            cmd.args(["remote", "add", "origin"]).arg(&origin);
+        });
+        assert_git_success(&repo, &["push", "-q", "-u", "origin", "main"]);
+        let wt_path = wt_new(home.path(), &repo, "gone-branch");
+        commit_file(&wt_path, "feature.txt");
+        assert_git_success(&wt_path, &["push", "-q", "-u", "origin", "gone-branch"]);
+        assert_git_success_with(&origin, |cmd| {
+            cmd.args(["branch", "-D", "gone-branch"]);
+        });
+    }
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["prune", "--gone"]);
+    });
+    assert_exit_code(&output, 0);
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let positions: Vec<usize> = names
+        .iter()
+        .map(|name| {
+            stderr
+                .find(&format!("{name}:\n  fetching from 'origin'\n"))
+                .unwrap_or_else(|| panic!("missing {name} section, got: {stderr}"))
+        })
+        .collect();
+    assert!(positions.is_sorted(), "got: {stderr}");
+    let deleted: Vec<&str> = stderr.lines().filter(|l| l.contains("[deleted]")).collect();
+    for name in names {
+        assert!(
+            deleted.iter().any(
+                |l| l.starts_with(&format!("{name}: ")) && l.ends_with("-> origin/gone-branch")
+            ),
+            "fetch output should carry its repo, got: {stderr}"
+        );
+    }
+    assert_eq!(deleted.len(), names.len(), "got: {stderr}");
+}
+
+#[test]
+fn ignore_untracked_prunes_worktree_with_only_untracked_files() {
+    let (home, repo) = setup();
+    let wt_path = wt_new(home.path(), &repo, "feat/built");
+    assert_git_success(&wt_path, &["commit", "--allow-empty", "-m", "built"]);
+    assert_git_success(&repo, &["merge", "--no-edit", "feat/built"]);
+    std::fs::write(wt_path.join("out.log"), "build").unwrap();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["prune", "--base", "main", "--repo"]).arg(&repo);
+    });
+    assert_exit_code(&output, 0);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(wt_path.exists(), "untracked files block by default");
+    assert!(
+        stderr.contains("skipping feat/built (merged, dirty)"),
+        "got: {stderr}"
+    );
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["prune", "--base", "main", "--ignore-untracked", "--repo"])
+            .arg(&repo);
+    });
+    assert_exit_code(&output, 0);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        !wt_path.exists(),
+        "untracked files should not block: {stderr}"
+    );
+    assert_branch_absent(&repo, "feat/built");
+}
+
+#[cfg(unix)]
+#[test]
+fn ignore_untracked_keeps_tracked_changes_made_during_prompt() {
+    use std::io::{Read, Write};
+
+    let (home, repo) = setup();
+    let wt_path = wt_new(home.path(), &repo, "feat/notes");
+    std::fs::write(wt_path.join("notes.txt"), "v1").unwrap();
+    assert_git_success(&wt_path, &["add", "notes.txt"]);
+    assert_git_success(&wt_path, &["commit", "-m", "notes"]);
+    assert_git_success(&repo, &["merge", "--no-edit", "feat/notes"]);
+    std::fs::write(wt_path.join("out.log"), "build").unwrap();
+    let (leader, follower) = pty();
+
+    let mut child = wt(home.path())
+        .args([
+            "prune",
+            "--base",
+            "main",
+            "-i",
+            "--ignore-untracked",
+            "--repo",
+        ])
+        .arg(&repo)
+        .stdin(follower)
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stderr = child.stderr.take().unwrap();
+    let mut seen = Vec::new();
+    while !String::from_utf8_lossy(&seen).contains("[y/N]") {
+        let mut buf = [0; 256];
+        let n = stderr.read(&mut buf).unwrap();
+        assert!(n > 0, "no prompt, got: {}", String::from_utf8_lossy(&seen));
+        seen.extend_from_slice(&buf[..n]);
+    }
+    std::fs::write(wt_path.join("notes.txt"), "v2").unwrap();
+    std::fs::File::from(leader).write_all(b"y\n").unwrap();
+    stderr.read_to_end(&mut seen).unwrap();
+    let status = child.wait().unwrap();
+
+    let stderr = String::from_utf8_lossy(&seen);
+    assert_eq!(status.code(), Some(1), "got: {stderr}");
+    assert!(stderr.contains("tracked files changed"), "got: {stderr}");
+    assert_eq!(
+        std::fs::read_to_string(wt_path.join("notes.txt")).unwrap(),
+        "v2"
+    );
+    assert_branch_present(&repo, "feat/notes");
+}
+
+#[test]
+fn older_than_only_removes_worktrees_with_old_heads() {
+    let (home, repo) = setup();
+    let commit = |branch: &str, date: Option<&str>| {
+        let wt_path = wt_new(home.path(), &repo, branch);
+        assert_git_success_with(&wt_path, |cmd| {
+            cmd.args(["commit", "--allow-empty", "-m", branch]);
+            if let Some(date) = date {
+                cmd.env("GIT_COMMITTER_DATE", date);
+            }
+        });
+        assert_git_success(&repo, &["merge", "--no-edit", branch]);
+        wt_path
+    };
+    let old = commit("feat/old", Some("2020-01-01T00:00:00Z"));
+    let old_dirty = commit("feat/old-dirty", Some("2020-01-02T00:00:00Z"));
+    std::fs::write(old_dirty.join("wip.txt"), "wip").unwrap();
+    let recent = commit("feat/recent", None);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["prune", "--base", "main", "--older-than", "14d", "--repo"])
+            .arg(&repo);
+    });
+    assert_exit_code(&output, 0);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!old.exists(), "old merged worktree should go: {stderr}");
+    assert_branch_absent(&repo, "feat/old");
+    assert!(old_dirty.exists(), "dirty worktrees stay at any age");
+    assert!(
+        stderr.contains("skipping feat/old-dirty (merged, dirty)"),
+        "got: {stderr}"
+    );
+    assert!(recent.exists(), "recent worktree should stay");
+    assert!(
+        stderr.contains("skipping feat/recent (merged, too recent)"),
+        "got: {stderr}"
+    );
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["prune", "--older-than", "14x", "--repo"])
+            .arg(&repo);
+    });
+    assert_exit_code(&output, 2);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("invalid duration '14x'"), "got: {stderr}");
+}
+
+#[test]
+fn custom_wt_root_is_shared_by_new_list_and_prune() {
+    let (home, repo) = setup();
+    let root = home.path().join("scratch");
+    let with_root = |args: &[&str]| {
+        run_wt(home.path(), |cmd| {
+            cmd.args(args).env("WT_ROOT", &root).current_dir(&repo);
+        })
+    };
+
+    let output = with_root(&["new", "-c", "feat/a"]);
+    assert_exit_code(&output, 0);
+    let wt_a = parse_wt_new_path(&output);
+    assert!(wt_a.starts_with(&root), "got: {}", wt_a.display());
+    let output = with_root(&["new", "-c", "feat/b"]);
+    assert_exit_code(&output, 0);
+    let wt_b = parse_wt_new_path(&output);
+    assert!(!home.path().join(".wt/worktrees").exists());
+
+    let output = with_root(&["list", "--all", "--json"]);
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("\"feat/a\""), "got: {stdout}");
+
+    std::fs::remove_dir_all(&wt_b).unwrap();
+    let output = with_root(&["prune"]);
+    assert_exit_code(&output, 0);
+    let list = assert_git_stdout_success(&repo, &["worktree", "list", "--porcelain"]);
+    assert!(
+        !list.contains("feat/b"),
+        "stale metadata should be pruned: {list}"
+    );
+    assert!(wt_a.exists());
+}
+
+#[test]
+fn relative_wt_root_is_rejected() {
+    let (home, repo) = setup();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["new", "-c", "feat/a", "--repo"])
+            .arg(&repo)
+            .env("WT_ROOT", "scratch");
+    });
+    assert_error(&output, 1, "WT_ROOT must be an absolute path\n");
+}
+
+#[test]
+fn skips_repo_locked_by_another_command() {
+    let home = TempDir::new().unwrap();
+    let repo_a = home.path().join("repo-a");
+    std::fs::create_dir(&repo_a).unwrap();
+    init_repo(&repo_a);
+    let repo_b = home.path().join("repo-b");
+    std::fs::create_dir(&repo_b).unwrap();
+    init_repo(&repo_b);
+
+    let wt_a1 = wt_new(home.path(), &repo_a, "branch-a1");
+    let _wt_a2 = wt_new(home.path(), &repo_a, "branch-a2");
+    let wt_b1 = wt_new(home.path(), &repo_b, "branch-b1");
+    let _wt_b2 = wt_new(home.path(), &repo_b, "branch-b2");
+    std::fs::remove_dir_all(&wt_a1).unwrap();
+    std::fs::remove_dir_all(&wt_b1).unwrap();
+
+    let lock = write_lock(home.path(), &repo_b, std::process::id(), "new");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.arg("prune");
+    });
+    assert!(
+        output.status.success(),
+        "a locked repo is not an error: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("skipping") && stderr.contains("repository is locked by `wt new`"),
+        "should warn about the locked repo, got: {stderr}"
+    );
+    assert!(lock.exists(), "another command's lock must be left alone");
+
+    let list_a = assert_git_stdout_success(&repo_a, &["worktree", "list", "--porcelain"]);
+    assert!(
+        !list_a.contains("prunable"),
+        "repo-a should be pruned: {list_a}"
+    );
+    let list_b = assert_git_stdout_success(&repo_b, &["worktree", "list", "--porcelain"]);
+    assert!(
+        list_b.contains("prunable"),
+        "repo-b should be untouched: {list_b}"
+    );
+}
+
 #[test]
 fn repo_flag_scopes_to_single_repo() {
     let home = TempDir::new().unwrap();
@@ -259,6 +568,215 @@ fn prunes_merged_worktree() {
     );
 }
 
+#[cfg(unix)]
+#[test]
+fn skips_worktrees_that_share_a_directory() {
+    let (home, repo, _origin) = setup_with_origin();
+
+    let wt_path = wt_new(home.path(), &repo, "merged-branch");
+    std::fs::write(wt_path.join("feature.txt"), "work").unwrap();
+    assert_git_success(&wt_path, &["add", "feature.txt"]);
+    assert_git_success(&wt_path, &["commit", "-m", "add feature"]);
+    assert_git_success(&wt_path, &["push", "-u", "origin", "merged-branch"]);
+    assert_git_success(&repo, &["merge", "merged-branch"]);
+    assert_git_success(&repo, &["push", "origin", "main"]);
+
+    let alias = wt_new(home.path(), &repo, "alias");
+    std::fs::remove_dir_all(&alias).unwrap();
+    std::os::unix::fs::symlink(&wt_path, &alias).unwrap();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.arg("prune");
+    });
+    assert!(
+        output.status.success(),
+        "wt prune should succeed: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("(same directory, see `wt doctor`)"),
+        "got: {stderr}"
+    );
+    assert!(wt_path.join("feature.txt").exists());
+    assert_branch_present(&repo, "merged-branch");
+}
+
+#[test]
+fn keep_branches_removes_worktree_but_not_branch() {
+    let (home, repo, _origin) = setup_with_origin();
+
+    let wt_path = wt_new(home.path(), &repo, "merged-kept");
+
+    std::fs::write(wt_path.join("feature.txt"), "work").unwrap();
+    assert_git_success(&wt_path, &["add", "feature.txt"]);
+    assert_git_success(&wt_path, &["commit", "-m", "add feature"]);
+    assert_git_success(&wt_path, &["push", "-u", "origin", "merged-kept"]);
+    assert_git_success(&repo, &["merge", "merged-kept"]);
+    assert_git_success(&repo, &["push", "origin", "main"]);
+    assert_git_success(&repo, &["fetch", "--prune", "origin"]);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["prune", "--keep-branches"]);
+    });
+    assert_exit_code(&output, 0);
+    assert!(
+        !wt_path.exists(),
+        "merged worktree directory should be removed"
+    );
+    assert_branch_present(&repo, "merged-kept");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("merged-kept (merged, branch kept)"),
+        "should report kept branch, got: {stderr}",
+    );
+    assert!(
+        !stderr.contains("cannot delete"),
+        "kept branches are not failures, got: {stderr}",
+    );
+}
+
+#[test]
+fn keeps_merged_branch_that_other_branches_track() {
+    let (home, repo, _origin) = setup_with_origin();
+
+    let wt_path = wt_new(home.path(), &repo, "integration");
+    std::fs::write(wt_path.join("feature.txt"), "work").unwrap();
+    assert_git_success(&wt_path, &["add", "feature.txt"]);
+    assert_git_success(&wt_path, &["commit", "-m", "add feature"]);
+    assert_git_success(&wt_path, &["push", "-u", "origin", "integration"]);
+    assert_git_success(&repo, &["branch", "--track", "feat/a", "integration"]);
+    assert_git_success(&repo, &["merge", "integration"]);
+    assert_git_success(&repo, &["push", "origin", "main"]);
+    assert_git_success(&repo, &["fetch", "--prune", "origin"]);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["prune", "--repo"]).arg(&repo);
+    });
+    assert_exit_code(&output, 0);
+    assert!(!wt_path.exists(), "merged worktree should still be removed");
+    assert_branch_present(&repo, "integration");
+    assert_eq!(
+        assert_git_stdout_success(&repo, &["config", "branch.feat/a.merge"]),
+        "refs/heads/integration\n",
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("removed integration (merged, branch kept, upstream of feat/a)"),
+        "should say why the branch stays, got: {stderr}",
+    );
+}
+
+fn merged_branch_deleted_on_origin(
+    home: &Path,
+    repo: &Path,
+    origin: &Path,
+    branch: &str,
+) -> PathBuf {
+    let wt_path = wt_new(home, repo, branch);
+    std::fs::write(wt_path.join("feature.txt"), "work").unwrap();
+    assert_git_success(&wt_path, &["add", "feature.txt"]);
+    assert_git_success(&wt_path, &["commit", "-m", "add feature"]);
+    assert_git_success(&wt_path, &["push", "-u", "origin", branch]);
+    assert_git_success(repo, &["merge", branch]);
+    assert_git_success(repo, &["push", "origin", "main"]);
+    // deleted on the server without a local fetch, so the tracking ref goes stale
+    assert_git_success(origin, &["branch", "-D", branch]);
+    wt_path
+}
+
+#[test]
+fn prune_refs_removes_stale_tracking_refs_and_config() {
+    let (home, repo, origin) = setup_with_origin();
+    let wt_path = merged_branch_deleted_on_origin(home.path(), &repo, &origin, "tidy-up");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["prune", "--gone", "--prune-refs"]);
+    });
+    assert_exit_code(&output, 0);
+    assert!(!wt_path.exists());
+    assert_branch_absent(&repo, "tidy-up");
+    let refs = assert_git_stdout_success(&repo, &["for-each-ref", "refs/remotes/origin/tidy-up"]);
+    assert!(refs.is_empty(), "stale tracking ref should be gone: {refs}");
+    let config = git(&repo)
+        .args(["config", "--get-regexp", "^branch\\.tidy-up\\."])
+        .output()
+        .unwrap();
+    assert!(config.stdout.is_empty(), "branch config should be gone");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("pruned stale refs from 'origin'"),
+        "should report remote prune, got: {stderr}",
+    );
+}
+
+#[test]
+fn prune_refs_without_fetch_prunes_tracking_refs_and_kept_branch_config() {
+    let (home, repo, origin) = setup_with_origin();
+    let wt_path = merged_branch_deleted_on_origin(home.path(), &repo, &origin, "kept-tidy");
+    assert_git_success(&repo, &["config", "branch.kept-tidy.description", "notes"]);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["prune", "--keep-branches", "--prune-refs"]);
+    });
+    assert_exit_code(&output, 0);
+    assert!(!wt_path.exists());
+    assert_branch_present(&repo, "kept-tidy");
+    let refs = assert_git_stdout_success(&repo, &["for-each-ref", "refs/remotes/origin/kept-tidy"]);
+    assert!(refs.is_empty(), "stale tracking ref should be gone: {refs}");
+    let config = git(&repo)
+        .args(["config", "--get", "branch.kept-tidy.remote"])
+        .output()
+        .unwrap();
+    assert!(
+        !config.status.success(),
+        "kept branch should lose its tracking config"
+    );
+    assert_eq!(
+        assert_git_stdout_success(&repo, &["config", "branch.kept-tidy.description"]),
+        "notes\n",
+    );
+}
+
+#[test]
+fn prune_refs_keeps_upstream_of_kept_branch_that_still_exists() {
+    let (home, repo, _origin) = setup_with_origin();
+    let wt_path = wt_new(home.path(), &repo, "still-there");
+    std::fs::write(wt_path.join("feature.txt"), "work").unwrap();
+    assert_git_success(&wt_path, &["add", "feature.txt"]);
+    assert_git_success(&wt_path, &["commit", "-m", "add feature"]);
+    assert_git_success(&wt_path, &["push", "-u", "origin", "still-there"]);
+    assert_git_success(&repo, &["merge", "still-there"]);
+    assert_git_success(&repo, &["push", "origin", "main"]);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["prune", "--keep-branches", "--prune-refs"]);
+    });
+    assert_exit_code(&output, 0);
+    assert!(!wt_path.exists());
+    assert_eq!(
+        assert_git_stdout_success(&repo, &["config", "branch.still-there.merge"]),
+        "refs/heads/still-there\n",
+    );
+}
+
+#[test]
+fn stale_tracking_refs_remain_without_prune_refs() {
+    let (home, repo, origin) = setup_with_origin();
+    merged_branch_deleted_on_origin(home.path(), &repo, &origin, "left-alone");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["prune", "--keep-branches"]);
+    });
+    assert_exit_code(&output, 0);
+    let refs =
+        assert_git_stdout_success(&repo, &["for-each-ref", "refs/remotes/origin/left-alone"]);
+    assert!(
+        !refs.is_empty(),
+        "tracking ref should be untouched without --prune-refs"
+    );
+}
+
 #[test]
 fn preserves_unmanaged_parent_when_pruning_merged_worktree() {
     let (home, repo, _origin) = setup_with_origin();
@@ -302,6 +820,72 @@ fn preserves_unmanaged_parent_when_pruning_merged_worktree() {
     assert_branch_absent(&repo, "merged-external");
 }
 
+#[test]
+fn labels_unmanaged_worktree_with_repo_name() {
+    let (home, repo, _origin) = setup_with_origin();
+    let unmanaged_wt = home.path().join("elsewhere").join("labelled-external");
+    let managed_wt = wt_new(home.path(), &repo, "labelled-managed");
+
+    assert_git_success_with(&repo, |cmd| {
+        cmd.args(["worktree", "add", "-b", "labelled-external"])
+            .arg(&unmanaged_wt)
+            .arg("main");
+    });
+    for (wt, branch) in [
+        (&unmanaged_wt, "labelled-external"),
+        (&managed_wt, "labelled-managed"),
+    ] {
+        commit_file(wt, &format!("{branch}.txt"));
+        assert_git_success(wt, &["push", "-q", "-u", "origin", branch]);
+        assert_git_success(&repo, &["merge", "-q", branch]);
+    }
+    assert_git_success(&repo, &["push", "-q", "origin", "main"]);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["prune", "--repo"]).arg(&repo);
+    });
+    assert!(
+        output.status.success(),
+        "wt prune failed: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("removed repo:labelled-external (merged)\n"),
+        "unmanaged worktree should carry its repo, got: {stderr}"
+    );
+    assert!(
+        stderr.contains("removed labelled-managed (merged)\n"),
+        "managed worktree should keep the bare branch, got: {stderr}"
+    );
+    assert!(!unmanaged_wt.exists());
+}
+
+#[test]
+fn labels_bare_repo_worktree_without_git_suffix() {
+    let (home, repo) = setup();
+    let bare = home.path().join("app.git");
+    assert_git_success_with(&repo, |cmd| {
+        cmd.args(["clone", "-q", "--bare", "."]).arg(&bare);
+    });
+    let unmanaged_wt = home.path().join("elsewhere").join("bare-external");
+    assert_git_success_with(&bare, |cmd| {
+        cmd.args(["worktree", "add", "-q", "-b", "bare-external"])
+            .arg(&unmanaged_wt)
+            .arg("main");
+    });
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["prune", "--base", "main", "--repo"]).arg(&bare);
+    });
+    assert_exit_code(&output, 0);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("removed app:bare-external (merged)\n"),
+        "got: {stderr}"
+    );
+}
+
 #[test]
 fn preserves_unmanaged_siblings_when_pruning_merged_worktree() {
     let (home, repo, _origin) = setup_with_origin();
@@ -570,6 +1154,69 @@ fn skips_squash_merged_worktree() {
     );
 }
 
+#[test]
+fn squashed_prunes_squash_merged_worktree() {
+    let (home, repo, _origin) = setup_with_origin();
+
+    let wt_path = wt_new(home.path(), &repo, "squash-branch");
+    commit_file(&wt_path, "one.txt");
+    commit_file(&wt_path, "two.txt");
+    let kept_path = wt_new(home.path(), &repo, "unmerged");
+    commit_file(&kept_path, "other.txt");
+
+    assert_git_success(&repo, &["merge", "--squash", "squash-branch"]);
+    assert_git_success(&repo, &["commit", "-m", "squash merge squash-branch"]);
+    assert_git_success(&repo, &["push", "origin", "main"]);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["prune", "--squashed"]);
+    });
+    assert!(
+        output.status.success(),
+        "wt prune --squashed should succeed: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    assert!(
+        !wt_path.exists(),
+        "squash-merged worktree should be removed"
+    );
+    assert_branch_absent(&repo, "squash-branch");
+    assert!(kept_path.exists(), "unmerged worktree should be kept");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("removed squash-branch (squash-merged)"),
+        "should label the squash match, got: {stderr}",
+    );
+}
+
+#[test]
+fn squashed_dry_run_writes_no_objects() {
+    let (home, repo, _origin) = setup_with_origin();
+
+    let wt_path = wt_new(home.path(), &repo, "squash-branch");
+    commit_file(&wt_path, "one.txt");
+    assert_git_success(&repo, &["merge", "--squash", "squash-branch"]);
+    assert_git_success(&repo, &["commit", "-m", "squash merge squash-branch"]);
+    assert_git_success(&repo, &["push", "origin", "main"]);
+    let objects_before = assert_git_stdout_success(&repo, &["count-objects"]);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["prune", "--squashed", "--dry-run"]);
+    });
+    assert_exit_code(&output, 0);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("would remove squash-branch (squash-merged)"),
+        "got: {stderr}",
+    );
+    assert!(wt_path.exists());
+    assert_eq!(
+        assert_git_stdout_success(&repo, &["count-objects"]),
+        objects_before,
+        "a dry run must not write to the object store"
+    );
+}
+
 #[test]
 fn skips_unpushed_ancestor_branch() {
     let (home, repo, _origin) = setup_with_origin();
@@ -597,6 +1244,81 @@ fn skips_unpushed_ancestor_branch() {
     );
 }
 
+#[test]
+fn prunes_against_base_from_repo_config() {
+    let (home, repo, _origin) = setup_with_origin();
+    assert_git_success(&repo, &["push", "origin", "main:develop"]);
+    let wt_path = wt_new(home.path(), &repo, "into-develop");
+    std::fs::write(wt_path.join("feature.txt"), "work").unwrap();
+    assert_git_success(&wt_path, &["add", "feature.txt"]);
+    assert_git_success(&wt_path, &["commit", "-m", "add feature"]);
+    assert_git_success(&wt_path, &["push", "-u", "origin", "into-develop"]);
+    assert_git_success(&wt_path, &["push", "origin", "into-develop:develop"]);
+    assert_git_success(&repo, &["fetch", "origin"]);
+
+    let prune = || {
+        run_wt(home.path(), |cmd| {
+            cmd.args(["prune", "--repo"]).arg(&repo);
+        })
+    };
+
+    std::fs::write(repo.join(".wt.toml"), "base = [\"develop\"]\n").unwrap();
+    let output = prune();
+    assert_exit_code(&output, 1);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("invalid .wt.toml: "), "got: {stderr}");
+    assert!(wt_path.exists());
+
+    std::fs::write(repo.join(".wt.toml"), "base = \"develop\"\n").unwrap();
+    let output = prune();
+    assert_exit_code(&output, 0);
+    assert!(
+        !wt_path.exists(),
+        "branch merged into develop should be pruned"
+    );
+    assert_branch_absent(&repo, "into-develop");
+}
+
+#[test]
+fn unused_prunes_only_worktrees_not_accessed_within_duration() {
+    let (home, repo) = setup();
+    let active = wt_new(home.path(), &repo, "feat/active");
+    let abandoned = wt_new(home.path(), &repo, "feat/abandoned");
+    commit_file(&abandoned, "unmerged.txt");
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let repo_key = canonical(&repo).display().to_string();
+    let entry = |path: &Path, accessed: u64| {
+        format!(
+            "[worktrees.\"{}\"]\nrepo = \"{repo_key}\"\naccessed = {accessed}\n",
+            canonical(path).display()
+        )
+    };
+    std::fs::write(
+        home.path().join(".local/state/wt/recency.toml"),
+        entry(&active, now) + &entry(&abandoned, now - 30 * 24 * 60 * 60),
+    )
+    .unwrap();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["prune", "--unused", "7d", "--repo"]).arg(&repo);
+    });
+
+    assert_exit_code(&output, 0);
+    assert!(active.exists(), "recently used worktree should be kept");
+    assert!(!abandoned.exists(), "unused worktree should be removed");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("removed feat/abandoned (unused, branch kept)"),
+        "got: {stderr}"
+    );
+    assert!(!stderr.contains("feat/active"), "got: {stderr}");
+    assert_branch_present(&repo, "feat/abandoned");
+}
+
 #[test]
 fn prunes_unpushed_ancestor_branch_with_explicit_base() {
     let (home, repo, _origin) = setup_with_origin();
@@ -829,6 +1551,78 @@ fn gone_prunes_upstream_gone_worktree() {
     );
 }
 
+#[test]
+fn report_file_records_gone_removal_and_counts() {
+    let (home, repo, _origin) = setup_with_origin();
+    let wt_path = wt_new(home.path(), &repo, "gone-branch");
+    commit_file(&wt_path, "feature.txt");
+    assert_git_success(&wt_path, &["push", "-u", "origin", "gone-branch"]);
+    assert_git_success(&repo, &["push", "origin", "--delete", "gone-branch"]);
+    let report = home.path().join("report.json");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["prune", "--gone", "--report-file"]).arg(&report);
+    });
+    assert_exit_code(&output, 0);
+
+    let report: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&report).unwrap()).unwrap();
+    assert_eq!(report["dry_run"], false);
+    assert_eq!(
+        report["counts"],
+        serde_json::json!({"worktrees": 1, "branches": 1, "orphans": 0, "errors": 0})
+    );
+    let repos = report["repos"].as_array().unwrap();
+    assert_eq!(repos.len(), 1);
+    let removed = &repos[0]["removed"][0];
+    assert_eq!(removed["branch"], "gone-branch");
+    assert_eq!(removed["reason"], "upstream gone");
+    assert_eq!(removed["branch_deleted"], true);
+    assert_eq!(
+        removed["path"].as_str().map(PathBuf::from),
+        Some(wt_path.clone())
+    );
+}
+
+#[test]
+fn report_file_records_repo_that_cannot_be_cleaned_up() {
+    let (home, repo, _origin) = setup_with_origin();
+    wt_new(home.path(), &repo, "feat/x");
+    std::fs::write(repo.join(".wt.toml"), "base = [\"develop\"]\n").unwrap();
+    let report = home.path().join("report.json");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["prune", "--report-file"]).arg(&report);
+    });
+    assert_exit_code(&output, 1);
+
+    let report: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&report).unwrap()).unwrap();
+    let errors = report["repos"][0]["errors"].as_array().unwrap();
+    assert!(
+        errors[0]
+            .as_str()
+            .is_some_and(|e| e.starts_with("cannot clean up: invalid .wt.toml: ")),
+        "got: {errors:?}"
+    );
+}
+
+#[test]
+fn report_file_write_failure_only_warns() {
+    let (home, _repo) = setup();
+    let report = home.path().join("missing-dir").join("report.json");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["prune", "--report-file"]).arg(&report);
+    });
+    assert_exit_code(&output, 0);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains(&format!("cannot write report {}", report.display())),
+        "got: {stderr}"
+    );
+}
+
 #[test]
 fn gone_fetches_non_origin_remote_before_classifying_gone() {
     let (home, repo) = setup();
@@ -1292,48 +2086,179 @@ fn skips_merged_when_default_branch_unknown() {
         .unwrap()
         .success();
     assert!(
-        branch_exists,
-        "branch should not be deleted when merged pruning is skipped"
+        branch_exists,
+        "branch should not be deleted when merged pruning is skipped"
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("cannot determine default branch"),
+        "should explain why merged pruning was skipped, got: {stderr}",
+    );
+}
+
+#[test]
+fn skips_base_branch_in_linked_worktree() {
+    let (home, repo, _origin) = setup_with_origin();
+
+    // Create a linked worktree on main, move primary to a side branch
+    let _wt_path = wt_new(home.path(), &repo, "side-branch");
+    assert_git_success(&repo, &["checkout", "-b", "other"]);
+    assert_git_success(&repo, &["push", "-u", "origin", "other"]);
+
+    // main is trivially an ancestor of origin/main, but must not be pruned
+    let main_wt = wt_checkout(home.path(), &repo, "main");
+
+    let output = wt_bin()
+        .args(["prune"])
+        .env("HOME", home.path())
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "wt prune should succeed: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    assert!(
+        main_wt.exists(),
+        "base branch worktree should not be pruned"
+    );
+    let branch_exists = git(&repo)
+        .args(["show-ref", "--verify", "--quiet", "refs/heads/main"])
+        .status()
+        .unwrap()
+        .success();
+    assert!(branch_exists, "base branch should not be deleted");
+}
+
+#[test]
+fn skips_base_branch_in_linked_worktree_with_configured_remote() {
+    let (home, repo) = setup();
+    let upstream = home.path().join("upstream.git");
+    init_bare_repo(&upstream);
+    assert_git_success_with(&repo, |cmd| {
+        cmd.args(["remote", "add", "upstream"]).arg(&upstream);
+    });
+    assert_git_success(&repo, &["push", "-u", "upstream", "main"]);
+    assert_git_success(&repo, &["config", "wt.remote", "upstream"]);
+
+    let _wt_path = wt_new(home.path(), &repo, "side-branch");
+    assert_git_success(&repo, &["checkout", "-b", "other"]);
+    assert_git_success(&repo, &["push", "-u", "upstream", "other"]);
+
+    // main is trivially an ancestor of upstream/main, but must not be pruned
+    let main_wt = wt_checkout(home.path(), &repo, "main");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["prune", "--repo"]).arg(&repo);
+    });
+    assert!(
+        output.status.success(),
+        "wt prune should succeed: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        !stderr.contains("skipping merged worktree pruning"),
+        "base should resolve against upstream, got: {stderr}",
+    );
+    assert!(
+        main_wt.exists(),
+        "base branch worktree should not be pruned"
     );
+    assert_branch_present(&repo, "main");
+}
+
+#[test]
+fn prunes_merged_worktree_when_only_remote_is_upstream() {
+    let (home, repo) = setup();
+    let upstream = home.path().join("upstream.git");
+    init_bare_repo(&upstream);
+    assert_git_success_with(&repo, |cmd| {
+        cmd.args(["remote", "add", "upstream"]).arg(&upstream);
+    });
+    assert_git_success(&repo, &["push", "-u", "upstream", "main"]);
+
+    let merged = wt_new(home.path(), &repo, "feat/merged");
+    commit_file(&merged, "merged.txt");
+    assert_git_success(&merged, &["push", "-u", "upstream", "feat/merged"]);
+    assert_git_success(&merged, &["push", "upstream", "feat/merged:main"]);
+    assert_git_success(&repo, &["fetch", "upstream"]);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["prune", "--repo"]).arg(&repo);
+    });
+    assert_exit_code(&output, 0);
     let stderr = String::from_utf8_lossy(&output.stderr);
     assert!(
-        stderr.contains("cannot determine default branch"),
-        "should explain why merged pruning was skipped, got: {stderr}",
+        stderr.contains("removed feat/merged (merged)"),
+        "should prune against upstream/main, got: {stderr}",
     );
+    assert!(!merged.exists());
+    assert_branch_absent(&repo, "feat/merged");
 }
 
 #[test]
-fn skips_base_branch_in_linked_worktree() {
+fn checkout_default_remote_picks_the_base_among_remotes() {
     let (home, repo, _origin) = setup_with_origin();
+    let upstream = home.path().join("upstream.git");
+    init_bare_repo(&upstream);
+    assert_git_success_with(&repo, |cmd| {
+        cmd.args(["remote", "add", "upstream"]).arg(&upstream);
+    });
+    assert_git_success(&repo, &["push", "upstream", "main"]);
 
-    // Create a linked worktree on main, move primary to a side branch
-    let _wt_path = wt_new(home.path(), &repo, "side-branch");
-    assert_git_success(&repo, &["checkout", "-b", "other"]);
-    assert_git_success(&repo, &["push", "-u", "origin", "other"]);
+    // Merged on upstream only, so only upstream/main can see it.
+    let merged = wt_new(home.path(), &repo, "feat/merged");
+    commit_file(&merged, "merged.txt");
+    assert_git_success(&merged, &["push", "-u", "origin", "feat/merged"]);
+    assert_git_success(&merged, &["push", "upstream", "feat/merged:main"]);
+    assert_git_success(&repo, &["fetch", "upstream"]);
 
-    // main is trivially an ancestor of origin/main, but must not be pruned
-    let main_wt = wt_checkout(home.path(), &repo, "main");
+    let prune = || {
+        run_wt(home.path(), |cmd| {
+            cmd.args(["prune", "--dry-run", "--repo"]).arg(&repo);
+        })
+    };
+    let stderr = String::from_utf8_lossy(&prune().stderr).to_string();
+    assert!(
+        !stderr.contains("would remove feat/merged"),
+        "origin comes first by default, got: {stderr}",
+    );
 
-    let output = wt_bin()
-        .args(["prune"])
-        .env("HOME", home.path())
-        .output()
-        .unwrap();
+    assert_git_success(&repo, &["config", "checkout.defaultRemote", "upstream"]);
+    let stderr = String::from_utf8_lossy(&prune().stderr).to_string();
+    assert!(
+        stderr.contains("would remove feat/merged (merged)"),
+        "checkout.defaultRemote should win, got: {stderr}",
+    );
+}
+
+#[test]
+fn skips_protected_branches() {
+    let (home, repo, _origin) = setup_with_origin();
+    let staging = wt_new(home.path(), &repo, "staging");
+    assert_git_success(&staging, &["push", "-u", "origin", "staging"]);
+    let merged = wt_new(home.path(), &repo, "feat/merged");
+    assert_git_success(&merged, &["push", "-u", "origin", "feat/merged"]);
+    assert_git_success(
+        &repo,
+        &["config", "wt.protectedBranches", "release/*, staging"],
+    );
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["prune", "--repo"]).arg(&repo);
+    });
     assert!(
         output.status.success(),
         "wt prune should succeed: {}",
         String::from_utf8_lossy(&output.stderr),
     );
+    assert!(staging.exists(), "protected worktree should be kept");
+    assert_branch_present(&repo, "staging");
     assert!(
-        main_wt.exists(),
-        "base branch worktree should not be pruned"
+        !merged.exists(),
+        "unprotected merged worktree should be pruned"
     );
-    let branch_exists = git(&repo)
-        .args(["show-ref", "--verify", "--quiet", "refs/heads/main"])
-        .status()
-        .unwrap()
-        .success();
-    assert!(branch_exists, "base branch should not be deleted");
 }
 
 #[test]
@@ -1666,6 +2591,34 @@ fn warns_and_skips_when_dot_git_file_is_malformed() {
     );
 }
 
+#[test]
+fn refuses_to_remove_when_dot_git_points_outside_worktrees_dir() {
+    let home = TempDir::new().unwrap();
+    let suspicious = home.path().join(".wt/worktrees/eeff33/odd-repo");
+    std::fs::create_dir_all(&suspicious).unwrap();
+    std::fs::write(suspicious.join("work.txt"), "keep me").unwrap();
+    let unrelated = home.path().join("unrelated/elsewhere");
+    std::fs::write(
+        suspicious.join(".git"),
+        format!("gitdir: {}\n", unrelated.display()),
+    )
+    .unwrap();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["prune"]);
+    });
+    assert_exit_code(&output, 0);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("points outside a repository's worktrees directory"),
+        "expected warning about unexpected gitdir, got: {stderr}",
+    );
+    assert!(
+        suspicious.join("work.txt").exists(),
+        "directory with an unexpected gitdir must not be removed"
+    );
+}
+
 #[test]
 fn base_flag_overrides_default_branch() {
     let (home, repo) = setup();
@@ -1909,6 +2862,7 @@ fn prune_removes_zombie_worktree_directory() {
         .join("abc123")
         .join("myrepo");
     std::fs::create_dir_all(&zombie).unwrap();
+    backdate(zombie.parent().unwrap());
 
     let output = wt_bin()
         .args(["prune"])
@@ -1931,6 +2885,91 @@ fn prune_removes_zombie_worktree_directory() {
     );
 }
 
+// Prune leaves `<id>`s younger than a few minutes to a `wt new` that may
+// still be filling them.
+fn backdate(dir: &Path) {
+    let status = std::process::Command::new("touch")
+        .args(["-t", "200001010000"])
+        .arg(dir)
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+#[test]
+fn removes_empty_id_directories_and_keeps_live_siblings() {
+    let (home, repo) = setup();
+    let live = wt_new(home.path(), &repo, "live-sibling");
+    let wt_root = home.path().join(".wt").join("worktrees");
+    let empty_id = wt_root.join("e0e0e0");
+    std::fs::create_dir(&empty_id).unwrap();
+    let emptied_id = wt_root.join("e1e1e1");
+    std::fs::create_dir_all(emptied_id.join("repo-a")).unwrap();
+    std::fs::create_dir_all(emptied_id.join("repo-b")).unwrap();
+    let user_id = wt_root.join("e2e2e2");
+    std::fs::create_dir(&user_id).unwrap();
+    std::fs::write(user_id.join("notes.txt"), "keep").unwrap();
+    for dir in [&empty_id, &emptied_id, &user_id] {
+        backdate(dir);
+    }
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["prune", "--dry-run"]);
+    });
+    assert_exit_code(&output, 0);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("would remove empty directory e0e0e0"),
+        "dry-run should report empty id dir, got: {stderr}",
+    );
+    assert!(empty_id.exists());
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["prune"]);
+    });
+    assert_exit_code(&output, 0);
+    assert!(!empty_id.exists(), "empty id dir should be removed");
+    assert!(
+        !emptied_id.exists(),
+        "id dir with only empty children should be removed"
+    );
+    assert!(
+        user_id.join("notes.txt").exists(),
+        "user files must be preserved"
+    );
+    assert!(live.exists(), "live worktree must be preserved");
+    assert!(live.parent().unwrap().exists());
+}
+
+#[test]
+fn keeps_freshly_created_empty_id_directory() {
+    let home = TempDir::new().unwrap();
+    let fresh_id = home.path().join(".wt/worktrees/e4e4e4");
+    std::fs::create_dir_all(fresh_id.join("repo")).unwrap();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["prune"]);
+    });
+    assert_exit_code(&output, 0);
+    assert!(
+        fresh_id.join("repo").exists(),
+        "an id dir a concurrent `wt new` may be filling must be kept"
+    );
+}
+
+#[test]
+fn keeps_empty_id_directory_containing_cwd() {
+    let home = TempDir::new().unwrap();
+    let empty_id = home.path().join(".wt/worktrees/e3e3e3");
+    std::fs::create_dir_all(&empty_id).unwrap();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["prune"]).current_dir(&empty_id);
+    });
+    assert_exit_code(&output, 0);
+    assert!(empty_id.exists(), "cwd directory must not be removed");
+}
+
 #[test]
 fn dry_run_reports_zombie_directory() {
     let home = TempDir::new().unwrap();
@@ -1942,6 +2981,7 @@ fn dry_run_reports_zombie_directory() {
         .join("deadbeef")
         .join("myrepo");
     std::fs::create_dir_all(&zombie).unwrap();
+    backdate(zombie.parent().unwrap());
 
     let output = wt_bin()
         .args(["prune", "--dry-run"])
@@ -2143,6 +3183,37 @@ fn stale_prunes_no_upstream_worktree() {
     );
 }
 
+#[test]
+fn interactive_without_terminal_prunes_as_usual() {
+    let (home, repo) = setup();
+    let wt_path = wt_new(home.path(), &repo, "no-upstream-branch");
+
+    let dry_run = run_wt(home.path(), |cmd| {
+        cmd.args(["prune", "--stale", "-i", "--dry-run"])
+            .stdin(std::process::Stdio::null());
+    });
+    assert_exit_code(&dry_run, 0);
+    let stderr = String::from_utf8_lossy(&dry_run.stderr);
+    assert!(
+        stderr.contains("would remove no-upstream-branch") && !stderr.contains("[y/N]"),
+        "dry run should only list, got: {stderr}",
+    );
+    assert!(wt_path.exists());
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["prune", "--stale", "--interactive"])
+            .stdin(std::process::Stdio::null());
+    });
+    assert_exit_code(&output, 0);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        !stderr.contains("[y/N]"),
+        "should not prompt, got: {stderr}"
+    );
+    assert!(!wt_path.exists(), "no-upstream worktree should be removed");
+    assert_branch_absent(&repo, "no-upstream-branch");
+}
+
 #[test]
 fn stale_skips_dirty_worktree() {
     let (home, repo) = setup();
@@ -2512,3 +3583,259 @@ fn stale_and_merged_with_origin() {
         "should report both merged and no upstream, got: {stderr}",
     );
 }
+
+#[test]
+fn reports_branches_that_cannot_be_deleted_after_removal() {
+    let (home, repo) = setup();
+    let wt_path = wt_new(home.path(), &repo, "shared");
+
+    let locked_path = home.path().join("locked-shared");
+    assert_git_success_with(&repo, |cmd| {
+        cmd.args(["worktree", "add", "--force", "--quiet"])
+            .arg(&locked_path)
+            .arg("shared");
+    });
+    assert_git_success_with(&repo, |cmd| {
+        cmd.args(["worktree", "lock"]).arg(&locked_path);
+    });
+
+    let output = wt_bin()
+        .args(["prune", "--stale", "--repo"])
+        .arg(&repo)
+        .env("HOME", home.path())
+        .output()
+        .unwrap();
+    assert_exit_code(&output, 1);
+    assert!(!wt_path.exists(), "worktree should still be removed");
+    assert!(locked_path.exists(), "locked worktree should be kept");
+    assert_branch_present(&repo, "shared");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("removed shared (no upstream, branch kept)"),
+        "should report the worktree removal, got: {stderr}",
+    );
+    assert!(
+        stderr.contains("cannot delete 1 branch:\n  shared: "),
+        "should summarize the branch failure, got: {stderr}",
+    );
+    let reason = stderr
+        .lines()
+        .find_map(|l| l.strip_prefix("  shared: "))
+        .unwrap();
+    assert!(
+        reason.contains("shared") && !reason.starts_with("worktree removed"),
+        "reason should be git's detail, got: {reason}",
+    );
+    assert!(
+        stderr.ends_with("cannot delete 1 branch\n"),
+        "should exit with a branch-deletion error, got: {stderr}",
+    );
+}
+
+#[test]
+fn repo_glob_prunes_each_matching_repo() {
+    let (home, outside) = setup();
+    let work = home.path().join("work");
+    let mut merged = Vec::new();
+    for name in ["alpha", "beta"] {
+        let repo = work.join(name);
+        std::fs::create_dir_all(&repo).unwrap();
+        init_repo(&repo);
+        merged.push((repo.clone(), wt_new(home.path(), &repo, "done")));
+    }
+    let outside_wt = wt_new(home.path(), &outside, "done");
+
+    let output = wt_bin()
+        .args(["prune", "--base", "main", "--repo"])
+        .arg(work.join("*"))
+        .env("HOME", home.path())
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "wt prune --repo <glob> should succeed: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+
+    for (repo, wt_path) in &merged {
+        assert!(!wt_path.exists(), "merged worktree should be removed");
+        assert_branch_absent(repo, "done");
+    }
+    assert!(
+        outside_wt.exists(),
+        "repo outside the glob should be untouched"
+    );
+    assert_branch_present(&outside, "done");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("alpha:") && stderr.contains("beta:"),
+        "output should be grouped by repo, got: {stderr}",
+    );
+}
+
+#[test]
+fn repo_glob_skips_plain_directories_inside_another_repo() {
+    let (home, outer) = setup();
+    let work = outer.join("work");
+    let inner = work.join("alpha");
+    std::fs::create_dir_all(&inner).unwrap();
+    init_repo(&inner);
+    std::fs::create_dir(work.join("notes")).unwrap();
+    let inner_wt = wt_new(home.path(), &inner, "done");
+    let outer_wt = wt_new(home.path(), &outer, "done");
+
+    let output = wt_bin()
+        .args(["prune", "--base", "main", "--repo"])
+        .arg(work.join("*"))
+        .env("HOME", home.path())
+        .output()
+        .unwrap();
+    assert_exit_code(&output, 0);
+    assert!(!inner_wt.exists(), "matched repo should be pruned");
+    assert!(outer_wt.exists(), "enclosing repo was never named");
+    assert_branch_present(&outer, "done");
+}
+
+#[test]
+fn repo_flag_is_repeatable() {
+    let (home, first) = setup();
+    let second = home.path().join("second");
+    std::fs::create_dir(&second).unwrap();
+    init_repo(&second);
+    let first_wt = wt_new(home.path(), &first, "done");
+    let second_wt = wt_new(home.path(), &second, "done");
+
+    let output = wt_bin()
+        .args(["prune", "--base", "main", "--repo"])
+        .arg(&first)
+        .arg("--repo")
+        .arg(&second)
+        .env("HOME", home.path())
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "wt prune with repeated --repo should succeed: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    assert!(!first_wt.exists());
+    assert!(!second_wt.exists());
+}
+
+#[test]
+fn repo_glob_without_matches_errors() {
+    let (home, _repo) = setup();
+    let pattern = home.path().join("missing").join("*");
+
+    let output = wt_bin()
+        .args(["prune", "--repo"])
+        .arg(&pattern)
+        .env("HOME", home.path())
+        .output()
+        .unwrap();
+    assert_error(
+        &output,
+        1,
+        &format!("no repositories match: {}\n", pattern.display()),
+    );
+}
+
+#[test]
+fn gone_groups_removed_worktrees_by_reason() {
+    let (home, repo, _origin) = setup_with_origin();
+
+    let push_branch = |branch: &str| {
+        let wt_path = wt_new(home.path(), &repo, branch);
+        std::fs::write(wt_path.join(format!("{branch}.txt")), "work").unwrap();
+        assert_git_success(&wt_path, &["add", "."]);
+        assert_git_success(&wt_path, &["commit", "-m", branch]);
+        assert_git_success(&wt_path, &["push", "-u", "origin", branch]);
+        wt_path
+    };
+    let merged = push_branch("only-merged");
+    let gone = push_branch("only-gone");
+    let both = push_branch("merged-gone");
+
+    assert_git_success(&repo, &["merge", "only-merged", "merged-gone"]);
+    assert_git_success(&repo, &["push", "origin", "main"]);
+    assert_git_success(&repo, &["push", "origin", "--delete", "only-gone"]);
+    assert_git_success(&repo, &["push", "origin", "--delete", "merged-gone"]);
+    assert_git_success(&repo, &["fetch", "--prune", "origin"]);
+
+    let output = wt_bin()
+        .args(["prune", "--gone", "--repo"])
+        .arg(&repo)
+        .env("HOME", home.path())
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "wt prune --gone should succeed: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    assert!(!merged.exists() && !gone.exists() && !both.exists());
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains(
+            "Merged:\n  only-merged\nUpstream gone:\n  only-gone\nMerged + upstream gone:\n  merged-gone\n"
+        ),
+        "should group removed worktrees by reason, got: {stderr}",
+    );
+    assert!(
+        stderr.contains("removed only-gone (upstream gone)"),
+        "per-line output should remain, got: {stderr}",
+    );
+}
+
+#[test]
+fn no_grouped_summary_without_gone() {
+    let (home, repo) = setup();
+    wt_new(home.path(), &repo, "done");
+
+    let output = wt_bin()
+        .args(["prune", "--base", "main", "--repo"])
+        .arg(&repo)
+        .env("HOME", home.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_stderr_exact(&output, "removed done (merged)\n");
+}
+
+fn commit_file(dir: &Path, name: &str) {
+    std::fs::write(dir.join(name), name).unwrap();
+    assert_git_success(dir, &["add", name]);
+    assert_git_success(dir, &["commit", "-m", name]);
+}
+
+#[cfg(unix)]
+#[test]
+fn removes_orphans_and_parents_when_wt_home_is_symlink() {
+    let home = TempDir::new().unwrap();
+    let target = symlink_wt_home(home.path());
+    let repo = home.path().join("repo");
+    std::fs::create_dir(&repo).unwrap();
+    init_repo(&repo);
+    let wt_path = wt_new(home.path(), &repo, "orphan-branch");
+    let parent_dir = wt_path.parent().unwrap().to_path_buf();
+    std::fs::remove_dir_all(&repo).unwrap();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.arg("prune");
+    });
+    assert!(
+        output.status.success(),
+        "wt prune should succeed: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    assert!(!wt_path.exists(), "orphaned worktree should be removed");
+    assert!(
+        !parent_dir.exists(),
+        "empty managed parent should be removed"
+    );
+    assert!(target.join("worktrees").exists());
+    assert!(home.path().join(".wt").is_symlink(), "~/.wt must survive");
+}
@@ -0,0 +1,96 @@
+pub mod common;
+
+use common::*;
+
+fn commit_on(wt_path: &std::path::Path, msg: &str) -> String {
+    assert_git_success(wt_path, &["commit", "--allow-empty", "-m", msg]);
+    assert_git_stdout_success(wt_path, &["rev-parse", "HEAD"])
+        .trim()
+        .to_string()
+}
+
+#[test]
+fn restores_branch_removed_by_rm() {
+    let (home, repo) = setup();
+    let wt_path = wt_new(home.path(), &repo, "feat/x");
+    let tip = commit_on(&wt_path, "work on x");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["rm", "feat/x", "--force", "--repo"]).arg(&repo);
+    });
+    assert!(output.status.success());
+    assert_branch_absent(&repo, "feat/x");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["restore", "feat/x", "--yes", "--repo"])
+            .arg(&repo);
+    });
+    assert!(
+        output.status.success(),
+        "wt restore failed: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("work on x"),
+        "should show the commit: {stderr}"
+    );
+    let restored = parse_wt_new_path(&output);
+    assert!(restored.exists());
+    let head = assert_git_stdout_success(&repo, &["rev-parse", "refs/heads/feat/x"]);
+    assert_eq!(head.trim(), tip);
+}
+
+#[test]
+fn restores_branch_from_reflog() {
+    let (home, repo) = setup();
+    assert_git_success(&repo, &["checkout", "-b", "feat/y"]);
+    let tip = commit_on(&repo, "work on y");
+    assert_git_success(&repo, &["checkout", "main"]);
+    assert_git_success(&repo, &["branch", "-D", "feat/y"]);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["restore", "feat/y", "--yes", "--repo"])
+            .arg(&repo);
+    });
+    assert!(
+        output.status.success(),
+        "wt restore failed: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    let head = assert_git_stdout_success(&repo, &["rev-parse", "refs/heads/feat/y"]);
+    assert_eq!(head.trim(), tip);
+}
+
+#[test]
+fn requires_confirmation_without_yes() {
+    let (home, repo) = setup();
+    let wt_path = wt_new(home.path(), &repo, "feat/z");
+    commit_on(&wt_path, "work on z");
+    run_wt(home.path(), |cmd| {
+        cmd.args(["rm", "feat/z", "--force", "--repo"]).arg(&repo);
+    });
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["restore", "feat/z", "--repo"]).arg(&repo);
+    });
+    assert!(!output.status.success(), "should not restore unconfirmed");
+    assert_stdout_empty(&output);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--yes"), "got: {stderr}");
+    assert_branch_absent(&repo, "feat/z");
+}
+
+#[test]
+fn errors_without_any_record() {
+    let (home, repo) = setup();
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["restore", "feat/never", "--yes", "--repo"])
+            .arg(&repo);
+    });
+    assert!(!output.status.success());
+    assert_stderr_exact(
+        &output,
+        "no record of branch 'feat/never' in wt's history or the reflog\n",
+    );
+}
@@ -88,6 +88,46 @@ fn refuses_dirty_without_force() {
     assert!(wt_path.exists());
 }
 
+#[test]
+fn ignore_untracked_removes_worktree_with_only_untracked_files() {
+    let (home, repo) = setup();
+    let untracked = wt_new(home.path(), &repo, "feat/untracked");
+    std::fs::create_dir(untracked.join("target")).unwrap();
+    std::fs::write(untracked.join("target/out.o"), "build").unwrap();
+    let modified = wt_new(home.path(), &repo, "feat/modified");
+    std::fs::write(modified.join("staged.txt"), "changed").unwrap();
+    assert_git_success(&modified, &["add", "staged.txt"]);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["rm", "feat/untracked", "--repo"]).arg(&repo);
+    });
+    assert_error(
+        &output,
+        1,
+        "worktree has local changes, use --force to remove\n",
+    );
+    assert!(untracked.exists());
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["rm", "feat/untracked", "--ignore-untracked", "--repo"])
+            .arg(&repo);
+    });
+    assert_exit_code(&output, 0);
+    assert!(!untracked.exists());
+    assert_branch_absent(&repo, "feat/untracked");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["rm", "feat/modified", "--ignore-untracked", "--repo"])
+            .arg(&repo);
+    });
+    assert_error(
+        &output,
+        1,
+        "worktree has local changes, use --force to remove\n",
+    );
+    assert!(modified.exists());
+}
+
 #[test]
 fn removes_branch_without_remote() {
     let (home, repo) = setup();
@@ -159,6 +199,65 @@ fn refuses_unmerged_branch_even_if_other_local_branch_contains_it() {
     assert!(wt_path.exists());
 }
 
+#[test]
+fn removes_branch_merged_into_base_even_when_head_is_elsewhere() {
+    let (home, repo, _origin) = setup_with_origin();
+    let wt_path = wt_new(home.path(), &repo, "merged-into-base");
+
+    std::fs::write(wt_path.join("new.txt"), "change").unwrap();
+    assert_git_success(&wt_path, &["add", "new.txt"]);
+    assert_git_success(&wt_path, &["commit", "-m", "feature"]);
+    assert_git_success(&repo, &["merge", "merged-into-base"]);
+    assert_git_success(&repo, &["push", "origin", "main"]);
+    assert_git_success(&repo, &["checkout", "-b", "side", "HEAD~1"]);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["rm", "merged-into-base", "--repo"]).arg(&repo);
+    });
+    assert_exit_code(&output, 0);
+    assert!(!wt_path.exists());
+    assert_branch_absent(&repo, "merged-into-base");
+}
+
+#[test]
+fn refuses_branch_merged_into_head_but_not_base() {
+    let (home, repo, _origin) = setup_with_origin();
+    let wt_path = wt_new(home.path(), &repo, "merged-into-head");
+
+    std::fs::write(wt_path.join("new.txt"), "change").unwrap();
+    assert_git_success(&wt_path, &["add", "new.txt"]);
+    assert_git_success(&wt_path, &["commit", "-m", "feature"]);
+    assert_git_success(&repo, &["merge", "merged-into-head"]);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["rm", "merged-into-head", "--repo"]).arg(&repo);
+    });
+    assert_error(
+        &output,
+        1,
+        "branch 'merged-into-head' is not fully merged, use --force to remove\n",
+    );
+    assert!(wt_path.exists());
+}
+
+#[test]
+fn upstream_takes_priority_over_base() {
+    let (home, repo, _origin) = setup_with_origin();
+    let wt_path = wt_new(home.path(), &repo, "pushed-not-merged");
+
+    std::fs::write(wt_path.join("new.txt"), "change").unwrap();
+    assert_git_success(&wt_path, &["add", "new.txt"]);
+    assert_git_success(&wt_path, &["commit", "-m", "feature"]);
+    assert_git_success(&wt_path, &["push", "-u", "origin", "pushed-not-merged"]);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["rm", "pushed-not-merged", "--repo"]).arg(&repo);
+    });
+    assert_exit_code(&output, 0);
+    assert!(!wt_path.exists());
+    assert_branch_absent(&repo, "pushed-not-merged");
+}
+
 #[test]
 fn removes_branch_when_remote_upstream_was_deleted() {
     let (home, repo) = setup();
@@ -218,6 +317,119 @@ fn removing_multiple_targets_reports_failures_and_removes_successes() {
     assert_branch_absent(&repo, "remove-me");
 }
 
+#[test]
+fn fail_fast_stops_at_first_failing_target() {
+    let (home, repo) = setup();
+    let good = wt_new(home.path(), &repo, "good");
+    let good2 = wt_new(home.path(), &repo, "good2");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["rm", "good", "bad", "good2", "--fail-fast", "--repo"])
+            .arg(&repo);
+    });
+    assert_exit_code(&output, 1);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.ends_with(
+            "no worktree found for: bad\n\
+             cannot remove 1 worktree, stopped at the first failure (--fail-fast)\n"
+        ),
+        "got: {stderr}"
+    );
+    assert!(
+        !good.exists(),
+        "target before the failure should be removed"
+    );
+    assert_branch_absent(&repo, "good");
+    assert!(
+        good2.exists(),
+        "target after the failure should be left alone"
+    );
+    assert_branch_present(&repo, "good2");
+
+    let good = wt_new(home.path(), &repo, "good");
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["rm", "good", "bad", "good2", "--repo"])
+            .arg(&repo);
+    });
+    assert_exit_code(&output, 1);
+    assert!(!good.exists());
+    assert!(
+        !good2.exists(),
+        "without --fail-fast every good target goes"
+    );
+}
+
+#[test]
+fn dry_run_reports_without_removing() {
+    let (home, repo) = setup();
+    let clean = wt_new(home.path(), &repo, "dry-clean");
+    let dirty = wt_new(home.path(), &repo, "dry-dirty");
+    std::fs::write(dirty.join("scratch.txt"), "wip").unwrap();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args([
+            "rm",
+            "dry-clean",
+            "dry-dirty",
+            "dry-missing",
+            "--dry-run",
+            "--repo",
+        ])
+        .arg(&repo);
+    });
+    assert_exit_code(&output, 1);
+    assert_stdout_empty(&output);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains(&format!(
+            "would remove worktree and branch 'dry-clean' (~/{})\n",
+            clean
+                .strip_prefix(canonical(home.path()))
+                .unwrap()
+                .display()
+        )),
+        "got: {stderr}"
+    );
+    assert!(
+        stderr.contains("worktree has local changes, use --force to remove\n"),
+        "got: {stderr}"
+    );
+    assert!(
+        stderr.contains("no worktree found for: dry-missing"),
+        "got: {stderr}"
+    );
+    assert!(
+        stderr.ends_with("cannot remove 2 worktrees\n"),
+        "got: {stderr}"
+    );
+    assert!(clean.exists() && dirty.exists());
+    assert_branch_present(&repo, "dry-clean");
+    assert_branch_present(&repo, "dry-dirty");
+}
+
+#[test]
+fn dry_run_merged_lists_candidates() {
+    let (home, repo, _origin) = setup_with_origin();
+    let merged = wt_new(home.path(), &repo, "dry-merged");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["rm", "--merged", "-n", "--keep-branch", "--repo"])
+            .arg(&repo);
+    });
+    assert!(
+        output.status.success(),
+        "wt rm --merged --dry-run failed: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.starts_with("would remove worktree (~/"),
+        "got: {stderr}"
+    );
+    assert!(merged.exists());
+}
+
 #[test]
 fn rejects_ambiguous_branch_name() {
     let (home, repo) = setup();
@@ -749,6 +961,43 @@ fn keep_branch_removes_worktree_but_preserves_branch() {
     );
 }
 
+#[test]
+fn keep_branch_removes_unmerged_worktree_without_force() {
+    let (home, repo) = setup();
+    let wt_path = wt_new(home.path(), &repo, "feat/unmerged");
+    assert_git_success(&wt_path, &["commit", "--allow-empty", "-m", "wip"]);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["rm", "feat/unmerged", "--keep-branch", "--repo"])
+            .arg(&repo);
+    });
+    assert_exit_code(&output, 0);
+    let display = wt_path
+        .strip_prefix(canonical(home.path()))
+        .unwrap()
+        .display()
+        .to_string();
+    assert_stderr_exact(
+        &output,
+        &format!("removed worktree (~/{display}), branch 'feat/unmerged' kept\n"),
+    );
+    assert!(!wt_path.exists());
+    assert_branch_present(&repo, "feat/unmerged");
+
+    let dirty = wt_new(home.path(), &repo, "feat/dirty");
+    std::fs::write(dirty.join("wip.txt"), "wip").unwrap();
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["rm", "feat/dirty", "--keep-branch", "--repo"])
+            .arg(&repo);
+    });
+    assert_error(
+        &output,
+        1,
+        "worktree has local changes, use --force to remove\n",
+    );
+    assert!(dirty.exists());
+}
+
 #[test]
 fn keep_branch_with_force_removes_dirty_worktree_but_preserves_branch() {
     let (home, repo) = setup();
@@ -987,3 +1236,243 @@ fn multi_target_unmerged_blocks_one() {
     assert!(unmerged_path.exists(), "unmerged worktree should remain");
     assert_branch_present(&repo, "unmerged-wt");
 }
+
+#[test]
+fn json_error_format_reports_stable_kind() {
+    let (home, repo) = setup();
+    let wt_path = wt_new(home.path(), &repo, "dirty-branch");
+
+    std::fs::write(wt_path.join("uncommitted.txt"), "changes").unwrap();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["rm", "dirty-branch", "--repo"])
+            .arg(&repo)
+            .env("WT_ERROR_FORMAT", "json");
+    });
+    assert_exit_code(&output, 1);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let error: serde_json::Value = serde_json::from_str(stderr.trim())
+        .unwrap_or_else(|e| panic!("stderr should be a JSON object ({e}), got: {stderr}"));
+    assert_eq!(error["error"], "dirty_worktree");
+    assert_eq!(
+        error["message"],
+        "worktree has local changes, use --force to remove"
+    );
+    assert_eq!(error["code"], 1);
+    assert!(wt_path.exists());
+}
+
+#[test]
+fn removes_worktree_created_from_sha_without_touching_branches() {
+    let (home, repo) = setup();
+    let sha = assert_git_stdout_success(&repo, &["rev-parse", "HEAD"]);
+    let wt_path = wt_checkout(home.path(), &repo, sha.trim());
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.arg("rm").arg(&wt_path).arg("--repo").arg(&repo);
+    });
+    assert_exit_code(&output, 0);
+    assert!(!wt_path.exists(), "worktree directory should be removed");
+    assert_branch_present(&repo, "main");
+}
+
+#[test]
+fn delete_branch_removes_named_branch_for_detached_worktree() {
+    let (home, repo) = setup();
+    let wt_path = wt_new(home.path(), &repo, "detach-me");
+    assert_git_success(&wt_path, &["checkout", "--detach"]);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.arg("rm")
+            .arg(&wt_path)
+            .args(["--delete-branch", "detach-me", "--repo"])
+            .arg(&repo);
+    });
+    assert_exit_code(&output, 0);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("removed worktree and branch 'detach-me'"),
+        "should report branch deletion, got: {stderr}",
+    );
+    assert!(!wt_path.exists());
+    assert_branch_absent(&repo, "detach-me");
+}
+
+#[test]
+fn delete_branch_refuses_worktree_on_a_branch() {
+    let (home, repo) = setup();
+    let wt_path = wt_new(home.path(), &repo, "on-branch");
+    assert_git_success(&repo, &["branch", "other"]);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["rm", "on-branch", "--delete-branch", "other", "--repo"])
+            .arg(&repo);
+    });
+    assert_exit_code(&output, 1);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("--delete-branch only applies to detached worktrees"),
+        "expected detached-only error, got: {stderr}",
+    );
+    assert!(wt_path.exists());
+    assert_branch_present(&repo, "other");
+}
+
+#[test]
+fn delete_branch_errors_when_branch_missing() {
+    let (home, repo) = setup();
+    let wt_path = wt_new(home.path(), &repo, "detach-missing");
+    assert_git_success(&wt_path, &["checkout", "--detach"]);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.arg("rm")
+            .arg(&wt_path)
+            .args(["--delete-branch", "nope", "--repo"])
+            .arg(&repo);
+    });
+    assert_error(&output, 1, "branch 'nope' not found\n");
+    assert!(wt_path.exists());
+}
+
+#[test]
+fn refuses_protected_branch_without_force() {
+    let (home, repo) = setup();
+    let wt_path = wt_new(home.path(), &repo, "staging");
+    assert_git_success(&repo, &["config", "wt.protectedBranches", "staging"]);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["rm", "staging", "--repo"]).arg(&repo);
+    });
+    assert!(!output.status.success(), "wt rm should refuse");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("branch 'staging' is protected") && stderr.contains("--force"),
+        "got: {stderr}"
+    );
+    assert!(wt_path.exists());
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["rm", "staging", "--force", "--repo"]).arg(&repo);
+    });
+    assert!(
+        output.status.success(),
+        "wt rm --force failed: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    assert!(!wt_path.exists());
+}
+
+#[test]
+fn delete_branch_refuses_protected_branch_without_force() {
+    let (home, repo) = setup();
+    let wt_path = wt_new(home.path(), &repo, "detach-protected");
+    assert_git_success(&wt_path, &["checkout", "--detach"]);
+    assert_git_success(&repo, &["branch", "staging"]);
+    assert_git_success(&repo, &["config", "wt.protectedBranches", "staging"]);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.arg("rm")
+            .arg(&wt_path)
+            .args(["--delete-branch", "staging", "--repo"])
+            .arg(&repo);
+    });
+    assert_error(
+        &output,
+        1,
+        "branch 'staging' is protected (wt.protectedBranches), use --force to remove\n",
+    );
+    assert!(wt_path.exists());
+    assert_branch_present(&repo, "staging");
+}
+
+#[test]
+fn merged_removes_every_merged_worktree() {
+    let (home, repo, _origin) = setup_with_origin();
+    let merged = wt_new(home.path(), &repo, "feat/merged");
+    let dirty = wt_new(home.path(), &repo, "feat/dirty");
+    let open = wt_new(home.path(), &repo, "feat/open");
+    std::fs::write(dirty.join("scratch.txt"), "wip").unwrap();
+    std::fs::write(open.join("open.txt"), "work").unwrap();
+    assert_git_success(&open, &["add", "open.txt"]);
+    assert_git_success(&open, &["commit", "-m", "unmerged work"]);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["rm", "--merged", "--repo"]).arg(&repo);
+    });
+    assert!(
+        output.status.success(),
+        "wt rm --merged failed: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("removed worktree and branch 'feat/merged'"),
+        "got: {stderr}"
+    );
+    assert!(
+        stderr.contains("skipping feat/dirty (local changes)"),
+        "got: {stderr}"
+    );
+    assert!(!merged.exists());
+    assert_branch_absent(&repo, "feat/merged");
+    assert!(dirty.exists());
+    assert!(open.exists());
+    assert_branch_present(&repo, "feat/open");
+    assert_branch_present(&repo, "main");
+}
+
+#[test]
+fn merged_refuses_without_base() {
+    let (home, repo) = setup();
+    let wt_path = wt_new(home.path(), &repo, "feat/merged");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["rm", "--merged", "--repo"]).arg(&repo);
+    });
+    assert_exit_code(&output, 1);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.starts_with("cannot find merged worktrees: "),
+        "got: {stderr}"
+    );
+    assert!(wt_path.exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn cwd_guard_and_cleanup_hold_when_wt_home_is_symlink() {
+    let (home, repo) = setup();
+    let target = symlink_wt_home(home.path());
+    let wt_path = wt_new(home.path(), &repo, "via-symlink");
+    let parent_dir = wt_path.parent().unwrap().to_path_buf();
+    let through_link = home
+        .path()
+        .join(".wt")
+        .join(wt_path.strip_prefix(&target).unwrap());
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["rm", "via-symlink", "--force"])
+            .current_dir(&through_link);
+    });
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("current directory is inside the worktree"),
+        "expected cwd guard error, got: {stderr}",
+    );
+    assert!(wt_path.exists());
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["rm", "via-symlink", "--repo"]).arg(&repo);
+    });
+    assert!(
+        output.status.success(),
+        "wt rm failed: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    assert!(!wt_path.exists());
+    assert!(
+        !parent_dir.exists(),
+        "empty managed parent should be removed"
+    );
+    assert!(home.path().join(".wt").is_symlink(), "~/.wt must survive");
+}
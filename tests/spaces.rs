@@ -0,0 +1,70 @@
+use serde_json::Value;
+use tempfile::TempDir;
+
+pub mod common;
+
+use common::*;
+
+// Everything under one directory with a space in it: HOME (so ~/.wt too), the
+// repo itself, and every worktree created from it.
+#[test]
+fn new_list_rm_prune_with_spaces_in_paths() {
+    let tmp = TempDir::new().unwrap();
+    let home = tmp.path().join("Mobile Documents");
+    let repo = home.join("my repo");
+    std::fs::create_dir_all(&repo).unwrap();
+    init_repo(&repo);
+
+    let kept = wt_new(&home, &repo, "feat/kept");
+    let removed = wt_new(&home, &repo, "feat/removed");
+    let orphaned = wt_new(&home, &repo, "feat/orphaned");
+    assert!(kept.to_string_lossy().contains("Mobile Documents"));
+
+    let output = run_wt(&home, |cmd| {
+        cmd.args(["list", "--json", "--repo"]).arg(&repo);
+    });
+    assert_exit_code(&output, 0);
+    let entries: Vec<Value> = serde_json::from_slice(&output.stdout).unwrap();
+    let path_of = |branch: &str| {
+        entries
+            .iter()
+            .find(|e| e["branch"] == branch)
+            .and_then(|e| e["path"].as_str())
+            .map(str::to_string)
+    };
+    assert_eq!(path_of("feat/kept").as_deref(), kept.to_str());
+    assert_eq!(
+        path_of("main"),
+        Some(canonical(&repo).to_string_lossy().into_owned())
+    );
+
+    let output = run_wt(&home, |cmd| {
+        cmd.args(["rm", "feat/removed", "--repo"]).arg(&repo);
+    });
+    assert_exit_code(&output, 0);
+    assert!(!removed.exists());
+    assert_branch_absent(&repo, "feat/removed");
+
+    // Dropping git's admin entry leaves the directory behind as an orphan
+    // that only the `.git` file's gitdir line ties back to the repo.
+    let admin = canonical(&repo).join(".git/worktrees");
+    let entry = std::fs::read_dir(&admin)
+        .unwrap()
+        .flatten()
+        .map(|e| e.path())
+        .find(|p| {
+            std::fs::read_to_string(p.join("gitdir"))
+                .is_ok_and(|g| g.trim_end() == orphaned.join(".git").to_string_lossy())
+        })
+        .expect("admin entry for the orphaned worktree");
+    std::fs::remove_dir_all(entry).unwrap();
+
+    let output = run_wt(&home, |cmd| {
+        cmd.arg("prune").current_dir(&home);
+    });
+    assert_exit_code(&output, 0);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("(orphan)"), "got: {stderr}");
+    assert!(!orphaned.exists(), "orphan should be removed: {stderr}");
+    assert!(kept.exists(), "live worktree must survive: {stderr}");
+}
@@ -0,0 +1,57 @@
+pub mod common;
+
+use common::*;
+
+fn recency(home: &std::path::Path) -> String {
+    std::fs::read_to_string(home.join(".local/state/wt/recency.toml")).unwrap_or_default()
+}
+
+#[test]
+fn state_prune_forgets_removed_worktrees() {
+    let (home, repo) = setup();
+    let kept = wt_new(home.path(), &repo, "feat/kept");
+    let gone = wt_new(home.path(), &repo, "feat/gone");
+    let kept_key = kept.canonicalize().unwrap().display().to_string();
+    let gone_key = gone.canonicalize().unwrap().display().to_string();
+
+    let before = recency(home.path());
+    assert!(before.contains(&kept_key), "got: {before}");
+    assert!(before.contains(&gone_key), "got: {before}");
+
+    assert_git_success_with(&repo, |cmd| {
+        cmd.args(["worktree", "remove"]).arg(&gone);
+    });
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["state", "prune"]);
+    });
+    assert!(
+        output.status.success(),
+        "wt state prune failed: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    assert_stdout_empty(&output);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("forgot"), "got: {stderr}");
+
+    let after = recency(home.path());
+    assert!(after.contains(&kept_key), "live worktree kept: {after}");
+    assert!(
+        !after.contains(&gone_key),
+        "removed worktree forgotten: {after}"
+    );
+}
+
+#[test]
+fn state_prune_forgets_worktrees_of_deleted_repo() {
+    let (home, repo) = setup();
+    let wt_path = wt_new(home.path(), &repo, "feat/orphan");
+    let key = wt_path.canonicalize().unwrap().display().to_string();
+    std::fs::remove_dir_all(&repo).unwrap();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["state", "prune"]);
+    });
+    assert!(output.status.success());
+    assert!(!recency(home.path()).contains(&key));
+}
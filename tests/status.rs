@@ -0,0 +1,167 @@
+use serde_json::Value;
+
+pub mod common;
+
+use common::*;
+
+fn commit_file(dir: &std::path::Path, name: &str) {
+    std::fs::write(dir.join(name), name).unwrap();
+    assert_git_success(dir, &["add", name]);
+    assert_git_success(dir, &["commit", "-m", name]);
+}
+
+fn status_json(home: &std::path::Path, repo: &std::path::Path) -> Value {
+    let output = run_wt(home, |cmd| {
+        cmd.args(["status", "--json", "--repo"]).arg(repo);
+    });
+    assert_exit_code(&output, 0);
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+fn entry<'a>(status: &'a Value, branch: &str) -> &'a Value {
+    status["worktrees"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|e| e["branch"] == branch)
+        .unwrap_or_else(|| panic!("no entry for {branch}: {status}"))
+}
+
+#[test]
+fn json_reports_dirty_merged_and_gone_worktrees() {
+    let (home, repo, _origin) = setup_with_origin();
+
+    let dirty = wt_new(home.path(), &repo, "feat/dirty");
+    commit_file(&dirty, "dirty.txt");
+    std::fs::write(dirty.join("wip.txt"), "wip").unwrap();
+
+    let merged = wt_new(home.path(), &repo, "feat/merged");
+    commit_file(&merged, "merged.txt");
+    assert_git_success(&merged, &["push", "-u", "origin", "feat/merged"]);
+    assert_git_success(&merged, &["push", "origin", "feat/merged:main"]);
+
+    let gone = wt_new(home.path(), &repo, "feat/gone");
+    commit_file(&gone, "gone.txt");
+    assert_git_success(&gone, &["push", "-u", "origin", "feat/gone"]);
+    commit_file(&gone, "ahead.txt");
+    assert_git_success(&repo, &["push", "origin", "--delete", "feat/gone"]);
+    assert_git_success(&repo, &["fetch", "--prune", "origin"]);
+
+    let status = status_json(home.path(), &repo);
+    assert_eq!(
+        status["counts"],
+        serde_json::json!({"worktrees": 4, "dirty": 1, "merged": 1, "upstream_gone": 1})
+    );
+
+    let dirty = entry(&status, "feat/dirty");
+    assert_eq!(dirty["dirty"], true);
+    assert_eq!(dirty["merged"], false);
+    assert_eq!(dirty["ahead"], Value::Null);
+
+    let merged = entry(&status, "feat/merged");
+    assert_eq!(merged["merged"], true);
+    assert_eq!(merged["ahead"], 0);
+    assert_eq!(merged["behind"], 0);
+
+    assert_eq!(entry(&status, "feat/gone")["upstream_gone"], true);
+    assert_eq!(
+        entry(&status, "main")["merged"],
+        false,
+        "the base branch is not merged into itself"
+    );
+}
+
+#[test]
+fn table_lists_worktrees_and_summarizes_on_stderr() {
+    let (home, repo) = setup();
+    let wt_path = wt_new(home.path(), &repo, "feat/dirty");
+    std::fs::write(wt_path.join("wip.txt"), "wip").unwrap();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["status", "--repo"]).arg(&repo);
+    });
+
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let row = stdout
+        .lines()
+        .find(|l| l.contains("feat/dirty"))
+        .unwrap_or_else(|| panic!("no row for feat/dirty: {stdout}"));
+    assert!(row.contains("dirty"), "got: {row}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.ends_with("2 worktrees: 1 dirty, 0 merged, 0 upstream-gone\n"),
+        "got: {stderr}"
+    );
+}
+
+#[test]
+fn all_table_fits_the_terminal_width() {
+    let (home, repo) = setup();
+    wt_new(
+        home.path(),
+        &repo,
+        "feat/a-rather-long-branch-name-for-status",
+    );
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["status", "--all"]).env("COLUMNS", "72");
+    });
+
+    assert_exit_code(&output, 0);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let row = stdout
+        .lines()
+        .find(|l| l.contains("feat/a-"))
+        .unwrap_or_else(|| panic!("no row for the branch: {stdout}"));
+    assert!(row.contains("feat/a-rathe... "), "got: {row}");
+    for line in stdout.lines() {
+        assert!(line.chars().count() <= 72, "too wide: {line:?}");
+    }
+}
+
+#[test]
+fn all_json_covers_every_repo_and_reports_unreadable_ones() {
+    let (home, repo) = setup();
+    let other = home.path().join("other");
+    std::fs::create_dir(&other).unwrap();
+    init_repo(&other);
+    let vanished = home.path().join("vanished");
+    std::fs::create_dir(&vanished).unwrap();
+    init_repo(&vanished);
+
+    let dirty = wt_new(home.path(), &repo, "feat/dirty");
+    std::fs::write(dirty.join("wip.txt"), "wip").unwrap();
+    wt_new(home.path(), &other, "feat/clean");
+    wt_new(home.path(), &vanished, "feat/orphaned");
+    std::fs::remove_dir_all(&vanished).unwrap();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["status", "--all", "--json"]);
+    });
+    assert_exit_code(&output, 0);
+    let status: Value = serde_json::from_slice(&output.stdout).unwrap();
+    let repos = status["repos"].as_array().unwrap();
+    let find = |name: &str| {
+        repos
+            .iter()
+            .find(|r| r["repo"] == name)
+            .unwrap_or_else(|| panic!("no repo {name}: {status}"))
+    };
+
+    let main_repo = find("repo");
+    assert_eq!(entry(main_repo, "feat/dirty")["dirty"], true);
+    assert_eq!(main_repo["counts"]["dirty"], 1);
+    assert!(main_repo.get("error").is_none());
+
+    let other_repo = find("other");
+    assert_eq!(entry(other_repo, "feat/clean")["dirty"], false);
+    assert_eq!(other_repo["counts"]["worktrees"], 2);
+
+    let vanished_repo = find("vanished");
+    assert!(vanished_repo["error"].is_string(), "got: {vanished_repo}");
+    assert_eq!(vanished_repo["worktrees"], serde_json::json!([]));
+
+    assert_eq!(status["counts"]["worktrees"], 4);
+    assert_eq!(status["counts"]["dirty"], 1);
+}
@@ -200,6 +200,17 @@ fn switch_alias_works() {
     assert!(path.exists(), "worktree path should exist");
 }
 
+#[test]
+fn switch_without_name_needs_a_terminal() {
+    let (home, repo) = setup();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.arg("switch").current_dir(&repo);
+    });
+
+    assert_error(&output, 1, "no branch given\n");
+}
+
 #[test]
 fn switch_skips_prunable_worktree() {
     let (home, repo) = setup();
@@ -229,6 +240,33 @@ fn switch_skips_prunable_worktree() {
     );
 }
 
+#[test]
+fn switch_path_only_prints_just_path_after_pruning() {
+    let (home, repo) = setup();
+    let path = wt_new(home.path(), &repo, "feat/quiet");
+
+    std::fs::remove_dir_all(&path).unwrap();
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::remove_dir(parent);
+    }
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["switch", "feat/quiet", "--path-only", "--repo"])
+            .arg(&repo);
+    });
+
+    assert!(output.status.success());
+    assert_stderr_empty(&output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(
+        stdout.lines().count(),
+        1,
+        "stdout should be one path: {stdout}"
+    );
+    let new_path = parse_wt_new_path(&output);
+    assert!(new_path.exists(), "worktree path should exist");
+}
+
 #[test]
 fn switch_prunes_stale_metadata_when_live_match_exists() {
     let (home, repo) = setup();
@@ -501,3 +539,313 @@ fn switch_no_fuzzy_check_for_existing_branch() {
     assert!(output.status.success());
     assert_stderr_exact(&output, "checking out 'feat/logni'\n");
 }
+
+#[test]
+fn switch_to_primary_branch_prints_repo_root() {
+    let (home, repo) = setup();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["switch", "main", "--repo"]).arg(&repo);
+    });
+
+    assert_exit_code(&output, 0);
+    assert_stderr_empty(&output);
+    let reported = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    assert_eq!(canonical(&reported), canonical(&repo));
+    assert_eq!(
+        dir_entry_count(&home.path().join(".wt").join("worktrees")),
+        0
+    );
+}
+
+#[test]
+fn switch_to_primary_branch_from_linked_worktree() {
+    let (home, repo) = setup();
+    let linked = wt_new(home.path(), &repo, "feat/elsewhere");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["switch", "main", "--repo"]).arg(&linked);
+    });
+
+    assert_exit_code(&output, 0);
+    let reported = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    assert_eq!(canonical(&reported), canonical(&repo));
+}
+
+#[test]
+fn switch_to_primary_branch_is_ambiguous_when_also_linked() {
+    let (home, repo) = setup();
+    let dup = home.path().join(".wt").join("worktrees").join("dup-main");
+    std::fs::create_dir_all(&dup).unwrap();
+    assert_git_success_with(&repo, |cmd| {
+        cmd.args(["worktree", "add", "--force", "--quiet"])
+            .arg(&dup)
+            .arg("main");
+    });
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["switch", "main", "--repo"]).arg(&repo);
+    });
+
+    assert_exit_code(&output, 1);
+    assert_stdout_empty(&output);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("ambiguous name 'main'"),
+        "expected ambiguity error, got: {stderr}",
+    );
+}
+
+#[test]
+fn switch_to_feature_branch_in_primary_is_idempotent() {
+    let (home, repo) = setup();
+    assert_git_success(&repo, &["checkout", "-b", "feat/x"]);
+
+    for _ in 0..2 {
+        let output = run_wt(home.path(), |cmd| {
+            cmd.args(["switch", "feat/x", "--repo"]).arg(&repo);
+        });
+        assert_exit_code(&output, 0);
+        assert_stderr_empty(&output);
+        let reported = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+        assert_eq!(canonical(&reported), canonical(&repo));
+    }
+    assert_eq!(
+        dir_entry_count(&home.path().join(".wt").join("worktrees")),
+        0
+    );
+}
+
+#[test]
+fn notes_when_creating_from_head_behind_upstream() {
+    let (home, repo, _origin) = setup_with_origin();
+    assert_git_success(&repo, &["commit", "--allow-empty", "-m", "upstream only"]);
+    assert_git_success(&repo, &["push", "origin", "main"]);
+    assert_git_success(&repo, &["reset", "--hard", "HEAD~1"]);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["switch", "-c", "feat/stale-start", "--repo"])
+            .arg(&repo);
+    });
+
+    assert_exit_code(&output, 0);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("note: 'main' is 1 commit behind 'origin/main'"),
+        "expected behind-upstream note, got: {stderr}",
+    );
+}
+
+#[cfg(unix)]
+fn fake_shell(home: &std::path::Path) -> (PathBuf, PathBuf) {
+    use std::os::unix::fs::PermissionsExt;
+
+    let record = home.join("shell-record");
+    let shell = home.join("fake-shell");
+    std::fs::write(
+        &shell,
+        format!(
+            "#!/bin/sh\nprintf '%s\\n%s\\n%s\\n' \"$(pwd -P)\" \"$WT_BRANCH\" \"$WT_REPO\" > '{}'\n",
+            record.display()
+        ),
+    )
+    .unwrap();
+    std::fs::set_permissions(&shell, std::fs::Permissions::from_mode(0o755)).unwrap();
+    (shell, record)
+}
+
+#[cfg(unix)]
+#[test]
+fn switch_shell_opens_shell_in_worktree() {
+    use std::os::fd::{FromRawFd, OwnedFd};
+
+    let (home, repo) = setup();
+    let (shell, record) = fake_shell(home.path());
+
+    let (mut leader, mut follower) = (0, 0);
+    let rc = unsafe {
+        libc::openpty(
+            &mut leader,
+            &mut follower,
+            std::ptr::null_mut(),
+            std::ptr::null(),
+            std::ptr::null(),
+        )
+    };
+    assert_eq!(rc, 0, "openpty failed");
+    let (_leader, follower) =
+        unsafe { (OwnedFd::from_raw_fd(leader), OwnedFd::from_raw_fd(follower)) };
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["switch", "-c", "feat/shell", "--shell", "--repo"])
+            .arg(&repo)
+            .env("SHELL", &shell)
+            .stdout(follower);
+    });
+    assert!(
+        output.status.success(),
+        "wt switch --shell failed: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+
+    let recorded = std::fs::read_to_string(&record).expect("shell should have run");
+    let mut lines = recorded.lines();
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["path", "feat/shell", "--repo"]).arg(&repo);
+    });
+    let wt_path = parse_wt_new_path(&output);
+    assert_eq!(
+        lines.next().map(PathBuf::from),
+        Some(wt_path.canonicalize().unwrap())
+    );
+    assert_eq!(lines.next(), Some("feat/shell"));
+    assert_eq!(
+        lines
+            .next()
+            .map(|l| PathBuf::from(l).canonicalize().unwrap()),
+        Some(repo.canonicalize().unwrap())
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn switch_shell_prints_path_without_tty() {
+    let (home, repo) = setup();
+    let (shell, record) = fake_shell(home.path());
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["switch", "-c", "feat/no-tty", "--shell", "--repo"])
+            .arg(&repo)
+            .env("SHELL", &shell);
+    });
+    assert!(
+        output.status.success(),
+        "wt switch --shell failed: {}",
+        String::from_utf8_lossy(&output.stderr),
+    );
+    let path = parse_wt_new_path(&output);
+    assert!(path.exists(), "printed path should be the new worktree");
+    assert!(!record.exists(), "shell should not run without a tty");
+}
+
+#[test]
+fn switch_eval_prints_quoted_shell_code() {
+    let home = tempfile::TempDir::new().unwrap();
+    let repo = home.path().join("it's a repo");
+    std::fs::create_dir_all(&repo).unwrap();
+    init_repo(&repo);
+    let path = wt_new(home.path(), &repo, "feat/eval");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["switch", "feat/eval", "--eval", "--repo"])
+            .arg(&repo);
+    });
+    assert_exit_code(&output, 0);
+    let quote =
+        |p: &std::path::Path| format!("'{}'", p.display().to_string().replace('\'', r"'\''"));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        format!(
+            "cd {}; export WT_BRANCH='feat/eval'; export WT_REPO={}\n",
+            quote(&path),
+            quote(&canonical(&repo)),
+        )
+    );
+}
+
+#[test]
+fn leading_dash_name_is_rejected() {
+    let (home, repo) = setup();
+
+    for args in [
+        &["switch", "--", "-bad"][..],
+        &["switch", "-c", "--", "-bad"][..],
+    ] {
+        let output = run_wt(home.path(), |cmd| {
+            cmd.current_dir(&repo).args(args);
+        });
+        assert_error(
+            &output,
+            1,
+            "invalid branch name '-bad': cannot start with '-'\n",
+        );
+    }
+    assert_branch_absent(&repo, "ad");
+}
+
+#[test]
+fn dash_returns_to_previous_worktree() {
+    let (home, repo) = setup();
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["switch", "-", "--repo"]).arg(&repo);
+    });
+    assert_error(&output, 1, "no previous worktree\n");
+
+    let first = wt_switch(home.path(), &repo, "feat/first");
+    let second = wt_switch(home.path(), &repo, "feat/second");
+
+    assert_eq!(
+        canonical(&wt_switch(home.path(), &repo, "-")),
+        canonical(&first)
+    );
+    // Like `cd -`, going back again returns to where we just came from.
+    assert_eq!(
+        canonical(&wt_switch(home.path(), &repo, "-")),
+        canonical(&second)
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn dismissed_picker_exits_130() {
+    use std::io::{Read, Write};
+
+    let (home, repo) = setup();
+    wt_new(home.path(), &repo, "feat/pick");
+    let (leader, follower) = pty();
+    let size = libc::winsize {
+        ws_row: 24,
+        ws_col: 80,
+        ws_xpixel: 0,
+        ws_ypixel: 0,
+    };
+    assert_eq!(
+        unsafe {
+            libc::ioctl(
+                std::os::fd::AsRawFd::as_raw_fd(&follower),
+                libc::TIOCSWINSZ,
+                &size,
+            )
+        },
+        0
+    );
+
+    let mut child = wt(home.path())
+        .args(["switch", "--repo"])
+        .arg(&repo)
+        .stdin(follower.try_clone().unwrap())
+        .stdout(follower.try_clone().unwrap())
+        .stderr(follower)
+        .spawn()
+        .unwrap();
+    // The picker asks where the cursor is before drawing; once it has an
+    // answer, Esc closes it.
+    let mut term = std::fs::File::from(leader);
+    let mut seen = Vec::new();
+    while !String::from_utf8_lossy(&seen).contains("\x1b[6n") {
+        let mut buf = [0; 256];
+        let n = term.read(&mut buf).unwrap();
+        assert!(
+            n > 0,
+            "no cursor query, got: {}",
+            String::from_utf8_lossy(&seen)
+        );
+        seen.extend_from_slice(&buf[..n]);
+    }
+    term.write_all(b"\x1b[1;1R").unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(300));
+    term.write_all(b"\x1b").unwrap();
+
+    assert_eq!(child.wait().unwrap().code(), Some(130));
+}
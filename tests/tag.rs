@@ -0,0 +1,67 @@
+pub mod common;
+
+use common::*;
+
+#[test]
+fn tags_head_of_detached_worktree() {
+    let (home, repo) = setup();
+    let sha = assert_git_stdout_success(&repo, &["rev-parse", "HEAD"])
+        .trim()
+        .to_string();
+    assert_git_success(&repo, &["commit", "--allow-empty", "-m", "later"]);
+    let wt_path = wt_checkout(home.path(), &repo, &sha);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["tag", "v-test"]).current_dir(&wt_path);
+    });
+
+    assert_exit_code(&output, 0);
+    assert_stdout_empty(&output);
+    assert_stderr_exact(&output, &format!("tagged {} as 'v-test'\n", &sha[..7]));
+    assert_eq!(
+        assert_git_stdout_success(&repo, &["rev-parse", "v-test"]).trim(),
+        sha
+    );
+}
+
+#[test]
+fn message_creates_annotated_tag() {
+    let (home, repo) = setup();
+    let sha = assert_git_stdout_success(&repo, &["rev-parse", "HEAD"])
+        .trim()
+        .to_string();
+    let wt_path = wt_checkout(home.path(), &repo, &sha);
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["tag", "good-build", "-m", "passes the suite", "--repo"])
+            .arg(&wt_path);
+    });
+
+    assert_exit_code(&output, 0);
+    assert_eq!(
+        assert_git_stdout_success(&repo, &["cat-file", "-t", "good-build"]).trim(),
+        "tag"
+    );
+    assert_eq!(
+        assert_git_stdout_success(&repo, &["rev-parse", "good-build^{commit}"]).trim(),
+        sha
+    );
+}
+
+#[test]
+fn refuses_worktree_on_a_branch() {
+    let (home, repo) = setup();
+    let wt_path = wt_new(home.path(), &repo, "feat/x");
+
+    let output = run_wt(home.path(), |cmd| {
+        cmd.args(["tag", "v-test"]).current_dir(&wt_path);
+    });
+
+    assert_error(
+        &output,
+        1,
+        "worktree is on branch 'feat/x', wt tag only tags a detached HEAD\n",
+    );
+    let tags = assert_git_stdout_success(&repo, &["tag", "--list"]);
+    assert!(tags.is_empty(), "no tag should be created, got: {tags}");
+}